@@ -1,11 +1,19 @@
 //! MCU core clock support.
 //!
-//! This module contains common definitions to abtract over the MCU core clock speed.  `avr-hal`
-//! does not support changing the clock-speed at runtime.
+//! This module contains common definitions to abtract over the MCU core clock speed.  The clock
+//! speed can only be changed at runtime through the [`SystemClock`] token, which tracks it in its
+//! type.  Note that the `arduino_hal::delay_*()` functions and the driver type aliases always
+//! assume the [`DefaultClock`][crate::DefaultClock].
 //!
 //! Most items in this module are re-exported from [`avr_hal_generic::clock`].
 pub use avr_hal_generic::clock::*;
 
+/// System clock prescaler token, with the board's default clock as the clock source.
+///
+/// Check the [`avr_hal_generic::clock::SystemClock`] documentation.
+pub type SystemClock<CLOCK = default::DefaultClock> =
+    crate::hal::clock::SystemClock<default::DefaultClock, CLOCK>;
+
 pub(crate) mod default {
     /// Default clock speed for this board.
     ///
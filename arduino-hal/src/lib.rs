@@ -77,6 +77,18 @@ compile_error!(
     "
 );
 
+#[cfg(all(
+    any(
+        feature = "embassy-time",
+        feature = "rtic-monotonic",
+        feature = "rtic-monotonic-tc3"
+    ),
+    feature = "mcu-attiny"
+))]
+compile_error!(
+    "The `embassy-time` and `rtic-monotonic` features need a 16-bit timer and are not supported on ATtiny boards."
+);
+
 /// Attribute to declare the entry point of the program
 ///
 /// Exactly one entry point must be declared in the entire dependency tree.
@@ -135,16 +147,42 @@ pub use port::Pins;
 #[cfg(feature = "mcu-atmega")]
 pub mod adc {
     pub use crate::hal::adc::{
-        channel, AdcChannel, AdcOps, AdcSettings, Channel, ClockDivider, ReferenceVoltage,
+        channel, AdcChannel, AdcOps, AdcPinOps, AdcSettings, AdcSleepOps, AdcSupplyOps,
+        AdcTemperatureOps, AdcTimerOps, AutoTrigger, Calibration, Channel, ClockDivider,
+        Differential, ReferenceVoltage, TriggerSource, VoltageInput,
     };
 
     /// Check the [`avr_hal_generic::adc::Adc`] documentation.
     pub type Adc = crate::hal::Adc<crate::DefaultClock>;
+
+    /// Check the [`avr_hal_generic::adc::Sampler`] documentation.
+    pub type Sampler<'a, TRIG> = crate::hal::adc::Sampler<'a, crate::DefaultClock, TRIG>;
+
+    /// Check the [`avr_hal_generic::adc::TimerTrigger`] documentation.
+    pub type TimerTrigger<TC> = crate::hal::adc::TimerTrigger<TC, crate::DefaultClock>;
+
+    /// Check the [`avr_hal_generic::adc::SharedAdc`] documentation.
+    pub type SharedAdc = crate::hal::adc::SharedAdc<crate::DefaultClock>;
+
+    /// Check the [`avr_hal_generic::adc::AnalogInput`] documentation.
+    pub type AnalogInput<'a, PIN> = crate::hal::adc::AnalogInput<'a, crate::DefaultClock, PIN>;
 }
 #[doc(no_inline)]
 #[cfg(feature = "mcu-atmega")]
 pub use adc::Adc;
 
+/// Analog comparator.
+#[cfg(feature = "board-selected")]
+pub mod comparator {
+    pub use crate::hal::comparator::{
+        AnalogComparator, AnalogComparatorBandgapOps, AnalogComparatorCaptureOps,
+        AnalogComparatorMuxOps, AnalogComparatorOps, Event, MuxedAdc, PositiveInput,
+    };
+}
+#[doc(no_inline)]
+#[cfg(feature = "board-selected")]
+pub use comparator::AnalogComparator;
+
 /// I2C bus controller.
 #[cfg(feature = "mcu-atmega")]
 pub mod i2c {
@@ -197,6 +235,221 @@ pub mod simple_pwm {
 
     #[cfg(feature = "mcu-attiny")]
     pub use attiny_hal::simple_pwm::*;
+
+    /// Check the [`avr_hal_generic::simple_pwm::Pwm16`] documentation.
+    pub type Pwm16<TC> = crate::hal::simple_pwm::Pwm16<TC, crate::DefaultClock>;
+
+    /// Check the [`atmega_hal::simple_pwm::Timer4HsPwm`] documentation.
+    #[cfg(any(
+        feature = "arduino-leonardo",
+        feature = "arduino-micro",
+        feature = "sparkfun-promicro"
+    ))]
+    pub type Timer4HsPwm = crate::hal::simple_pwm::Timer4HsPwm<crate::DefaultClock>;
+}
+
+/// Monotonic system time (`millis()`/`micros()`).
+///
+/// # Example
+/// ```no_run
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// // Share TC0 with PWM on d5/d6, like the Arduino core does
+/// let timer0 = Timer0Pwm::new(dp.TC0, Prescaler::Prescale64);
+/// let mut d6 = pins.d6.into_output().into_pwm(&timer0);
+/// let systime = arduino_hal::SysTime::with_pwm_timer(timer0).unwrap();
+///
+/// unsafe { avr_device::interrupt::enable() };
+/// let elapsed = systime.millis();
+///
+/// #[avr_device::interrupt(atmega328p)]
+/// fn TIMER0_OVF() {
+///     arduino_hal::time::tick();
+/// }
+/// ```
+#[cfg(feature = "board-selected")]
+pub mod time {
+    pub use crate::hal::time::{tick, MicrosInstant, MillisInstant};
+
+    /// Check the [`avr_hal_generic::time::SysTime`] documentation.
+    pub type SysTime<TC> = crate::hal::time::SysTime<TC, crate::DefaultClock>;
+}
+#[doc(no_inline)]
+#[cfg(feature = "board-selected")]
+pub use time::SysTime;
+
+/// [`embassy-time`](https://docs.rs/embassy-time) driver on `TC1`.
+///
+/// Enabled with the `embassy-time` feature.  `embassy-time` must be configured for a tick rate of
+/// the board's clock divided by 8: `tick-hz-2_000_000` for 16 MHz boards and `tick-hz-1_000_000`
+/// for 8 MHz boards.
+///
+/// # Example
+/// ```no_run
+/// #[embassy_executor::main]
+/// async fn main(_spawner: embassy_executor::Spawner) {
+///     let dp = arduino_hal::Peripherals::take().unwrap();
+///
+///     arduino_hal::time_driver::init(dp.TC1);
+///     arduino_hal::time_driver::enable_idle_sleep();
+///
+///     loop {
+///         embassy_time::Timer::after_millis(500).await;
+///     }
+/// }
+/// ```
+#[cfg(all(feature = "mcu-atmega", feature = "embassy-time"))]
+pub mod time_driver {
+    pub use crate::hal::time_driver::{enable_idle_sleep, idle, TICK_HZ};
+
+    /// Start the time driver on `TC1`.
+    ///
+    /// Check the [`atmega_hal::time_driver::init()`] documentation.
+    pub fn init(tc1: crate::pac::TC1) {
+        crate::hal::time_driver::init::<crate::DefaultClock>(tc1);
+    }
+}
+
+/// [RTIC](https://rtic.rs) monotonics.
+///
+/// Enabled with the `rtic-monotonic` feature for `TC1` and the `rtic-monotonic-tc3` feature for
+/// `TC3` (Leonardo, Micro, ProMicro and Mega boards).
+///
+/// # Example
+/// ```no_run
+/// use arduino_hal::rtic_monotonic::{DefaultMonotonic as Mono, Monotonic};
+///
+/// Mono::start(dp.TC1);
+///
+/// // ... in an RTIC task
+/// Mono::delay(100.millis()).await;
+/// ```
+#[cfg(all(
+    feature = "mcu-atmega",
+    any(feature = "rtic-monotonic", feature = "rtic-monotonic-tc3")
+))]
+pub mod rtic_monotonic {
+    pub use crate::hal::rtic_monotonic::*;
+
+    /// RTIC monotonic on `TC1`, ticking at the core clock divided by 64 (250 kHz for 16 MHz
+    /// boards).
+    #[cfg(feature = "rtic-monotonic")]
+    pub type DefaultMonotonic = crate::hal::rtic_monotonic::Tc1Monotonic<
+        crate::DefaultClock,
+        { <crate::DefaultClock as crate::hal::clock::Clock>::FREQ / 64 },
+    >;
+}
+
+/// Input capture unit of 16-bit timers.
+#[cfg(feature = "board-selected")]
+pub mod input_capture {
+    pub use crate::hal::input_capture::{
+        CaptureError, CaptureEvent, Edge, InputCaptureOps, PwmMeasurement,
+    };
+
+    /// Check the [`avr_hal_generic::input_capture::InputCapture`] documentation.
+    pub type InputCapture<TC, IMODE> =
+        crate::hal::input_capture::InputCapture<TC, IMODE, crate::DefaultClock>;
+}
+#[doc(no_inline)]
+#[cfg(feature = "board-selected")]
+pub use input_capture::InputCapture;
+
+/// Square-wave tone generation, like `tone()` in the Arduino core.
+///
+/// # Example
+/// ```no_run
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// // d11 is OC2A, so the timer toggles the pin in hardware
+/// let mut tone = arduino_hal::Tone::new(dp.TC2, pins.d11.into_output());
+/// tone.play(440.Hz()).unwrap();
+/// ```
+#[cfg(feature = "board-selected")]
+pub mod tone {
+    pub use crate::hal::tone::{Note, ToneOps};
+
+    /// Check the [`avr_hal_generic::tone::Tone`] documentation.
+    pub type Tone<TC, PIN> = crate::hal::tone::Tone<TC, crate::DefaultClock, PIN>;
+}
+#[doc(no_inline)]
+#[cfg(feature = "board-selected")]
+pub use tone::Tone;
+
+/// Hobby servo control.
+///
+/// # Example
+/// ```no_run
+/// use arduino_hal::simple_pwm::Pwm16;
+/// use arduino_hal::servo::{Servo, REFRESH_RATE};
+///
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let pwm = Pwm16::new(dp.TC1, REFRESH_RATE).unwrap();
+/// let mut servo = Servo::new(pins.d9.into_output(), &pwm);
+/// servo.set_angle(90);
+/// ```
+#[cfg(feature = "board-selected")]
+pub mod servo {
+    pub use crate::hal::servo::{
+        Servo, DEFAULT_MAX_PULSE_WIDTH, DEFAULT_MIN_PULSE_WIDTH, MAX_PULSE_WIDTH, REFRESH_RATE,
+    };
+
+    /// Check the [`avr_hal_generic::servo::ServoMux`] documentation.
+    ///
+    /// The servos are attached as downgraded pins (`pin.into_output().downgrade()`).
+    pub type ServoMux<TC, const N: usize> =
+        crate::hal::servo::ServoMux<TC, crate::DefaultClock, crate::hal::port::Dynamic, N>;
+}
+
+/// General-purpose timers.
+#[cfg(feature = "board-selected")]
+pub mod timer {
+    pub use crate::hal::timer::{
+        Duration, Event, Hertz, OutputCompare, Prescaler, TimerError, TimerMode, TimerOps,
+    };
+
+    /// Check the [`avr_hal_generic::timer::Timer`] documentation.
+    pub type Timer<TC> = crate::hal::timer::Timer<TC, crate::DefaultClock>;
+}
+#[doc(no_inline)]
+#[cfg(feature = "board-selected")]
+pub use timer::Timer;
+
+/// Internal RC oscillator calibration.
+#[cfg(feature = "board-selected")]
+pub mod osccal {
+    pub use crate::hal::osccal::{
+        calibrate, calibration, load_calibration, restore_calibration, set_calibration,
+        store_calibration, CalibrationError, CalibrationReference, CrystalReference, OscCalOps,
+        UartReference,
+    };
+    #[cfg(feature = "mcu-atmega")]
+    pub use crate::hal::osccal::{UsbSofOps, UsbSofReference};
+}
+
+/// Sleep modes and power management.
+#[cfg(feature = "board-selected")]
+pub mod power {
+    pub use crate::hal::power::{
+        disable_wake_on_interrupt, disable_wake_on_pin_change, enable_interrupts_and_sleep,
+        power_down_unused, set_async_timer_clock, set_powered, sleep, sleep_with_bod_disabled,
+        wait_for_async_timer, wake_on_interrupt, wake_on_pin_change, wake_on_watchdog,
+        AsyncTimerOps, ExternalInterrupt, ExternalInterruptOps, PinChangeOps, PowerReductionOps,
+        Sense, SleepBodOps, SleepMode, SleepOps, WakeError,
+    };
+}
+
+/// Reset cause reporting.
+///
+/// Enable the `reset-cause` feature to capture the reset cause at startup, which also recovers it
+/// from the MCUSR value passed on by Optiboot.
+#[cfg(feature = "board-selected")]
+pub mod reset {
+    pub use crate::hal::reset::{ResetCause, ResetCauseOps, ResetSource};
 }
 
 #[cfg(feature = "mcu-atmega")]
@@ -1,6 +1,15 @@
 /// Analog-to-Digial converter
+use core::cell::RefCell;
 use core::marker::PhantomData;
 
+use embedded_hal_v0 as hal_v0;
+use unwrap_infallible::UnwrapInfallible;
+
+use crate::eeprom::{Eeprom, EepromOps, OutOfBoundsError};
+use crate::port::{mode, Pin, PinOps};
+use crate::power::SleepOps;
+use crate::timer::{Event, Hertz, OutputCompare, Prescaler, TimerError, TimerMode, TimerOps};
+
 /// The division factor between the system clock frequency and the input clock to the AD converter.
 ///
 /// To get 10-bit precision, clock from 50kHz to 200kHz must be supplied.  If you need less
@@ -34,11 +43,22 @@ pub trait AdcOps<H> {
     /// Settings type for this ADC.
     type Settings: PartialEq + Copy;
 
+    /// Auto-trigger sources of this ADC.
+    type Trigger: PartialEq + Copy;
+
+    /// Voltage references of this ADC.
+    type Reference: PartialEq + Copy;
+
     /// Initialize the ADC peripheral with the specified settings.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
     fn raw_init(&mut self, settings: Self::Settings);
 
+    /// Disable the ADC peripheral.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_disable(&mut self);
+
     /// Read out the ADC data register.
     ///
     /// This method must only be called after a conversion completed.
@@ -46,6 +66,16 @@ pub trait AdcOps<H> {
     /// **Warning**: This is a low-level method and should not be called directly from user code.
     fn raw_read_adc(&self) -> u16;
 
+    /// Read out the upper byte of the ADC data register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_adc_high(&self) -> u8;
+
+    /// Left-adjust the conversion result, so the upper 8 bits are in the high byte.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_left_adjust(&mut self, enable: bool);
+
     /// Check whether the ADC is currently converting a signal.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
@@ -74,6 +104,28 @@ pub trait AdcOps<H> {
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
     fn raw_disable_channel(&mut self, channel: Self::Channel);
+
+    /// Start conversions on every event of `trigger`, or stop auto-triggering with `None`.
+    ///
+    /// In free-running mode, this also starts the first conversion.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_auto_trigger(&mut self, trigger: Option<Self::Trigger>);
+
+    /// Enable or disable the "conversion complete" (`ADC`) interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_interrupt(&mut self, enable: bool);
+
+    /// Read back the currently selected voltage reference.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_reference(&self) -> Self::Reference;
+
+    /// Select a voltage reference without touching the other settings.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_reference(&mut self, reference: Self::Reference);
 }
 
 /// Trait marking a type as an ADC channel for a certain ADC.
@@ -126,6 +178,100 @@ impl<H, ADC: AdcOps<H>> AdcChannel<H, ADC> for Channel<H, ADC> {
     }
 }
 
+/// Differential channel between two analog pins, amplified by `GAIN`
+///
+/// The ADC measures the voltage of the `POS` pin relative to the `NEG` pin.  Differential
+/// conversions are signed, so read them with [`Adc::read_differential()`].  Only the pin pairs and
+/// gains which the ADC supports are channels.
+///
+/// # Example
+/// ```
+/// let a0 = pins.pf0.into_analog_input(&mut adc);
+/// let a1 = pins.pf1.into_analog_input(&mut adc);
+///
+/// let diff = Differential::<_, _, 10>::new(&a1, &a0);
+/// let value = adc.read_differential(&diff);
+/// ```
+pub struct Differential<POS, NEG, const GAIN: u8> {
+    _pins: PhantomData<(POS, NEG)>,
+}
+
+impl<POS: PinOps, NEG: PinOps, const GAIN: u8> Differential<POS, NEG, GAIN> {
+    /// Differential channel between two pins in analog mode.
+    pub fn new(_pos: &Pin<mode::Analog, POS>, _neg: &Pin<mode::Analog, NEG>) -> Self {
+        Self { _pins: PhantomData }
+    }
+
+    /// The gain of the channel.
+    pub const fn gain(&self) -> u8 {
+        GAIN
+    }
+}
+
+/// Per-device calibration of the bandgap reference and the temperature sensor
+///
+/// The nominal values for each chip are taken from its datasheet, but the actual bandgap voltage
+/// varies by a few percent and the temperature sensor offset by several degrees between devices.
+/// For accurate readings, measure them once, e.g. with [`Adc::calibrate_vcc()`] and
+/// [`Adc::calibrate_temperature()`], and keep the result in the EEPROM.
+///
+/// # Example
+/// ```
+/// let mut eeprom = atmega_hal::Eeprom::new(dp.EEPROM);
+///
+/// if let Some(calibration) = Calibration::load(&eeprom, 0) {
+///     adc.set_calibration(calibration);
+/// } else {
+///     // Supply voltage measured with a multimeter
+///     adc.calibrate_vcc(4980);
+///     adc.calibration().store(&mut eeprom, 0).unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    /// Voltage of the bandgap reference in millivolts.
+    pub bandgap_millivolts: u16,
+    /// Reading of the temperature sensor at 25 °C.
+    pub temperature_offset: u16,
+    /// Change of the temperature sensor reading in 1/1000 LSB per °C.
+    pub temperature_slope: u16,
+}
+
+impl Calibration {
+    /// Number of EEPROM bytes used by a stored calibration record.
+    pub const SIZE: u16 = 7;
+
+    const MAGIC: u8 = 0xca;
+
+    /// Load a calibration record from the EEPROM at `offset`.
+    ///
+    /// Returns `None` if no record was stored there.
+    pub fn load<H, EEPROM: EepromOps<H>>(eeprom: &Eeprom<H, EEPROM>, offset: u16) -> Option<Self> {
+        let mut buf = [0; Self::SIZE as usize];
+        eeprom.read(offset, &mut buf).ok()?;
+        if buf[0] != Self::MAGIC {
+            return None;
+        }
+        Some(Self {
+            bandgap_millivolts: u16::from_le_bytes([buf[1], buf[2]]),
+            temperature_offset: u16::from_le_bytes([buf[3], buf[4]]),
+            temperature_slope: u16::from_le_bytes([buf[5], buf[6]]),
+        })
+    }
+
+    /// Store this calibration record in the EEPROM at `offset`.
+    pub fn store<H, EEPROM: EepromOps<H>>(
+        &self,
+        eeprom: &mut Eeprom<H, EEPROM>,
+        offset: u16,
+    ) -> Result<(), OutOfBoundsError> {
+        let [b0, b1] = self.bandgap_millivolts.to_le_bytes();
+        let [o0, o1] = self.temperature_offset.to_le_bytes();
+        let [s0, s1] = self.temperature_slope.to_le_bytes();
+        eeprom.write(offset, &[Self::MAGIC, b0, b1, o0, o1, s0, s1])
+    }
+}
+
 /// Analog-to-Digital Converter
 /// ```
 /// let dp = atmega_hal::Peripherals::take().unwrap();
@@ -144,6 +290,7 @@ impl<H, ADC: AdcOps<H>> AdcChannel<H, ADC> for Channel<H, ADC> {
 pub struct Adc<H, ADC: AdcOps<H>, CLOCK> {
     p: ADC,
     reading_channel: Option<ADC::Channel>,
+    calibration: Option<Calibration>,
     _clock: PhantomData<CLOCK>,
     _h: PhantomData<H>,
 }
@@ -153,17 +300,37 @@ where
     ADC: AdcOps<H>,
     CLOCK: crate::clock::Clock,
 {
-    pub fn new(p: ADC, settings: ADC::Settings) -> Self {
+    /// Enable the ADC with the given settings.
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
+    pub fn new(p: ADC, settings: ADC::Settings) -> Self
+    where
+        ADC: crate::power::PowerReductionOps<H>,
+    {
         let mut adc = Self {
             p,
             reading_channel: None,
+            calibration: None,
             _clock: PhantomData,
             _h: PhantomData,
         };
+        adc.p.raw_set_powered(true);
         adc.initialize(settings);
         adc
     }
 
+    /// Disable the ADC and return the peripheral.
+    ///
+    /// The clock of the peripheral is stopped in the Power Reduction Register, if available.
+    pub fn release(mut self) -> ADC
+    where
+        ADC: crate::power::PowerReductionOps<H>,
+    {
+        self.p.raw_disable();
+        self.p.raw_set_powered(false);
+        self.p
+    }
+
     pub fn initialize(&mut self, settings: ADC::Settings) {
         self.p.raw_init(settings);
     }
@@ -178,6 +345,11 @@ where
         self.p.raw_disable_channel(pin.channel());
     }
 
+    #[inline]
+    pub(crate) fn peripheral_mut(&mut self) -> &mut ADC {
+        &mut self.p
+    }
+
     pub fn read_blocking<PIN: AdcChannel<H, ADC>>(&mut self, pin: &PIN) -> u16 {
         // assert!(self.reading_channel.is_none());
         self.p.raw_set_channel(pin.channel());
@@ -186,6 +358,108 @@ where
         self.p.raw_read_adc()
     }
 
+    /// Read a differential channel.
+    ///
+    /// The result is in the range `-512..=511`, relative to the reference voltage divided by the
+    /// gain of the channel.
+    pub fn read_differential<POS, NEG, const GAIN: u8>(
+        &mut self,
+        channel: &Differential<POS, NEG, GAIN>,
+    ) -> i16
+    where
+        Differential<POS, NEG, GAIN>: AdcChannel<H, ADC>,
+    {
+        let value = self.read_blocking(channel);
+        // Sign-extend the 10-bit two's complement result.
+        ((value << 6) as i16) >> 6
+    }
+
+    /// Read a channel with `extra_bits` of additional resolution.
+    ///
+    /// This takes `4^extra_bits` samples and decimates their sum, so the result has
+    /// `10 + extra_bits` bits.  At most 6 extra bits are supported, larger values are treated as
+    /// 6, which gives a 16-bit result from 4096 conversions.  Oversampling only gains
+    /// resolution when the signal carries at least 1 LSB of noise; a perfectly steady input gives
+    /// the same result as a single conversion, just scaled.
+    ///
+    /// # Example
+    /// ```
+    /// // 12-bit result, built from 16 conversions
+    /// let value = adc.read_oversampled(&a0, 2);
+    /// ```
+    pub fn read_oversampled<PIN: AdcChannel<H, ADC>>(&mut self, pin: &PIN, extra_bits: u8) -> u16 {
+        let extra_bits = extra_bits.min(6);
+        let samples = 1u16 << (2 * extra_bits);
+        (self.sum_samples(pin, samples) >> extra_bits) as u16
+    }
+
+    /// Read a channel as the average of `samples` conversions.
+    ///
+    /// This reduces noise but, unlike [`Adc::read_oversampled()`], keeps the 10-bit range.  Zero
+    /// samples are treated as one.
+    pub fn read_averaged<PIN: AdcChannel<H, ADC>>(&mut self, pin: &PIN, samples: u16) -> u16 {
+        let samples = samples.max(1);
+        (self.sum_samples(pin, samples) / u32::from(samples)) as u16
+    }
+
+    fn sum_samples<PIN: AdcChannel<H, ADC>>(&mut self, pin: &PIN, samples: u16) -> u32 {
+        (0..samples)
+            .map(|_| u32::from(self.read_blocking(pin)))
+            .sum()
+    }
+
+    /// Read a channel while the CPU is in ADC Noise Reduction sleep.
+    ///
+    /// Stopping the CPU and the I/O clocks during the conversion removes most of the digital
+    /// noise from the measurement.  The CPU wakes up again on the ADC conversion complete
+    /// interrupt, so an `ADC` interrupt handler must be defined (it can be empty).  Other
+    /// interrupts may wake the CPU early, in which case it goes back to sleep until the conversion
+    /// is done.
+    ///
+    /// Interrupts are enabled while sleeping and restored to their previous state afterwards.
+    ///
+    /// # Safety
+    /// Interrupt handlers run while this method sleeps, so it must not be called inside a critical
+    /// section.  See [`avr_device::interrupt::enable()`].
+    ///
+    /// # Example
+    /// ```
+    /// let mut cpu = dp.CPU;
+    /// let value = unsafe { adc.read_noise_reduced(&a0, &mut cpu) };
+    ///
+    /// #[avr_device::interrupt(atmega328p)]
+    /// fn ADC() {}
+    /// ```
+    pub unsafe fn read_noise_reduced<PIN, CPU>(&mut self, pin: &PIN, cpu: &mut CPU) -> u16
+    where
+        PIN: AdcChannel<H, ADC>,
+        CPU: AdcSleepOps<H, ADC>,
+    {
+        self.reading_channel = None;
+        while self.p.raw_is_converting() {}
+
+        let irq_flag = avr_device::interrupt::disable_save();
+        self.p.raw_set_channel(pin.channel());
+        self.p.raw_set_interrupt(true);
+        cpu.raw_set_mode(CPU::NOISE_REDUCTION);
+        cpu.raw_set_enabled(true);
+
+        // Start the conversion explicitly, so ADSC tells whether it has completed after waking up.
+        self.p.raw_start_conversion();
+        while self.p.raw_is_converting() {
+            // SAFETY: The caller guarantees that this is not inside a critical section.
+            crate::power::enable_interrupts_and_sleep();
+            avr_device::interrupt::disable();
+        }
+
+        cpu.raw_set_enabled(false);
+        self.p.raw_set_interrupt(false);
+        // SAFETY: This restores the interrupt state from before entering this method.
+        unsafe { avr_device::interrupt::restore(irq_flag) };
+
+        self.p.raw_read_adc()
+    }
+
     pub fn read_nonblocking<PIN: AdcChannel<H, ADC>>(
         &mut self,
         pin: &PIN,
@@ -214,6 +488,636 @@ where
     }
 }
 
+impl<H, ADC, CLOCK> Adc<H, ADC, CLOCK>
+where
+    ADC: AdcOps<H>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Time for the bandgap to start up and for the AREF capacitor to settle after switching the
+    /// reference.
+    const SETTLING_MICROS: u32 = 2000;
+
+    /// Switch to `reference` and wait for it to settle.  Returns whether the reference changed.
+    fn switch_reference(&mut self, reference: ADC::Reference) -> bool {
+        self.reading_channel = None;
+        while self.p.raw_is_converting() {}
+
+        if self.p.raw_reference() == reference {
+            return false;
+        }
+        self.p.raw_set_reference(reference);
+        avr_device::asm::delay_cycles(CLOCK::FREQ / 1_000_000 * Self::SETTLING_MICROS);
+        true
+    }
+
+    /// Run a conversion on the currently selected channel and throw away the result.
+    fn discard_conversion(&mut self) {
+        self.p.raw_start_conversion();
+        while self.p.raw_is_converting() {}
+    }
+
+    /// Convert `channel` against `reference`, giving the reference and the input time to settle.
+    ///
+    /// The first conversion after switching is discarded.  The previous reference is restored
+    /// afterwards.
+    fn read_settled(&mut self, channel: ADC::Channel, reference: ADC::Reference) -> u16 {
+        self.reading_channel = None;
+        while self.p.raw_is_converting() {}
+
+        let previous = self.p.raw_reference();
+        self.p.raw_set_channel(channel);
+        // The bandgap and the temperature sensor need the settling time even if the reference
+        // stays the same, so always wait here.
+        if !self.switch_reference(reference) {
+            avr_device::asm::delay_cycles(CLOCK::FREQ / 1_000_000 * Self::SETTLING_MICROS);
+        }
+        self.discard_conversion();
+
+        self.p.raw_start_conversion();
+        while self.p.raw_is_converting() {}
+        let value = self.p.raw_read_adc();
+
+        self.p.raw_set_reference(previous);
+        value
+    }
+
+    /// Select a different voltage reference for all following conversions.
+    ///
+    /// When the reference actually changes, this waits for the voltage on the AREF pin to settle
+    /// and discards the first conversion, whose result would be inaccurate.
+    pub fn set_reference(&mut self, reference: ADC::Reference) {
+        if self.switch_reference(reference) {
+            self.discard_conversion();
+        }
+    }
+
+    /// Read a channel against the given voltage reference.
+    ///
+    /// The reference stays selected afterwards, so reading several channels against the same
+    /// reference only pays the settling time once.  This makes it possible to read e.g. a sensor
+    /// against the internal reference and a potentiometer against AVcc in the same loop.
+    ///
+    /// # Example
+    /// ```
+    /// let sensor = adc.read_with_reference(&a0, ReferenceVoltage::Internal);
+    /// let poti = adc.read_with_reference(&a1, ReferenceVoltage::AVcc);
+    /// ```
+    pub fn read_with_reference<PIN: AdcChannel<H, ADC>>(
+        &mut self,
+        pin: &PIN,
+        reference: ADC::Reference,
+    ) -> u16 {
+        if self.switch_reference(reference) {
+            self.p.raw_set_channel(pin.channel());
+            self.discard_conversion();
+        }
+        self.read_blocking(pin)
+    }
+
+    /// Read the upper 8 bits of a conversion.
+    ///
+    /// The result is left-adjusted in the data register, so only a single byte has to be read.
+    /// 8-bit results stay accurate with a faster ADC clock: With [`ClockDivider::Factor16`] at
+    /// 16 MHz, the ADC runs at 1 MHz and reaches about 76k samples per second.
+    pub fn read_blocking_8bit<PIN: AdcChannel<H, ADC>>(&mut self, pin: &PIN) -> u8 {
+        self.p.raw_set_channel(pin.channel());
+        self.p.raw_set_left_adjust(true);
+        self.p.raw_start_conversion();
+        while self.p.raw_is_converting() {}
+        let value = self.p.raw_read_adc_high();
+        self.p.raw_set_left_adjust(false);
+        value
+    }
+}
+
+impl<H, ADC, CLOCK> Adc<H, ADC, CLOCK>
+where
+    ADC: AdcSupplyOps<H>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Use a per-device calibration instead of the nominal values from the datasheet.
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = Some(calibration);
+    }
+
+    /// The calibration currently in use.
+    pub fn calibration(&self) -> Calibration {
+        self.calibration.unwrap_or(ADC::CALIBRATION)
+    }
+
+    /// Measure the supply voltage in millivolts.
+    ///
+    /// This converts the internal bandgap reference against the supply voltage, so it works
+    /// without any external components.  The accuracy depends on the bandgap voltage in the
+    /// [`Calibration`].
+    ///
+    /// The reference is switched to the supply voltage for the measurement and restored
+    /// afterwards.  Waiting for the bandgap to settle makes this take a few milliseconds.
+    pub fn read_vcc_millivolts(&mut self) -> u16 {
+        let reading = self.read_settled(ADC::BANDGAP, ADC::SUPPLY_REFERENCE);
+        let millivolts =
+            u32::from(self.calibration().bandgap_millivolts) * 1024 / u32::from(reading.max(1));
+        millivolts.min(u32::from(u16::MAX)) as u16
+    }
+
+    /// Calibrate the bandgap voltage against a known supply voltage.
+    ///
+    /// Measure the actual supply voltage, e.g. with a multimeter, and pass it in millivolts.
+    pub fn calibrate_vcc(&mut self, vcc_millivolts: u16) {
+        let reading = self.read_settled(ADC::BANDGAP, ADC::SUPPLY_REFERENCE);
+        let mut calibration = self.calibration();
+        calibration.bandgap_millivolts =
+            (u32::from(vcc_millivolts) * u32::from(reading) / 1024) as u16;
+        self.calibration = Some(calibration);
+    }
+}
+
+impl<H, ADC, CLOCK> Adc<H, ADC, CLOCK>
+where
+    ADC: AdcTemperatureOps<H>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Measure the chip temperature in °C.
+    ///
+    /// Without a per-device [`Calibration`], the result can be off by ±10 °C.
+    pub fn read_temperature_celsius(&mut self) -> i16 {
+        let reading = self.read_settled(ADC::TEMPERATURE, ADC::TEMPERATURE_REFERENCE);
+        let calibration = self.calibration();
+        let delta = i32::from(reading) - i32::from(calibration.temperature_offset);
+        (25 + delta * 1000 / i32::from(calibration.temperature_slope.max(1))) as i16
+    }
+
+    /// Calibrate the temperature sensor offset against a known temperature.
+    ///
+    /// The slope is kept at its current value.  For best results, run this with the chip at a
+    /// known ambient temperature after it has been idle for a while.
+    pub fn calibrate_temperature(&mut self, celsius: i16) {
+        let reading = self.read_settled(ADC::TEMPERATURE, ADC::TEMPERATURE_REFERENCE);
+        let mut calibration = self.calibration();
+        let delta = (i32::from(celsius) - 25) * i32::from(calibration.temperature_slope) / 1000;
+        calibration.temperature_offset = (i32::from(reading) - delta) as u16;
+        self.calibration = Some(calibration);
+    }
+}
+
+/// Internal trait for timers whose compare match can trigger ADC conversions.
+///
+/// **Prefer using the [`TimerTrigger`] API instead of this trait.**
+pub trait AdcTimerOps<H, ADC: AdcOps<H>>: TimerOps<H> {
+    /// Auto-trigger source of the compare match.
+    const TRIGGER: ADC::Trigger;
+
+    /// The compare unit which triggers the ADC.
+    const COMPARE: OutputCompare;
+}
+
+/// Internal trait for the sleep controller of MCUs whose ADC supports noise reduction sleep.
+///
+/// **Prefer using [`Adc::read_noise_reduced()`] instead of this trait.**
+pub trait AdcSleepOps<H, ADC: AdcOps<H>>: SleepOps<H> {
+    /// The ADC Noise Reduction sleep mode.
+    const NOISE_REDUCTION: Self::Mode;
+}
+
+/// Internal trait for ADCs which can measure their own supply voltage.
+///
+/// **Prefer using [`Adc::read_vcc_millivolts()`] instead of this trait.**
+pub trait AdcSupplyOps<H>: AdcOps<H> {
+    /// The reference which is connected to the supply voltage.
+    const SUPPLY_REFERENCE: Self::Reference;
+
+    /// The internal bandgap reference channel.
+    const BANDGAP: Self::Channel;
+
+    /// Nominal calibration values from the datasheet.
+    ///
+    /// The temperature fields are unused on chips without a temperature sensor.
+    const CALIBRATION: Calibration;
+}
+
+/// Internal trait for ADCs with an internal temperature sensor.
+///
+/// **Prefer using [`Adc::read_temperature_celsius()`] instead of this trait.**
+pub trait AdcTemperatureOps<H>: AdcSupplyOps<H> {
+    /// The internal reference which the temperature sensor must be measured against.
+    const TEMPERATURE_REFERENCE: Self::Reference;
+
+    /// The temperature sensor channel.
+    const TEMPERATURE: Self::Channel;
+}
+
+/// Event source for [`Sampler`].
+///
+/// This is implemented for the auto-trigger sources of each ADC and for [`TimerTrigger`].
+pub trait TriggerSource<H, ADC: AdcOps<H>> {
+    /// The auto-trigger source to select.
+    fn source(&self) -> ADC::Trigger;
+
+    /// Prepare the trigger events before sampling starts.
+    fn start(&mut self) {}
+
+    /// Prepare the next trigger event after a conversion completed.
+    fn rearm(&mut self) {}
+}
+
+/// Timer which triggers ADC conversions at a fixed sample rate.
+///
+/// The timer runs in CTC mode and its compare match starts a conversion.  The sample rate should be
+/// lower than the conversion rate of the ADC, which is 1/13 of the ADC clock.
+///
+/// # Example
+/// ```
+/// // 8 kHz sample rate
+/// let trigger = TimerTrigger::<_, _, CoreClock>::new(dp.TC0, 8.kHz()).unwrap();
+/// ```
+pub struct TimerTrigger<H, TC, CLOCK> {
+    timer: TC,
+    prescaler: Prescaler,
+    top: u16,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC, CLOCK> TimerTrigger<H, TC, CLOCK>
+where
+    TC: TimerOps<H>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Run `timer` with a compare match at `rate`.
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
+    pub fn new(mut timer: TC, rate: Hertz) -> Result<Self, TimerError>
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        let hz = rate.to_Hz();
+        if hz == 0 {
+            return Err(TimerError::PeriodTooLong);
+        }
+        let (prescaler, top) = Prescaler::for_cycles(CLOCK::FREQ / hz, TC::PRESCALERS, TC::MAX)?;
+
+        timer.raw_set_powered(true);
+        timer.raw_set_clock(None);
+        timer.raw_set_mode(TimerMode::Ctc);
+        timer.raw_set_compare(OutputCompare::A, top);
+        timer.raw_write_counter(0);
+        timer.raw_set_clock(Some(prescaler));
+
+        Ok(Self {
+            timer,
+            prescaler,
+            top,
+            _clock: PhantomData,
+            _h: PhantomData,
+        })
+    }
+
+    /// The actual sample rate.
+    pub fn rate(&self) -> Hertz {
+        Hertz::from_raw(CLOCK::FREQ / (self.prescaler.divider() * (self.top as u32 + 1)))
+    }
+
+    /// Stop the timer and return it.
+    ///
+    /// The clock of the peripheral is stopped in the Power Reduction Register, if available.
+    pub fn release(mut self) -> TC
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        self.timer.raw_set_clock(None);
+        self.timer.raw_set_powered(false);
+        self.timer
+    }
+}
+
+impl<H, ADC, TC, CLOCK> TriggerSource<H, ADC> for TimerTrigger<H, TC, CLOCK>
+where
+    ADC: AdcOps<H>,
+    TC: AdcTimerOps<H, ADC>,
+{
+    #[inline]
+    fn source(&self) -> ADC::Trigger {
+        TC::TRIGGER
+    }
+
+    fn start(&mut self) {
+        if TC::COMPARE == OutputCompare::B {
+            // The compare match on B happens together with the one on A, which is TOP.
+            self.timer.raw_set_compare(OutputCompare::B, self.top);
+        }
+        self.rearm();
+    }
+
+    fn rearm(&mut self) {
+        // The ADC only starts a conversion on a rising edge of the interrupt flag.
+        self.timer.raw_clear_pending(match TC::COMPARE {
+            OutputCompare::A => Event::CompareA,
+            OutputCompare::B => Event::CompareB,
+        });
+    }
+}
+
+/// Interrupt-driven sampling of one channel into a buffer
+///
+/// Conversions are started by an auto-trigger source, like free-running mode, an external
+/// interrupt or a [`TimerTrigger`] for a fixed sample rate.  Each result is written to the buffer
+/// until it is full, then sampling stops until [`restart()`][Sampler::restart] is called.
+///
+/// **[`on_conversion()`][Sampler::on_conversion] must be called from the `ADC` interrupt handler.**
+/// The sampler is usually kept in a `Mutex<RefCell<_>>` which the interrupt handler and the main
+/// program share.
+///
+/// # Example
+/// ```
+/// static SAMPLER: Mutex<RefCell<Option<Sampler<_, _, CoreClock, _>>>> = ...;
+/// static mut BUFFER: [u16; 256] = [0; 256];
+///
+/// let trigger = TimerTrigger::new(dp.TC0, 8.kHz()).unwrap();
+/// let sampler = Sampler::new(adc, &a0, trigger, unsafe { &mut BUFFER });
+/// interrupt::free(|cs| SAMPLER.borrow(cs).replace(Some(sampler)));
+///
+/// #[avr_device::interrupt(atmega328p)]
+/// fn ADC() {
+///     interrupt::free(|cs| {
+///         if let Some(sampler) = SAMPLER.borrow(cs).borrow_mut().as_mut() {
+///             sampler.on_conversion();
+///         }
+///     })
+/// }
+/// ```
+pub struct Sampler<'a, H, ADC: AdcOps<H>, CLOCK, TRIG> {
+    adc: Adc<H, ADC, CLOCK>,
+    channel: ADC::Channel,
+    trigger: TRIG,
+    buffer: &'a mut [u16],
+    len: usize,
+}
+
+impl<'a, H, ADC, CLOCK, TRIG> Sampler<'a, H, ADC, CLOCK, TRIG>
+where
+    ADC: AdcOps<H>,
+    CLOCK: crate::clock::Clock,
+    TRIG: TriggerSource<H, ADC>,
+{
+    /// Start sampling `pin` on every event of `trigger`.
+    pub fn new<PIN: AdcChannel<H, ADC>>(
+        adc: Adc<H, ADC, CLOCK>,
+        pin: &PIN,
+        trigger: TRIG,
+        buffer: &'a mut [u16],
+    ) -> Self {
+        let mut sampler = Self {
+            adc,
+            channel: pin.channel(),
+            trigger,
+            buffer,
+            len: 0,
+        };
+        sampler.restart();
+        sampler
+    }
+
+    /// Store the result of a conversion.
+    ///
+    /// Call this from the `ADC` interrupt handler.
+    pub fn on_conversion(&mut self) {
+        if let Some(slot) = self.buffer.get_mut(self.len) {
+            *slot = self.adc.p.raw_read_adc();
+            self.len += 1;
+        }
+        if self.is_full() {
+            self.stop();
+        } else {
+            self.trigger.rearm();
+        }
+    }
+
+    /// Whether the buffer is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == self.buffer.len()
+    }
+
+    /// The samples taken so far.
+    #[inline]
+    pub fn samples(&self) -> &[u16] {
+        &self.buffer[..self.len]
+    }
+
+    /// Discard the samples and fill the buffer again.
+    pub fn restart(&mut self) {
+        self.stop();
+        self.len = 0;
+        if self.buffer.is_empty() {
+            return;
+        }
+        self.adc.p.raw_set_channel(self.channel);
+        self.trigger.start();
+        self.adc.p.raw_set_interrupt(true);
+        self.adc.p.raw_set_auto_trigger(Some(self.trigger.source()));
+    }
+
+    /// Stop sampling.  A conversion which is already running completes, but is not stored.
+    pub fn stop(&mut self) {
+        self.adc.p.raw_set_auto_trigger(None);
+        self.adc.p.raw_set_interrupt(false);
+    }
+
+    /// Stop sampling and return the ADC, the trigger source and the buffer.
+    pub fn release(mut self) -> (Adc<H, ADC, CLOCK>, TRIG, &'a mut [u16]) {
+        self.stop();
+        while self.adc.p.raw_is_converting() {}
+        (self.adc, self.trigger, self.buffer)
+    }
+}
+
+impl<H, ADC, CLOCK, PIN> hal_v0::adc::OneShot<ADC, u16, PIN> for Adc<H, ADC, CLOCK>
+where
+    ADC: AdcOps<H>,
+    CLOCK: crate::clock::Clock,
+    PIN: AdcChannel<H, ADC> + hal_v0::adc::Channel<ADC>,
+{
+    type Error = core::convert::Infallible;
+
+    fn read(&mut self, pin: &mut PIN) -> nb::Result<u16, Self::Error> {
+        self.read_nonblocking(pin)
+    }
+}
+
+/// Internal trait for pins which are connected to an ADC channel.
+///
+/// This provides the embedded-hal 0.2 `adc::Channel` implementation of analog pins, which needs
+/// the channel without a pin instance.
+pub trait AdcPinOps<ADC> {
+    /// Channel ID type of the ADC.
+    type Channel;
+
+    /// The channel of this pin.
+    const CHANNEL: Self::Channel;
+}
+
+impl<ADC, PIN: AdcPinOps<ADC>> hal_v0::adc::Channel<ADC> for Pin<mode::Analog, PIN> {
+    type ID = PIN::Channel;
+
+    fn channel() -> Self::ID {
+        PIN::CHANNEL
+    }
+}
+
+/// Analog input which reports the voltage at its pin
+///
+/// Drivers for analog sensors can be written against this trait instead of the [`Adc`] API.
+pub trait VoltageInput {
+    /// Measure the voltage at the input in millivolts.
+    fn read_millivolts(&mut self) -> u16;
+}
+
+/// ADC which can be shared by several [`AnalogInput`] handles
+///
+/// Starting a conversion and polling for its result each happen in a short critical section, while
+/// the conversion itself runs with interrupts enabled.  A reading never switches the channel while
+/// a conversion of another input is running, but waits for that conversion to complete first.
+/// The handles can therefore also be used from interrupt handlers: A reading which gets
+/// interrupted by a reading of another input is started again afterwards, so it just takes
+/// longer.  `&SharedAdc` implements the embedded-hal 0.2 `OneShot` trait as well, for drivers
+/// which expect an ADC and a pin.
+///
+/// # Example
+/// ```
+/// let adc = SharedAdc::new(atmega_hal::Adc::new(dp.ADC, Default::default()), 5000);
+///
+/// let mut joystick_x = adc.input(adc.with(|adc| pins.pc0.into_analog_input(adc)));
+/// let mut joystick_y = adc.input(adc.with(|adc| pins.pc1.into_analog_input(adc)));
+///
+/// let x = joystick_x.read();
+/// let y_millivolts = joystick_y.read_millivolts();
+/// ```
+pub struct SharedAdc<H, ADC: AdcOps<H>, CLOCK> {
+    adc: avr_device::interrupt::Mutex<RefCell<Adc<H, ADC, CLOCK>>>,
+    reference_millivolts: u16,
+}
+
+impl<H, ADC, CLOCK> SharedAdc<H, ADC, CLOCK>
+where
+    ADC: AdcOps<H>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Share `adc`, whose reference voltage is `reference_millivolts`.
+    ///
+    /// The reference voltage is used to convert readings into millivolts.
+    pub fn new(adc: Adc<H, ADC, CLOCK>, reference_millivolts: u16) -> Self {
+        Self {
+            adc: avr_device::interrupt::Mutex::new(RefCell::new(adc)),
+            reference_millivolts,
+        }
+    }
+
+    /// Access the ADC itself in a critical section, e.g. to put more pins into analog mode.
+    pub fn with<R>(&self, f: impl FnOnce(&mut Adc<H, ADC, CLOCK>) -> R) -> R {
+        avr_device::interrupt::free(|cs| f(&mut self.adc.borrow(cs).borrow_mut()))
+    }
+
+    /// Create a handle for reading `pin` through this ADC.
+    pub fn input<PIN: AdcChannel<H, ADC>>(&self, pin: PIN) -> AnalogInput<'_, H, ADC, CLOCK, PIN> {
+        AnalogInput { adc: self, pin }
+    }
+
+    /// Reference voltage in millivolts.
+    pub fn reference_millivolts(&self) -> u16 {
+        self.reference_millivolts
+    }
+
+    /// Give up sharing and get the ADC back.
+    pub fn into_inner(self) -> Adc<H, ADC, CLOCK> {
+        self.adc.into_inner().into_inner()
+    }
+
+    fn read_nonblocking<PIN: AdcChannel<H, ADC>>(
+        &self,
+        pin: &PIN,
+    ) -> nb::Result<u16, core::convert::Infallible> {
+        avr_device::interrupt::free(|cs| {
+            let mut adc = self.adc.borrow(cs).borrow_mut();
+            // Switching the channel now would return the result of the running conversion for
+            // this input.
+            if adc.p.raw_is_converting() && adc.reading_channel != Some(pin.channel()) {
+                return Err(nb::Error::WouldBlock);
+            }
+            adc.read_nonblocking(pin)
+        })
+    }
+
+    fn to_millivolts(&self, value: u16) -> u16 {
+        (u32::from(value) * u32::from(self.reference_millivolts) / 1024) as u16
+    }
+}
+
+impl<H, ADC, CLOCK, PIN> hal_v0::adc::OneShot<ADC, u16, PIN> for &SharedAdc<H, ADC, CLOCK>
+where
+    ADC: AdcOps<H>,
+    CLOCK: crate::clock::Clock,
+    PIN: AdcChannel<H, ADC> + hal_v0::adc::Channel<ADC>,
+{
+    type Error = core::convert::Infallible;
+
+    fn read(&mut self, pin: &mut PIN) -> nb::Result<u16, Self::Error> {
+        self.read_nonblocking(pin)
+    }
+}
+
+/// Analog input pin with a shared reference to its ADC
+///
+/// Created with [`SharedAdc::input()`].  Like a digital input pin, the handle can be moved into a
+/// driver, which then reads it through [`VoltageInput`] or the embedded-hal 0.2 `OneShot` trait.
+pub struct AnalogInput<'a, H, ADC: AdcOps<H>, CLOCK, PIN> {
+    adc: &'a SharedAdc<H, ADC, CLOCK>,
+    pin: PIN,
+}
+
+impl<H, ADC, CLOCK, PIN> AnalogInput<'_, H, ADC, CLOCK, PIN>
+where
+    ADC: AdcOps<H>,
+    CLOCK: crate::clock::Clock,
+    PIN: AdcChannel<H, ADC>,
+{
+    /// Read the raw 10-bit value.
+    pub fn read(&mut self) -> u16 {
+        nb::block!(self.adc.read_nonblocking(&self.pin)).unwrap_infallible()
+    }
+
+    /// Release the pin.
+    pub fn release(self) -> PIN {
+        self.pin
+    }
+}
+
+impl<H, ADC, CLOCK, PIN> VoltageInput for AnalogInput<'_, H, ADC, CLOCK, PIN>
+where
+    ADC: AdcOps<H>,
+    CLOCK: crate::clock::Clock,
+    PIN: AdcChannel<H, ADC>,
+{
+    fn read_millivolts(&mut self) -> u16 {
+        let value = self.read();
+        self.adc.to_millivolts(value)
+    }
+}
+
+/// Reads the channel of the `pin` argument.  For analog pins and the fixed channels, this is
+/// always the channel of the handle itself, as the channel is determined by the type.
+impl<H, ADC, CLOCK, PIN> hal_v0::adc::OneShot<ADC, u16, PIN> for AnalogInput<'_, H, ADC, CLOCK, PIN>
+where
+    ADC: AdcOps<H>,
+    CLOCK: crate::clock::Clock,
+    PIN: AdcChannel<H, ADC> + hal_v0::adc::Channel<ADC>,
+{
+    type Error = core::convert::Infallible;
+
+    fn read(&mut self, pin: &mut PIN) -> nb::Result<u16, Self::Error> {
+        self.adc.read_nonblocking(pin)
+    }
+}
+
 #[macro_export]
 macro_rules! impl_adc {
     (
@@ -221,8 +1125,15 @@ macro_rules! impl_adc {
         peripheral: $ADC:ty,
         settings: $Settings:ty,
         apply_settings: |$settings_periph_var:ident, $settings_var:ident| $apply_settings:block,
+        reference: $Reference:ty,
+        get_reference: |$get_ref_periph_var:ident| $get_reference:block,
+        set_reference: |$set_ref_periph_var:ident, $ref_var:ident| $set_reference:block,
+        set_left_adjust: |$adlar_periph_var:ident, $adlar_var:ident| $set_left_adjust:block,
         channel_id: $Channel:ty,
         set_channel: |$periph_var:ident, $chan_var:ident| $set_channel:block,
+        trigger: $Trigger:ty,
+        set_trigger: |$trigger_periph_var:ident, $trigger_var:ident| $set_trigger:block,
+        clear_trigger: |$clear_trigger_var:ident| $clear_trigger:block,
         pins: {
             $(
                 $(#[$pin_attr:meta])*
@@ -235,10 +1146,18 @@ macro_rules! impl_adc {
                 $channel_ty:ty: $channel:expr,
             )*
         },)?
+        $(differential: {
+            $(
+                $(#[$diff_attr:meta])*
+                $diff_pos:ty, $diff_neg:ty, $diff_gain:literal: $diff_channel:expr,
+            )*
+        },)?
     ) => {
         impl $crate::adc::AdcOps<$HAL> for $ADC {
             type Channel = $Channel;
             type Settings = $Settings;
+            type Trigger = $Trigger;
+            type Reference = $Reference;
 
             #[inline]
             fn raw_init(&mut self, settings: Self::Settings) {
@@ -248,11 +1167,30 @@ macro_rules! impl_adc {
                 $apply_settings
             }
 
+            #[inline]
+            fn raw_disable(&mut self) {
+                self.adcsra().modify(|_, w| w.aden().clear_bit());
+            }
+
             #[inline]
             fn raw_read_adc(&self) -> u16 {
                 self.adc().read().bits()
             }
 
+            #[inline]
+            fn raw_read_adc_high(&self) -> u8 {
+                // SAFETY: ADCH directly follows ADCL and reading it on its own is allowed.
+                unsafe { core::ptr::read_volatile((self.adc().as_ptr() as *const u8).add(1)) }
+            }
+
+            #[inline]
+            fn raw_set_left_adjust(&mut self, enable: bool) {
+                let $adlar_periph_var = self;
+                let $adlar_var = enable;
+
+                $set_left_adjust
+            }
+
             #[inline]
             fn raw_is_converting(&self) -> bool {
                 self.adcsra().read().adsc().bit_is_set()
@@ -294,6 +1232,51 @@ macro_rules! impl_adc {
                     _ => unreachable!(),
                 }
             }
+
+            #[inline]
+            fn raw_set_auto_trigger(&mut self, trigger: Option<Self::Trigger>) {
+                let $trigger_periph_var = self;
+                let $trigger_var = trigger;
+
+                $set_trigger
+            }
+
+            #[inline]
+            fn raw_set_interrupt(&mut self, enable: bool) {
+                self.adcsra().modify(|_, w| w.adie().bit(enable));
+            }
+
+            #[inline]
+            fn raw_reference(&self) -> Self::Reference {
+                let $get_ref_periph_var = self;
+
+                $get_reference
+            }
+
+            #[inline]
+            fn raw_set_reference(&mut self, reference: Self::Reference) {
+                let $set_ref_periph_var = self;
+                let $ref_var = reference;
+
+                $set_reference
+            }
+        }
+
+        impl $crate::adc::TriggerSource<$HAL, $ADC> for $Trigger {
+            #[inline]
+            fn source(&self) -> $Trigger {
+                *self
+            }
+
+            fn start(&mut self) {
+                self.rearm();
+            }
+
+            fn rearm(&mut self) {
+                // The ADC only starts a conversion on a rising edge of the interrupt flag.
+                let $clear_trigger_var = *self;
+                $clear_trigger
+            }
         }
 
         $(
@@ -304,6 +1287,12 @@ macro_rules! impl_adc {
                 $pin_channel
             }
         }
+
+        $(#[$pin_attr])*
+        impl $crate::adc::AdcPinOps<$ADC> for $pin {
+            type Channel = $Channel;
+            const CHANNEL: $Channel = $pin_channel;
+        }
         )+
 
         $($(
@@ -315,6 +1304,16 @@ macro_rules! impl_adc {
             }
         }
 
+        $(#[$channel_attr])*
+        impl $crate::hal_v0::adc::Channel<$ADC> for $channel_ty {
+            type ID = $Channel;
+
+            #[inline]
+            fn channel() -> $Channel {
+                $channel
+            }
+        }
+
         /// Convert this channel into a generic "[`Channel`][adc-channel]" type.
         ///
         /// The generic channel type can be used to store multiple channels in an array.
@@ -327,5 +1326,17 @@ macro_rules! impl_adc {
             }
         }
         )*)?
+
+        $($(
+        $(#[$diff_attr])*
+        impl $crate::adc::AdcChannel<$HAL, $ADC>
+            for $crate::adc::Differential<$diff_pos, $diff_neg, $diff_gain>
+        {
+            #[inline]
+            fn channel(&self) -> $Channel {
+                $diff_channel
+            }
+        }
+        )*)?
     };
 }
@@ -22,6 +22,30 @@
 //!   type Adc = atmega_hal::adc::Adc<crate::CoreClock>;
 //!   type I2c = atmega_hal::i2c::I2c<crate::CoreClock>;
 //!   ```
+//!
+//! # Runtime Prescaler
+//! Most MCUs can divide the clock source by a power of two at runtime, through the system clock
+//! prescaler (CLKPR), to save power.  As the clock speed is part of the driver types, the
+//! prescaler is controlled through a [`SystemClock`] token which carries the current clock speed
+//! in its type.  Switching the prescaler consumes the token and returns one for the new speed,
+//! which is then used to rebuild the drivers:
+//!
+//! ```ignore
+//! let clock = atmega_hal::clock::SystemClock::<MHz16, MHz16>::new(&mut dp.CPU);
+//! let mut delay = clock.delay();
+//!
+//! // ... later, before a low-power phase
+//! let clock = clock.switch::<MHz1>(&mut dp.CPU);
+//! let mut delay = clock.delay();
+//! let mut serial = serial.with_baudrate(clock.baudrate(9600));
+//! let mut i2c = i2c.with_clock(&clock, 50_000);
+//! ```
+//!
+//! Drivers which were built for the old clock speed keep their type, so they must not be used
+//! after switching the prescaler.  The `Adc` and `Timer` drivers are rebuilt by releasing the
+//! peripheral and constructing them again.
+
+use core::marker::PhantomData;
 
 /// A clock speed
 pub trait Clock {
@@ -71,9 +95,147 @@ impl Clock for MHz8 {
     const FREQ: u32 = 8_000_000;
 }
 
+/// 4 MHz Clock
+#[derive(ufmt::derive::uDebug, Debug)]
+pub struct MHz4;
+impl Clock for MHz4 {
+    const FREQ: u32 = 4_000_000;
+}
+
+/// 2 MHz Clock
+#[derive(ufmt::derive::uDebug, Debug)]
+pub struct MHz2;
+impl Clock for MHz2 {
+    const FREQ: u32 = 2_000_000;
+}
+
 /// 1 MHz Clock
 #[derive(ufmt::derive::uDebug, Debug)]
 pub struct MHz1;
 impl Clock for MHz1 {
     const FREQ: u32 = 1_000_000;
 }
+
+/// 500 kHz Clock
+#[derive(ufmt::derive::uDebug, Debug)]
+pub struct KHz500;
+impl Clock for KHz500 {
+    const FREQ: u32 = 500_000;
+}
+
+/// 250 kHz Clock
+#[derive(ufmt::derive::uDebug, Debug)]
+pub struct KHz250;
+impl Clock for KHz250 {
+    const FREQ: u32 = 250_000;
+}
+
+/// 125 kHz Clock
+#[derive(ufmt::derive::uDebug, Debug)]
+pub struct KHz125;
+impl Clock for KHz125 {
+    const FREQ: u32 = 125_000;
+}
+
+/// 62.5 kHz Clock
+#[derive(ufmt::derive::uDebug, Debug)]
+pub struct KHz62_5;
+impl Clock for KHz62_5 {
+    const FREQ: u32 = 62_500;
+}
+
+/// 31.25 kHz Clock
+#[derive(ufmt::derive::uDebug, Debug)]
+pub struct KHz31_25;
+impl Clock for KHz31_25 {
+    const FREQ: u32 = 31_250;
+}
+
+/// Internal trait for the system clock prescaler (CLKPR).
+///
+/// **Prefer using the [`SystemClock`] token instead of this trait.**
+pub trait ClockPrescalerOps<H> {
+    /// Divide the clock source by `2^clkps`.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_prescaler(&mut self, clkps: u8);
+}
+
+/// Token for the system clock prescaler, tracking the core clock speed at the type level
+///
+/// `SOURCE` is the speed of the clock source selected in the fuses and `CLOCK` the core clock
+/// speed it is currently divided down to.  The divider has to be a power of two between 1 and
+/// 256, which is checked at compile time.
+///
+/// Only one token should exist at a time, as the clock speed it tracks would be wrong otherwise.
+pub struct SystemClock<H, SOURCE, CLOCK> {
+    _source: PhantomData<SOURCE>,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, SOURCE: Clock, CLOCK: Clock> SystemClock<H, SOURCE, CLOCK> {
+    /// CLKPS value dividing `SOURCE` down to `CLOCK`.
+    const CLKPS: u8 = {
+        let divider = SOURCE::FREQ / CLOCK::FREQ;
+        assert!(
+            divider * CLOCK::FREQ == SOURCE::FREQ && divider.is_power_of_two() && divider <= 256,
+            "the clock source cannot be divided down to this clock speed"
+        );
+        divider.trailing_zeros() as u8
+    };
+
+    /// Take control of the prescaler and set it to run the core at `CLOCK`.
+    ///
+    /// This overrides the divider selected by the `CKDIV8` fuse.
+    pub fn new(cpu: &mut impl ClockPrescalerOps<H>) -> Self {
+        cpu.raw_set_prescaler(Self::CLKPS);
+        Self {
+            _source: PhantomData,
+            _clock: PhantomData,
+            _h: PhantomData,
+        }
+    }
+
+    /// Change the prescaler to run the core at `NEW`.
+    ///
+    /// Ongoing transfers and timer periods are disturbed by the switch, so peripherals should be
+    /// idle.
+    pub fn switch<NEW: Clock>(
+        self,
+        cpu: &mut impl ClockPrescalerOps<H>,
+    ) -> SystemClock<H, SOURCE, NEW> {
+        SystemClock::new(cpu)
+    }
+
+    /// Delay provider for the current clock speed.
+    pub fn delay(&self) -> crate::delay::Delay<CLOCK> {
+        crate::delay::Delay::new()
+    }
+
+    /// USART baudrate for the current clock speed.
+    pub fn baudrate(&self, baud: u32) -> crate::usart::Baudrate<CLOCK> {
+        crate::usart::Baudrate::new(baud)
+    }
+}
+
+#[macro_export]
+macro_rules! impl_clock_prescaler {
+    (
+        hal: $HAL:ty,
+        peripheral: $CPU:ty,
+        register: $reg:ident,
+    ) => {
+        impl $crate::clock::ClockPrescalerOps<$HAL> for $CPU {
+            #[inline]
+            fn raw_set_prescaler(&mut self, clkps: u8) {
+                // The new value must be written within four cycles after setting CLKPCE.
+                $crate::avr_device::interrupt::free(|_| {
+                    self.$reg().write(|w| w.clkpce().set_bit());
+                    // SAFETY: CLKPS is in the lower nibble and `clkps` was checked to be 8 at most.
+                    self.$reg().write(|w| unsafe { w.bits(clkps) });
+                });
+            }
+        }
+    };
+}
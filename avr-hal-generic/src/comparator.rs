@@ -0,0 +1,349 @@
+//! Analog Comparator
+//!
+//! The analog comparator compares the voltages at its positive and negative inputs and sets its
+//! output when the positive input is higher.  By default, the positive input is the AIN0 pin and
+//! the negative input is the AIN1 pin.  Depending on the MCU, the positive input can be switched to
+//! the internal bandgap reference and the negative input to any of the ADC multiplexer channels.
+//!
+//! The AIN0/AIN1 pins must be configured as floating inputs by the user.
+use core::marker::PhantomData;
+
+use crate::adc::{Adc, AdcChannel, AdcOps};
+
+/// Comparator output transition which sets the interrupt flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// The output changed in either direction.
+    Toggle,
+    /// The positive input dropped below the negative input.
+    Falling,
+    /// The positive input rose above the negative input.
+    Rising,
+}
+
+/// Source of the positive comparator input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositiveInput {
+    /// The AIN0 pin.
+    Ain0,
+    /// The internal bandgap reference.
+    Bandgap,
+}
+
+/// Internal trait for low-level analog comparator operations.
+///
+/// **HAL users should use the [`AnalogComparator`] type instead.**
+pub trait AnalogComparatorOps<H> {
+    /// Power up the comparator, comparing AIN0 against AIN1 with interrupts disabled.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_init(&mut self);
+
+    /// Power the comparator up or down.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_enabled(&mut self, enable: bool);
+
+    /// Read the comparator output.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_output(&self) -> bool;
+
+    /// Select which output transition sets the interrupt flag.
+    ///
+    /// The interrupt must be disabled while doing so.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_event(&mut self, event: Event);
+
+    /// Enable or disable the comparator interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_interrupt(&mut self, enable: bool);
+
+    /// Check whether the interrupt flag is set and clear it.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_check_flag(&mut self) -> bool;
+}
+
+/// Internal trait for comparators which can use the bandgap reference as positive input.
+///
+/// **HAL users should use the [`AnalogComparator`] type instead.**
+pub trait AnalogComparatorBandgapOps<H>: AnalogComparatorOps<H> {
+    /// Connect the bandgap reference instead of AIN0 to the positive input.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_bandgap(&mut self, enable: bool);
+}
+
+/// Internal trait for comparators which can trigger the Timer/Counter1 input capture.
+///
+/// **HAL users should use the [`AnalogComparator`] type instead.**
+pub trait AnalogComparatorCaptureOps<H>: AnalogComparatorOps<H> {
+    /// Connect the comparator output instead of the ICP pin to the input capture unit.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_input_capture(&mut self, enable: bool);
+}
+
+/// Internal trait for comparators which can use the ADC multiplexer as negative input.
+///
+/// **HAL users should use the [`AnalogComparator`] type instead.**
+pub trait AnalogComparatorMuxOps<H, ADC: AdcOps<H>>: AnalogComparatorOps<H> {
+    /// Connect the given ADC channel instead of AIN1 to the negative input, or switch back to AIN1
+    /// for `None`.
+    ///
+    /// The ADC has to be disabled while its multiplexer is used by the comparator.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_adc_multiplexer(&mut self, adc: &mut ADC, channel: Option<ADC::Channel>);
+}
+
+/// Analog Comparator
+///
+/// # Example
+/// ```
+/// let mut comparator = atmega_hal::AnalogComparator::new(dp.AC);
+///
+/// // Check whether the voltage at AIN0 is above the voltage at AIN1
+/// let above = comparator.output();
+///
+/// // Wait until it falls below
+/// comparator.set_event(Event::Falling);
+/// nb::block!(comparator.wait_event()).unwrap_infallible();
+///
+/// // Or get notified by the ANALOG_COMP interrupt
+/// comparator.listen(Event::Rising);
+/// ```
+pub struct AnalogComparator<H, AC: AnalogComparatorOps<H>> {
+    p: AC,
+    _h: PhantomData<H>,
+}
+
+impl<H, AC: AnalogComparatorOps<H>> AnalogComparator<H, AC> {
+    /// Power up the comparator, comparing AIN0 against AIN1.
+    pub fn new(mut p: AC) -> Self {
+        p.raw_init();
+        Self { p, _h: PhantomData }
+    }
+
+    /// Whether the positive input is currently higher than the negative input.
+    pub fn output(&self) -> bool {
+        self.p.raw_output()
+    }
+
+    /// Enable the comparator interrupt for the given output transition.
+    ///
+    /// Events which happened before are discarded.
+    pub fn listen(&mut self, event: Event) {
+        self.p.raw_set_interrupt(false);
+        self.p.raw_set_event(event);
+        self.p.raw_check_flag();
+        self.p.raw_set_interrupt(true);
+    }
+
+    /// Disable the comparator interrupt again.
+    pub fn unlisten(&mut self) {
+        self.p.raw_set_interrupt(false);
+    }
+
+    /// Select the output transition which is reported by [`AnalogComparator::wait_event()`]
+    /// without enabling the interrupt.
+    pub fn set_event(&mut self, event: Event) {
+        self.p.raw_set_interrupt(false);
+        self.p.raw_set_event(event);
+        self.p.raw_check_flag();
+    }
+
+    /// Wait until the selected output transition happens.
+    ///
+    /// Returns immediately if it happened since the last call.  Only useful while the interrupt is
+    /// not enabled, as the interrupt handler clears the flag otherwise.
+    pub fn wait_event(&mut self) -> nb::Result<(), core::convert::Infallible> {
+        if self.p.raw_check_flag() {
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Power down the comparator and return the peripheral.
+    pub fn release(mut self) -> AC {
+        self.p.raw_set_interrupt(false);
+        self.p.raw_set_enabled(false);
+        self.p
+    }
+}
+
+impl<H, AC: AnalogComparatorBandgapOps<H>> AnalogComparator<H, AC> {
+    /// Select the positive comparator input.
+    ///
+    /// The bandgap reference needs some time to settle after switching to it, so the output should
+    /// not be trusted immediately.
+    pub fn set_positive_input(&mut self, input: PositiveInput) {
+        self.p.raw_set_bandgap(input == PositiveInput::Bandgap);
+    }
+}
+
+impl<H, AC: AnalogComparatorCaptureOps<H>> AnalogComparator<H, AC> {
+    /// Trigger the Timer/Counter1 input capture with the comparator output instead of the ICP pin.
+    ///
+    /// The capture edge is still selected by the input capture unit.
+    pub fn set_input_capture(&mut self, enable: bool) {
+        self.p.raw_set_input_capture(enable);
+    }
+}
+
+impl<H, AC> AnalogComparator<H, AC>
+where
+    AC: AnalogComparatorOps<H>,
+{
+    /// Use an ADC channel instead of AIN1 as negative input.
+    ///
+    /// The channel must be one of the single-ended ADC inputs.  The ADC is disabled while its
+    /// multiplexer is used by the comparator, so it is kept in the returned [`MuxedAdc`] until
+    /// [`AnalogComparator::use_ain1()`] hands it back.
+    ///
+    /// # Example
+    /// ```
+    /// let muxed = comparator.use_adc_input(adc, &a0);
+    /// // ...
+    /// let adc = comparator.use_ain1(muxed);
+    /// ```
+    pub fn use_adc_input<ADC, CLOCK, PIN>(
+        &mut self,
+        mut adc: Adc<H, ADC, CLOCK>,
+        pin: &PIN,
+    ) -> MuxedAdc<H, ADC, CLOCK>
+    where
+        AC: AnalogComparatorMuxOps<H, ADC>,
+        ADC: AdcOps<H>,
+        CLOCK: crate::clock::Clock,
+        PIN: AdcChannel<H, ADC>,
+    {
+        self.p
+            .raw_set_adc_multiplexer(adc.peripheral_mut(), Some(pin.channel()));
+        MuxedAdc { adc }
+    }
+
+    /// Use AIN1 as negative input again, re-enable the ADC and return it.
+    pub fn use_ain1<ADC, CLOCK>(&mut self, muxed: MuxedAdc<H, ADC, CLOCK>) -> Adc<H, ADC, CLOCK>
+    where
+        AC: AnalogComparatorMuxOps<H, ADC>,
+        ADC: AdcOps<H>,
+        CLOCK: crate::clock::Clock,
+    {
+        let mut adc = muxed.adc;
+        self.p.raw_set_adc_multiplexer(adc.peripheral_mut(), None);
+        adc
+    }
+}
+
+/// ADC whose multiplexer selects the negative comparator input
+///
+/// Returned by [`AnalogComparator::use_adc_input()`].  The ADC cannot be used for conversions
+/// meanwhile, [`AnalogComparator::use_ain1()`] gives it back.
+pub struct MuxedAdc<H, ADC: AdcOps<H>, CLOCK> {
+    adc: Adc<H, ADC, CLOCK>,
+}
+
+#[macro_export]
+macro_rules! impl_analog_comparator {
+    (
+        hal: $HAL:ty,
+        peripheral: $AC:ty,
+        $(bandgap: $acbg:ident,)?
+        $(input_capture: $acic:ident,)?
+        $(adc_multiplexer: {
+            peripheral: $ADC:ty,
+            set_enabled: |$ac:ident, $adc:ident, $enable:ident| $set_mux:expr,
+        },)?
+    ) => {
+        // Writing a one to ACI clears the flag, so all other modifications of ACSR must write a
+        // zero there to not lose a pending event.
+        impl $crate::comparator::AnalogComparatorOps<$HAL> for $AC {
+            #[inline]
+            fn raw_init(&mut self) {
+                self.acsr().write(|w| w.aci().set_bit());
+            }
+
+            #[inline]
+            fn raw_set_enabled(&mut self, enable: bool) {
+                self.acsr()
+                    .modify(|_, w| w.acd().bit(!enable).aci().clear_bit());
+            }
+
+            #[inline]
+            fn raw_output(&self) -> bool {
+                self.acsr().read().aco().bit_is_set()
+            }
+
+            #[inline]
+            fn raw_set_event(&mut self, event: $crate::comparator::Event) {
+                let acis = match event {
+                    $crate::comparator::Event::Toggle => 0b00,
+                    $crate::comparator::Event::Falling => 0b10,
+                    $crate::comparator::Event::Rising => 0b11,
+                };
+                // SAFETY: All three values are valid interrupt modes.
+                self.acsr()
+                    .modify(|_, w| unsafe { w.acis().bits(acis) }.aci().clear_bit());
+            }
+
+            #[inline]
+            fn raw_set_interrupt(&mut self, enable: bool) {
+                self.acsr()
+                    .modify(|_, w| w.acie().bit(enable).aci().clear_bit());
+            }
+
+            #[inline]
+            fn raw_check_flag(&mut self) -> bool {
+                let set = self.acsr().read().aci().bit_is_set();
+                if set {
+                    self.acsr().modify(|_, w| w.aci().set_bit());
+                }
+                set
+            }
+        }
+
+        $(
+            impl $crate::comparator::AnalogComparatorBandgapOps<$HAL> for $AC {
+                #[inline]
+                fn raw_set_bandgap(&mut self, enable: bool) {
+                    self.acsr()
+                        .modify(|_, w| w.$acbg().bit(enable).aci().clear_bit());
+                }
+            }
+        )?
+
+        $(
+            impl $crate::comparator::AnalogComparatorCaptureOps<$HAL> for $AC {
+                #[inline]
+                fn raw_set_input_capture(&mut self, enable: bool) {
+                    self.acsr()
+                        .modify(|_, w| w.$acic().bit(enable).aci().clear_bit());
+                }
+            }
+        )?
+
+        $(
+            impl $crate::comparator::AnalogComparatorMuxOps<$HAL, $ADC> for $AC {
+                #[inline]
+                fn raw_set_adc_multiplexer(
+                    &mut self,
+                    adc: &mut $ADC,
+                    channel: Option<<$ADC as $crate::adc::AdcOps<$HAL>>::Channel>,
+                ) {
+                    if let Some(channel) = channel {
+                        $crate::adc::AdcOps::<$HAL>::raw_set_channel(adc, channel);
+                    }
+                    let $ac = self;
+                    let $adc = adc;
+                    let $enable = channel.is_some();
+                    $set_mux
+                }
+            }
+        )?
+    };
+}
@@ -204,6 +204,49 @@ impl delay_v0::DelayUs<u16> for Delay<crate::clock::MHz8> {
     }
 }
 
+impl delay_v0::DelayUs<u16> for Delay<crate::clock::MHz4> {
+    fn delay_us(&mut self, mut us: u16) {
+        // for the 4 crate::clock::MHz clock, e.g. a 16 MHz source divided by 4
+
+        // for a 1 to 4 microsecond delay, simply return.  the overhead
+        // of the function call takes 14 (16) cycles, which is 4us
+        if us <= 4 {
+            return;
+        } // = 3 cycles, (4 when true)
+
+        // the following loop takes 1 microsecond (4 cycles)
+        // per iteration, so nothing to do here!
+
+        // account for the time taken in the preceeding commands.
+        // we just burned 16 (18) cycles above, remove 4, (4*1=4)
+        // us is at least 5 so we can substract 4
+        us -= 4; // = 2 cycles
+
+        busy_loop(us);
+    }
+}
+
+impl delay_v0::DelayUs<u16> for Delay<crate::clock::MHz2> {
+    fn delay_us(&mut self, mut us: u16) {
+        // for the 2 crate::clock::MHz clock, e.g. a 16 MHz source divided by 8
+
+        // the overhead of the function call takes 14 (16) cycles, which is 8us
+        if us <= 12 {
+            return;
+        } // = 3 cycles, (4 when true), (must be more than 12 if we want to substract 10)
+
+        // compensate for the time taken by the preceeding and next commands (about 20 cycles)
+        us -= 10; // = 2 cycles
+
+        // the following loop takes 2 microseconds (4 cycles)
+        // per iteration, so execute it us/2 times
+        // us is at least 3, divided by 2 gives us 1 (no zero delay bug)
+        us >>= 1; // us div 2, = 2 cycles
+
+        busy_loop(us);
+    }
+}
+
 impl delay_v0::DelayUs<u16> for Delay<crate::clock::MHz1> {
     fn delay_us(&mut self, mut us: u16) {
         // for the 1 crate::clock::MHz internal clock (default settings for common Atmega microcontrollers)
@@ -227,6 +270,34 @@ impl delay_v0::DelayUs<u16> for Delay<crate::clock::MHz1> {
     }
 }
 
+// Clocks below 1 MHz, where a single loop iteration (4 cycles) takes several microseconds.  The
+// overhead of the function call and the calculation is about 22 cycles, like for 1 MHz.
+macro_rules! impl_delay_slow {
+    ($($Clock:ident: $cycle_us:literal us per cycle, $shift:literal,)+) => {$(
+        impl delay_v0::DelayUs<u16> for Delay<crate::clock::$Clock> {
+            fn delay_us(&mut self, us: u16) {
+                // must be more than the overhead plus one loop iteration, so at least one
+                // iteration is left after compensating (no zero delay bug)
+                if us <= 26 * $cycle_us {
+                    return;
+                }
+
+                // compensate for the time taken by the preceeding and next commands and execute
+                // the loop (4 cycles) once for each `4 * cycle_us` microseconds
+                busy_loop((us - 22 * $cycle_us) >> $shift);
+            }
+        }
+    )+};
+}
+
+impl_delay_slow! {
+    KHz500: 2 us per cycle, 3,
+    KHz250: 4 us per cycle, 4,
+    KHz125: 8 us per cycle, 5,
+    KHz62_5: 16 us per cycle, 6,
+    KHz31_25: 32 us per cycle, 7,
+}
+
 // ------------------------------------------------------------------------ }}}
 
 impl<SPEED> delay_v0::DelayUs<u8> for Delay<SPEED>
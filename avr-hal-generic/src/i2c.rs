@@ -163,6 +163,11 @@ pub trait I2cOps<H, SDA, SCL> {
     /// **Warning**: This is a low-level method and should not be called directly from user code.
     fn raw_setup<CLOCK: crate::clock::Clock>(&mut self, speed: u32);
 
+    /// Disable the bus such that the pins can be used for other purposes again.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_disable(&mut self);
+
     /// Start a bus transaction to a certain `address` in either read or write mode.
     ///
     /// If a previous transaction was not stopped via `raw_stop()`, this should generate a repeated
@@ -226,7 +231,8 @@ pub struct I2c<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> {
 impl<H, I2C, SDAPIN, SCLPIN, CLOCK>
     I2c<H, I2C, port::Pin<port::mode::Input, SDAPIN>, port::Pin<port::mode::Input, SCLPIN>, CLOCK>
 where
-    I2C: I2cOps<H, port::Pin<port::mode::Input, SDAPIN>, port::Pin<port::mode::Input, SCLPIN>>,
+    I2C: I2cOps<H, port::Pin<port::mode::Input, SDAPIN>, port::Pin<port::mode::Input, SCLPIN>>
+        + crate::power::PowerReductionOps<H>,
     SDAPIN: port::PinOps,
     SCLPIN: port::PinOps,
     CLOCK: crate::clock::Clock,
@@ -239,6 +245,8 @@ where
     /// This method expects the internal pull-ups to be configured for both pins to comply with the
     /// I2C specification.  If you have external pull-ups connected, use
     /// [`I2c::with_external_pullup`] instead.
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
     pub fn new(
         p: I2C,
         sda: port::Pin<port::mode::Input<port::mode::PullUp>, SDAPIN>,
@@ -252,6 +260,7 @@ where
             _clock: PhantomData,
             _h: PhantomData,
         };
+        i2c.p.raw_set_powered(true);
         i2c.p.raw_setup::<CLOCK>(speed);
         i2c
     }
@@ -262,6 +271,8 @@ where
     /// the correct ones.  This is enforced at compile time.
     ///
     /// This method expects that external resistors pull up SDA and SCL.
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
     pub fn with_external_pullup(
         p: I2C,
         sda: port::Pin<port::mode::Input<port::mode::Floating>, SDAPIN>,
@@ -275,11 +286,46 @@ where
             _clock: PhantomData,
             _h: PhantomData,
         };
+        i2c.p.raw_set_powered(true);
         i2c.p.raw_setup::<CLOCK>(speed);
         i2c
     }
 }
 
+impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> I2c<H, I2C, SDA, SCL, CLOCK> {
+    /// Disable the bus and release the peripheral and the pins.
+    ///
+    /// The clock of the peripheral is stopped in the Power Reduction Register, if available.
+    pub fn release(mut self) -> (I2C, SDA, SCL)
+    where
+        I2C: crate::power::PowerReductionOps<H>,
+    {
+        self.p.raw_disable();
+        self.p.raw_set_powered(false);
+        (self.p, self.sda, self.scl)
+    }
+
+    /// Set up the bus again for the clock speed of `clock`.
+    ///
+    /// This is used to keep the bus working after switching the system clock prescaler.  The
+    /// `speed` might have to be lowered, as the bus can only run at up to a sixteenth of the clock
+    /// speed.
+    pub fn with_clock<SOURCE, NEW: crate::clock::Clock>(
+        mut self,
+        _clock: &crate::clock::SystemClock<H, SOURCE, NEW>,
+        speed: u32,
+    ) -> I2c<H, I2C, SDA, SCL, NEW> {
+        self.p.raw_setup::<NEW>(speed);
+        I2c {
+            p: self.p,
+            sda: self.sda,
+            scl: self.scl,
+            _clock: PhantomData,
+            _h: PhantomData,
+        }
+    }
+}
+
 impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> I2c<H, I2C, SDA, SCL, CLOCK>
 where
     CLOCK: crate::clock::Clock,
@@ -485,6 +531,11 @@ macro_rules! impl_i2c_twi {
                 self.twsr().write(|w| w.twps().prescaler_1());
             }
 
+            #[inline]
+            fn raw_disable(&mut self) {
+                self.twcr().reset();
+            }
+
             #[inline]
             fn raw_start(&mut self, address: u8, direction: Direction) -> Result<(), Error> {
                 // Write start condition
@@ -0,0 +1,435 @@
+//! Input capture unit of 16-bit timers
+//!
+//! On a selected edge of the ICP pin, the timer copies its counter value into the input capture
+//! register (`ICRn`).  This allows measuring the timing of external signals (pulse widths,
+//! periods, frequencies) with the resolution of the timer clock and without polling the pin.
+//!
+//! The [`InputCapture`] driver runs the timer freely in normal mode and counts its overflows in
+//! software, so all timestamps are 32-bit tick counts.  For this to work, the driver must be
+//! polled (or its [`on_overflow()`][InputCapture::on_overflow] and
+//! [`on_capture()`][InputCapture::on_capture] methods called from the interrupt handlers) at least
+//! once per counter period, that is every 65536 timer ticks.
+//!
+//! # Example
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//!
+//! let mut icp = InputCapture::<_, _, CoreClock>::new(
+//!     dp.TC1,
+//!     pins.pb0.into_floating_input(),
+//!     Prescaler::Prescale8,
+//! )
+//! .unwrap();
+//!
+//! let pulse = icp.measure_pulse(Edge::Rising, 100.millis()).unwrap();
+//! let pulse_us = icp.to_duration(pulse).to_micros();
+//! ```
+use core::marker::PhantomData;
+
+use crate::port::{mode, Pin, PinOps};
+use crate::timer::{Duration, Event, Hertz, Prescaler, TimerError, TimerMode, TimerOps};
+
+/// Signal edge which triggers a capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    Rising,
+    Falling,
+}
+
+impl Edge {
+    /// The other edge.
+    pub const fn opposite(self) -> Self {
+        match self {
+            Edge::Rising => Edge::Falling,
+            Edge::Falling => Edge::Rising,
+        }
+    }
+}
+
+/// Events of the input capture driver which can raise an interrupt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureEvent {
+    /// A capture occurred (`TIMERn_CAPT`).
+    Capture,
+    /// The counter overflowed (`TIMERn_OVF`).
+    Overflow,
+}
+
+/// Errors of the blocking measurement methods.
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureError {
+    /// No edge was captured within the timeout.
+    Timeout,
+}
+
+/// Result of [`InputCapture::measure_pwm()`], in timer ticks.
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PwmMeasurement {
+    /// Time from one rising edge to the next.
+    pub period: u32,
+    /// Time from the rising edge to the falling edge.
+    pub high: u32,
+}
+
+/// Internal trait for timers with an input capture unit.
+///
+/// **Prefer using the [`InputCapture`] API instead of this trait.**
+pub trait InputCaptureOps<H>: TimerOps<H> {
+    /// The input capture (ICP) pin of this timer.
+    type IcpPin: PinOps;
+
+    /// Select the edge which triggers a capture.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_capture_edge(&mut self, edge: Edge);
+
+    /// Enable or disable the noise canceler of the ICP pin.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_noise_canceler(&mut self, enable: bool);
+
+    /// Read the input capture register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_capture(&self) -> u16;
+
+    /// Enable or disable the capture interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_capture_interrupt(&mut self, enable: bool);
+
+    /// Check whether the input capture flag is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_capture_pending(&self) -> bool;
+
+    /// Clear the input capture flag.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_capture_pending(&mut self);
+}
+
+/// Input capture driver
+///
+/// Takes ownership of the timer and of its ICP pin, which must be configured as an input.
+/// Timestamps are 32-bit tick counts which wrap around; differences between them must be
+/// calculated with `wrapping_sub()`.
+///
+/// The capture and overflow interrupts can be enabled with [`listen()`][InputCapture::listen].
+/// Their handlers must then call [`on_capture()`][InputCapture::on_capture] and
+/// [`on_overflow()`][InputCapture::on_overflow] because the hardware clears the flags which are
+/// otherwise polled by the driver.
+pub struct InputCapture<H, TC: InputCaptureOps<H>, IMODE, CLOCK> {
+    p: TC,
+    pin: Pin<mode::Input<IMODE>, TC::IcpPin>,
+    prescaler: Prescaler,
+    edge: Edge,
+    overflows: u16,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC, IMODE, CLOCK> InputCapture<H, TC, IMODE, CLOCK>
+where
+    TC: InputCaptureOps<H>,
+    IMODE: mode::InputMode,
+    CLOCK: crate::clock::Clock,
+{
+    /// Start the timer with the given prescaler and capture rising edges on its ICP pin.
+    ///
+    /// The prescaler determines the resolution and the maximum time between two calls of the
+    /// driver (65536 ticks).  The clock of the peripheral is started in the Power Reduction
+    /// Register, if necessary.
+    pub fn new(
+        mut p: TC,
+        pin: Pin<mode::Input<IMODE>, TC::IcpPin>,
+        prescaler: Prescaler,
+    ) -> Result<Self, TimerError>
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        if !TC::PRESCALERS.contains(&prescaler) {
+            return Err(TimerError::UnsupportedPrescaler);
+        }
+
+        p.raw_set_powered(true);
+        p.raw_set_clock(None);
+        for event in [Event::Overflow, Event::CompareA, Event::CompareB] {
+            p.raw_set_interrupt(event, false);
+        }
+        p.raw_set_capture_interrupt(false);
+        p.raw_set_mode(TimerMode::Normal);
+        p.raw_set_noise_canceler(false);
+        p.raw_set_capture_edge(Edge::Rising);
+        p.raw_write_counter(0);
+        p.raw_clear_pending(Event::Overflow);
+        p.raw_clear_capture_pending();
+        p.raw_set_clock(Some(prescaler));
+
+        Ok(Self {
+            p,
+            pin,
+            prescaler,
+            edge: Edge::Rising,
+            overflows: 0,
+            _clock: PhantomData,
+            _h: PhantomData,
+        })
+    }
+
+    /// Select the edge which triggers a capture.
+    ///
+    /// A pending capture is discarded.
+    pub fn set_edge(&mut self, edge: Edge) {
+        self.p.raw_set_capture_edge(edge);
+        // Changing the edge can set the capture flag.
+        self.p.raw_clear_capture_pending();
+        self.edge = edge;
+    }
+
+    /// The edge which currently triggers a capture.
+    #[inline]
+    pub fn edge(&self) -> Edge {
+        self.edge
+    }
+
+    /// Enable or disable the noise canceler.
+    ///
+    /// With the noise canceler, the pin must be stable for four core clock cycles before an edge
+    /// is detected, which also delays the capture by four cycles.
+    #[inline]
+    pub fn set_noise_canceler(&mut self, enable: bool) {
+        self.p.raw_set_noise_canceler(enable);
+    }
+
+    /// Frequency at which the counter is incremented.
+    #[inline]
+    pub fn tick_rate(&self) -> Hertz {
+        Hertz::from_raw(CLOCK::FREQ / self.prescaler.divider())
+    }
+
+    /// Convert a number of timer ticks into a duration.
+    pub fn to_duration(&self, ticks: u32) -> Duration {
+        let us = ticks as u64 * 1_000_000 / self.tick_rate().to_Hz() as u64;
+        Duration::from_ticks(u32::try_from(us).unwrap_or(u32::MAX))
+    }
+
+    /// Convert a duration into a number of timer ticks.
+    pub fn to_ticks(&self, duration: Duration) -> u32 {
+        let ticks = duration.to_micros() as u64 * self.tick_rate().to_Hz() as u64 / 1_000_000;
+        u32::try_from(ticks).unwrap_or(u32::MAX)
+    }
+
+    /// Current time in timer ticks.
+    pub fn now(&mut self) -> u32 {
+        avr_device::interrupt::free(|_| {
+            let mut counter = self.p.raw_read_counter();
+            // The flag might have been set right after reading the counter, so the counter must
+            // be read again.
+            if self.p.raw_is_pending(Event::Overflow) {
+                counter = self.p.raw_read_counter();
+                self.p.raw_clear_pending(Event::Overflow);
+                self.overflows = self.overflows.wrapping_add(1);
+            }
+            (self.overflows as u32) << 16 | counter as u32
+        })
+    }
+
+    /// Timestamp of the next capture.
+    ///
+    /// Each capture must be read within one counter period, otherwise its timestamp is wrong.
+    pub fn capture(&mut self) -> nb::Result<u32, core::convert::Infallible> {
+        if self.p.raw_is_capture_pending() {
+            self.p.raw_clear_capture_pending();
+            Ok(self.on_capture())
+        } else {
+            // Keep track of overflows while waiting.
+            self.now();
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Handler for the capture interrupt: Timestamp of the latest capture.
+    pub fn on_capture(&mut self) -> u32 {
+        let icr = self.p.raw_read_capture();
+        let now = self.now();
+        // The capture happened within the last counter period, so its timestamp is the latest
+        // point in time before now with the captured lower 16 bits.
+        now.wrapping_sub((now as u16).wrapping_sub(icr) as u32)
+    }
+
+    /// Handler for the overflow interrupt.
+    #[inline]
+    pub fn on_overflow(&mut self) {
+        self.overflows = self.overflows.wrapping_add(1);
+    }
+
+    /// Enable the interrupt for an event.
+    pub fn listen(&mut self, event: CaptureEvent) {
+        match event {
+            CaptureEvent::Capture => self.p.raw_set_capture_interrupt(true),
+            CaptureEvent::Overflow => self.p.raw_set_interrupt(Event::Overflow, true),
+        }
+    }
+
+    /// Disable the interrupt for an event.
+    pub fn unlisten(&mut self, event: CaptureEvent) {
+        match event {
+            CaptureEvent::Capture => self.p.raw_set_capture_interrupt(false),
+            CaptureEvent::Overflow => self.p.raw_set_interrupt(Event::Overflow, false),
+        }
+    }
+
+    fn wait_capture(&mut self, start: u32, timeout: u32) -> Result<u32, CaptureError> {
+        loop {
+            if let Ok(timestamp) = self.capture() {
+                return Ok(timestamp);
+            }
+            if self.now().wrapping_sub(start) > timeout {
+                return Err(CaptureError::Timeout);
+            }
+        }
+    }
+
+    /// Measure the time between two consecutive edges of the selected kind.
+    ///
+    /// This blocks until the second edge was captured or `timeout` has passed.
+    pub fn measure_period(&mut self, timeout: Duration) -> Result<u32, CaptureError> {
+        let timeout = self.to_ticks(timeout);
+        self.set_edge(self.edge);
+        let start = self.now();
+        let first = self.wait_capture(start, timeout)?;
+        let second = self.wait_capture(start, timeout)?;
+        Ok(second.wrapping_sub(first))
+    }
+
+    /// Measure the width of a pulse starting with the given edge.
+    ///
+    /// [`Edge::Rising`] measures a high pulse and [`Edge::Falling`] a low pulse.  This blocks
+    /// until the pulse has ended or `timeout` has passed.  The selected edge is restored
+    /// afterwards.
+    pub fn measure_pulse(
+        &mut self,
+        start_edge: Edge,
+        timeout: Duration,
+    ) -> Result<u32, CaptureError> {
+        let previous = self.edge;
+        let result = self.measure_pulse_inner(start_edge, self.to_ticks(timeout));
+        self.set_edge(previous);
+        result
+    }
+
+    fn measure_pulse_inner(&mut self, start_edge: Edge, timeout: u32) -> Result<u32, CaptureError> {
+        self.set_edge(start_edge);
+        let start = self.now();
+        let begin = self.wait_capture(start, timeout)?;
+        self.set_edge(start_edge.opposite());
+        let end = self.wait_capture(start, timeout)?;
+        Ok(end.wrapping_sub(begin))
+    }
+
+    /// Measure period and high time of a PWM signal.
+    ///
+    /// This blocks until a full period was captured or `timeout` has passed.  The selected edge
+    /// is restored afterwards.
+    ///
+    /// Edges must be at least a few microseconds apart, as the edge is switched in software
+    /// between the captures.
+    pub fn measure_pwm(&mut self, timeout: Duration) -> Result<PwmMeasurement, CaptureError> {
+        let previous = self.edge;
+        let timeout = self.to_ticks(timeout);
+        let result = self.measure_pwm_inner(timeout);
+        self.set_edge(previous);
+        result
+    }
+
+    fn measure_pwm_inner(&mut self, timeout: u32) -> Result<PwmMeasurement, CaptureError> {
+        self.set_edge(Edge::Rising);
+        let start = self.now();
+        let rise = self.wait_capture(start, timeout)?;
+        self.set_edge(Edge::Falling);
+        let fall = self.wait_capture(start, timeout)?;
+        self.set_edge(Edge::Rising);
+        let next_rise = self.wait_capture(start, timeout)?;
+        Ok(PwmMeasurement {
+            period: next_rise.wrapping_sub(rise),
+            high: fall.wrapping_sub(rise),
+        })
+    }
+
+    /// Stop the timer and return the peripheral and the ICP pin.
+    ///
+    /// The clock of the peripheral is stopped in the Power Reduction Register, if available.
+    pub fn release(mut self) -> (TC, Pin<mode::Input<IMODE>, TC::IcpPin>)
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        self.p.raw_set_clock(None);
+        self.p.raw_set_capture_interrupt(false);
+        self.p.raw_set_interrupt(Event::Overflow, false);
+        self.p.raw_set_powered(false);
+        (self.p, self.pin)
+    }
+}
+
+#[macro_export]
+macro_rules! impl_input_capture {
+    (
+        hal: $HAL:ty,
+        peripheral: $TC:ty,
+        pin: $Pin:ty,
+        registers: {
+            control: $tccrb:ident,
+            capture: $icr:ident,
+            mask: $timsk:ident,
+            flags: $tifr:ident,
+        },
+        bits: {
+            edge: $ices:ident,
+            noise_canceler: $icnc:ident,
+            interrupt: $icie:ident,
+            flag: $icf:ident,
+        },
+    ) => {
+        impl $crate::input_capture::InputCaptureOps<$HAL> for $TC {
+            type IcpPin = $Pin;
+
+            #[inline]
+            fn raw_set_capture_edge(&mut self, edge: $crate::input_capture::Edge) {
+                self.$tccrb()
+                    .modify(|_, w| w.$ices().bit(edge == $crate::input_capture::Edge::Rising));
+            }
+
+            #[inline]
+            fn raw_set_noise_canceler(&mut self, enable: bool) {
+                self.$tccrb().modify(|_, w| w.$icnc().bit(enable));
+            }
+
+            #[inline]
+            fn raw_read_capture(&self) -> u16 {
+                $crate::timer::access_16bit(|| self.$icr().read().bits())
+            }
+
+            #[inline]
+            fn raw_set_capture_interrupt(&mut self, enable: bool) {
+                // SAFETY: The mask register may be shared with other timers, so the
+                // read-modify-write sequence is wrapped in a critical section.
+                $crate::avr_device::interrupt::free(|_| {
+                    self.$timsk().modify(|_, w| w.$icie().bit(enable));
+                })
+            }
+
+            #[inline]
+            fn raw_is_capture_pending(&self) -> bool {
+                self.$tifr().read().$icf().bit_is_set()
+            }
+
+            #[inline]
+            fn raw_clear_capture_pending(&mut self) {
+                // Flags are cleared by writing a logical one.
+                self.$tifr().write(|w| w.$icf().set_bit());
+            }
+        }
+    };
+}
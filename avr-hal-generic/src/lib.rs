@@ -3,6 +3,7 @@
 
 pub use embedded_hal as hal;
 pub use embedded_hal_v0 as hal_v0;
+pub use fugit;
 
 #[doc(hidden)]
 pub use avr_device;
@@ -13,18 +14,33 @@ pub use paste;
 
 pub mod adc;
 pub mod clock;
+pub mod comparator;
 pub mod delay;
 pub mod eeprom;
 pub mod i2c;
+pub mod input_capture;
+pub mod osccal;
 pub mod port;
+pub mod power;
+pub mod reset;
+#[cfg(feature = "rtic-monotonic")]
+pub mod rtic_monotonic;
+pub mod servo;
 pub mod simple_pwm;
 pub mod spi;
+pub mod time;
+#[cfg(feature = "embassy-time")]
+pub mod time_driver;
+pub mod timer;
+pub mod tone;
 pub mod usart;
 pub mod wdt;
 
 /// Prelude containing all HAL traits
 pub mod prelude {
     pub use crate::hal_v0::prelude::*;
+    pub use fugit::ExtU32 as _fugit_ExtU32;
+    pub use fugit::RateExtU32 as _fugit_RateExtU32;
     pub use ufmt::uWrite as _ufmt_uWrite;
     pub use unwrap_infallible::UnwrapInfallible as _unwrap_infallible_UnwrapInfallible;
 }
@@ -0,0 +1,507 @@
+//! Internal RC oscillator calibration
+//!
+//! The internal RC oscillator is only calibrated to about ±10% in the factory, which is too
+//! inaccurate for a USART.  [`calibrate()`] tunes the oscillator calibration register (OSCCAL)
+//! until the core clock matches its nominal speed, measured against an external reference:
+//!
+//! - [`CrystalReference`]: A 32.768 kHz watch crystal on the asynchronous timer.
+//! - [`UartReference`]: A stream of `0x55` (`U`) sync bytes sent by the host at a known baudrate.
+//! - [`UsbSofReference`]: The start-of-frame packets sent every millisecond by a USB host.
+//!
+//! The core clock cycles are counted with a [`Timer`] running directly from the core clock.  The
+//! nominal clock speed is the `CLOCK` type of this timer.
+//!
+//! The calibration value is lost on reset, so it is usually stored in the EEPROM with
+//! [`store_calibration()`] and applied on every boot with [`restore_calibration()`].
+//!
+//! # Example
+//! ```
+//! let mut cpu = dp.CPU;
+//! let mut eeprom = Eeprom::new(dp.EEPROM);
+//!
+//! if !osccal::restore_calibration(&mut cpu, &eeprom, CALIBRATION_OFFSET) {
+//!     let mut timer = Timer::<_, CoreClock>::new(dp.TC1);
+//!     let rx = pins.pd0.into_floating_input();
+//!     let mut reference = osccal::UartReference::new(&mut timer, &rx, 9600);
+//!
+//!     let value = osccal::calibrate(&mut cpu, &mut reference).unwrap();
+//!     osccal::store_calibration(&mut eeprom, CALIBRATION_OFFSET, value).unwrap();
+//! }
+//! ```
+use crate::eeprom::{Eeprom, EepromOps, OutOfBoundsError};
+use crate::port::{mode, Pin, PinOps};
+use crate::timer::{Event, Prescaler, Timer, TimerMode, TimerOps};
+
+/// Errors during the oscillator calibration.
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationError {
+    /// The reference did not produce the expected events in time.
+    NoReference,
+    /// The nominal clock speed cannot be reached within the tuning range of the oscillator.
+    OutOfRange,
+}
+
+/// Internal trait for the oscillator calibration register.
+///
+/// **Prefer using [`calibrate()`] and [`set_calibration()`] instead of this trait.**
+pub trait OscCalOps<H> {
+    /// Bits of OSCCAL which tune the frequency monotonically.  The remaining bits select one of
+    /// several overlapping frequency ranges and are left unchanged by the calibration.
+    const TUNING_MASK: u8;
+
+    /// Read the OSCCAL register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_osccal(&self) -> u8;
+
+    /// Write the OSCCAL register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_osccal(&mut self, value: u8);
+}
+
+/// Internal trait for the start-of-frame flag of a USB device controller.
+///
+/// **Prefer using the [`UsbSofReference`] instead of this trait.**
+pub trait UsbSofOps<H> {
+    /// Whether a start-of-frame packet was received since the flag was cleared.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_sof(&self) -> bool;
+
+    /// Clear the start-of-frame flag.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_sof(&mut self);
+}
+
+/// External reference for the oscillator calibration
+pub trait CalibrationReference {
+    /// Length of the reference interval at the nominal clock speed, in core clock cycles.
+    fn expected_cycles(&self) -> u32;
+
+    /// Measure the length of the reference interval at the current clock speed, in core clock
+    /// cycles.
+    fn measure(&mut self) -> Result<u32, CalibrationError>;
+}
+
+/// Core clock cycle counter, extending a free-running timer by counting its overflows.
+///
+/// The overflow flag is polled, so [`CycleCounter::now()`] has to be called at least twice per
+/// timer period (every 128 cycles for 8-bit timers).
+struct CycleCounter<'a, H, TC: TimerOps<H>, CLOCK: crate::clock::Clock> {
+    timer: &'a mut Timer<H, TC, CLOCK>,
+    overflows: u32,
+}
+
+impl<'a, H, TC: TimerOps<H>, CLOCK: crate::clock::Clock> CycleCounter<'a, H, TC, CLOCK> {
+    fn start(timer: &'a mut Timer<H, TC, CLOCK>) -> Self {
+        // All timers can run directly from the core clock.
+        timer.start_free_running(Prescaler::Direct).ok();
+        Self {
+            timer,
+            overflows: 0,
+        }
+    }
+
+    fn now(&mut self) -> u32 {
+        let counter = self.timer.counter();
+        // A pending overflow with a high counter value happened after the counter was read and is
+        // accounted for on the next call.
+        if self.timer.is_pending(Event::Overflow) && counter <= TC::MAX / 2 {
+            self.timer.clear_pending(Event::Overflow);
+            self.overflows += 1;
+        }
+        self.overflows * (TC::MAX as u32 + 1) + counter as u32
+    }
+
+    /// Poll `condition` until it is true and return the cycle count at that moment.
+    fn wait_for(
+        &mut self,
+        deadline: u32,
+        mut condition: impl FnMut() -> bool,
+    ) -> Result<u32, CalibrationError> {
+        loop {
+            let now = self.now();
+            if condition() {
+                return Ok(now);
+            }
+            if now > deadline {
+                return Err(CalibrationError::NoReference);
+            }
+        }
+    }
+}
+
+impl<H, TC: TimerOps<H>, CLOCK: crate::clock::Clock> Drop for CycleCounter<'_, H, TC, CLOCK> {
+    fn drop(&mut self) {
+        self.timer.stop();
+    }
+}
+
+/// Calibration reference from a 32.768 kHz watch crystal on the asynchronous timer
+///
+/// The crystal is connected to the TOSC1/TOSC2 pins, which are usually shared with the main
+/// crystal pins, so this is only available when running from the internal oscillator.  A watch
+/// crystal can take up to a second to start up, which [`calibrate()`] does not wait for.
+pub struct CrystalReference<'a, H, TC: TimerOps<H>, ATC, CLOCK: crate::clock::Clock> {
+    timer: &'a mut Timer<H, TC, CLOCK>,
+    crystal: &'a mut ATC,
+}
+
+impl<'a, H, TC, ATC, CLOCK> CrystalReference<'a, H, TC, ATC, CLOCK>
+where
+    TC: TimerOps<H>,
+    ATC: TimerOps<H> + crate::power::AsyncTimerOps<H> + crate::power::PowerReductionOps<H>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Number of crystal periods in the reference interval (about 2 ms).
+    pub const TICKS: u16 = 64;
+
+    /// Count core clock cycles with `timer` during crystal periods counted by the asynchronous
+    /// timer `crystal`.
+    ///
+    /// The asynchronous timer is switched to the crystal and keeps running from it.
+    pub fn new(timer: &'a mut Timer<H, TC, CLOCK>, crystal: &'a mut ATC) -> Self {
+        crystal.raw_set_powered(true);
+        for event in [Event::Overflow, Event::CompareA, Event::CompareB] {
+            crystal.raw_set_interrupt(event, false);
+        }
+        crystal.raw_set_async(true);
+        crystal.raw_set_mode(TimerMode::Normal);
+        crystal.raw_write_counter(0);
+        crystal.raw_set_clock(Some(Prescaler::Direct));
+        while crystal.raw_is_updating() {}
+        crystal.raw_clear_pending(Event::Overflow);
+
+        Self { timer, crystal }
+    }
+}
+
+impl<H, TC, ATC, CLOCK> CalibrationReference for CrystalReference<'_, H, TC, ATC, CLOCK>
+where
+    TC: TimerOps<H>,
+    ATC: TimerOps<H> + crate::power::AsyncTimerOps<H> + crate::power::PowerReductionOps<H>,
+    CLOCK: crate::clock::Clock,
+{
+    fn expected_cycles(&self) -> u32 {
+        CLOCK::FREQ * Self::TICKS as u32 / 32_768
+    }
+
+    fn measure(&mut self) -> Result<u32, CalibrationError> {
+        let deadline = 4 * self.expected_cycles();
+        let crystal = &*self.crystal;
+        let mut counter = CycleCounter::start(self.timer);
+
+        // Start on the edge of a crystal period.
+        let initial = crystal.raw_read_counter();
+        let start = counter.wait_for(deadline, || crystal.raw_read_counter() != initial)?;
+        let first = initial.wrapping_add(1) & ATC::MAX;
+        let end = counter.wait_for(deadline, || {
+            crystal.raw_read_counter().wrapping_sub(first) & ATC::MAX >= Self::TICKS
+        })?;
+
+        Ok(end - start)
+    }
+}
+
+/// Calibration reference from sync bytes sent by the host
+///
+/// The host has to send a continuous stream of `0x55` (`U`) bytes with 8N1 framing at the given
+/// baudrate during the calibration.  Each of these bytes starts a new bit with a falling edge on
+/// the RX pin every two bit periods, which are timed by polling the pin.
+pub struct UartReference<'a, H, TC: TimerOps<H>, CLOCK: crate::clock::Clock, IMODE, PIN> {
+    timer: &'a mut Timer<H, TC, CLOCK>,
+    rx: &'a Pin<mode::Input<IMODE>, PIN>,
+    baudrate: u32,
+}
+
+impl<'a, H, TC, CLOCK, IMODE, PIN> UartReference<'a, H, TC, CLOCK, IMODE, PIN>
+where
+    TC: TimerOps<H>,
+    CLOCK: crate::clock::Clock,
+    IMODE: mode::InputMode,
+    PIN: PinOps,
+{
+    /// Number of falling edge intervals in the reference interval (eight bit periods).
+    const EDGES: u32 = 4;
+
+    /// Count core clock cycles with `timer` between edges of the sync bytes received on `rx`.
+    pub fn new(
+        timer: &'a mut Timer<H, TC, CLOCK>,
+        rx: &'a Pin<mode::Input<IMODE>, PIN>,
+        baudrate: u32,
+    ) -> Self {
+        Self {
+            timer,
+            rx,
+            baudrate,
+        }
+    }
+}
+
+impl<H, TC, CLOCK, IMODE, PIN> CalibrationReference for UartReference<'_, H, TC, CLOCK, IMODE, PIN>
+where
+    TC: TimerOps<H>,
+    CLOCK: crate::clock::Clock,
+    IMODE: mode::InputMode,
+    PIN: PinOps,
+{
+    fn expected_cycles(&self) -> u32 {
+        (CLOCK::FREQ * 2 * Self::EDGES + self.baudrate / 2) / self.baudrate
+    }
+
+    fn measure(&mut self) -> Result<u32, CalibrationError> {
+        // Allow the host up to a second to start sending.
+        let deadline = CLOCK::FREQ;
+        let rx = self.rx;
+        let mut counter = CycleCounter::start(self.timer);
+
+        loop {
+            counter.wait_for(deadline, || rx.is_high())?;
+            let start = counter.wait_for(deadline, || rx.is_low())?;
+
+            let mut last = start;
+            let (mut shortest, mut longest) = (u32::MAX, 0);
+            for _ in 0..Self::EDGES {
+                counter.wait_for(deadline, || rx.is_high())?;
+                let edge = counter.wait_for(deadline, || rx.is_low())?;
+                shortest = shortest.min(edge - last);
+                longest = longest.max(edge - last);
+                last = edge;
+            }
+
+            // A pause between two bytes stretches one of the intervals, so try again.
+            if longest <= shortest + shortest / 4 {
+                return Ok(last - start);
+            }
+        }
+    }
+}
+
+/// Calibration reference from the start-of-frame packets of a USB host
+///
+/// A USB host sends a start-of-frame packet every millisecond to a configured device, so the USB
+/// stack has to enumerate the device first.  The start-of-frame interrupt must be disabled during
+/// the calibration.
+pub struct UsbSofReference<'a, H, TC: TimerOps<H>, CLOCK: crate::clock::Clock, USB> {
+    timer: &'a mut Timer<H, TC, CLOCK>,
+    usb: &'a mut USB,
+}
+
+impl<'a, H, TC, CLOCK, USB> UsbSofReference<'a, H, TC, CLOCK, USB>
+where
+    TC: TimerOps<H>,
+    CLOCK: crate::clock::Clock,
+    USB: UsbSofOps<H>,
+{
+    /// Number of USB frames in the reference interval.
+    const FRAMES: u32 = 4;
+
+    /// Count core clock cycles with `timer` between start-of-frame packets received by `usb`.
+    pub fn new(timer: &'a mut Timer<H, TC, CLOCK>, usb: &'a mut USB) -> Self {
+        Self { timer, usb }
+    }
+}
+
+impl<H, TC, CLOCK, USB> CalibrationReference for UsbSofReference<'_, H, TC, CLOCK, USB>
+where
+    TC: TimerOps<H>,
+    CLOCK: crate::clock::Clock,
+    USB: UsbSofOps<H>,
+{
+    fn expected_cycles(&self) -> u32 {
+        CLOCK::FREQ / 1000 * Self::FRAMES
+    }
+
+    fn measure(&mut self) -> Result<u32, CalibrationError> {
+        let deadline = 4 * self.expected_cycles();
+        let mut counter = CycleCounter::start(self.timer);
+
+        // The flag might have been set a while ago, so start on the next frame.
+        self.usb.raw_clear_sof();
+        let start = counter.wait_for(deadline, || self.usb.raw_is_sof())?;
+        let mut end = start;
+        for _ in 0..Self::FRAMES {
+            self.usb.raw_clear_sof();
+            end = counter.wait_for(deadline, || self.usb.raw_is_sof())?;
+        }
+
+        Ok(end - start)
+    }
+}
+
+/// Current oscillator calibration value.
+pub fn calibration<H, CPU: OscCalOps<H>>(cpu: &CPU) -> u8 {
+    cpu.raw_read_osccal()
+}
+
+/// Apply an oscillator calibration value.
+///
+/// The clock speed must not change by more than 2% at once, so the tuning bits are changed in
+/// single steps within the current frequency range.  The range bits are switched last, as the
+/// ranges overlap and stepping across a range boundary would make the frequency jump.
+pub fn set_calibration<H, CPU: OscCalOps<H>>(cpu: &mut CPU, value: u8) {
+    let current = cpu.raw_read_osccal();
+    let range = current & !CPU::TUNING_MASK;
+    let target = value & CPU::TUNING_MASK;
+    let mut tuning = current & CPU::TUNING_MASK;
+    while tuning != target {
+        tuning = if tuning < target {
+            tuning + 1
+        } else {
+            tuning - 1
+        };
+        cpu.raw_write_osccal(range | tuning);
+    }
+    if range | tuning != value {
+        cpu.raw_write_osccal(value);
+    }
+}
+
+/// Tune the internal oscillator until the core clock matches the nominal speed of the reference.
+///
+/// The best calibration value is applied and returned.  If the remaining error is above 2%, the
+/// previous value is restored and [`CalibrationError::OutOfRange`] is returned.
+///
+/// Interrupts should be disabled, as they disturb the measurements.
+pub fn calibrate<H, CPU, R>(cpu: &mut CPU, reference: &mut R) -> Result<u8, CalibrationError>
+where
+    CPU: OscCalOps<H>,
+    R: CalibrationReference,
+{
+    let original = cpu.raw_read_osccal();
+    let result = search(cpu, reference);
+    match result {
+        Ok(value) => set_calibration(cpu, value),
+        Err(_) => set_calibration(cpu, original),
+    }
+    result
+}
+
+fn search<H, CPU, R>(cpu: &mut CPU, reference: &mut R) -> Result<u8, CalibrationError>
+where
+    CPU: OscCalOps<H>,
+    R: CalibrationReference,
+{
+    let expected = reference.expected_cycles();
+    let range = cpu.raw_read_osccal() & !CPU::TUNING_MASK;
+
+    // Find the lowest setting which is not too slow.  The frequency rises with the setting, so a
+    // binary search works.
+    let (mut low, mut high) = (0, CPU::TUNING_MASK);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        set_calibration(cpu, range | mid);
+        if reference.measure()? >= expected {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    // The setting below might be closer to the nominal speed.
+    let mut error = |tuning: u8| -> Result<u32, CalibrationError> {
+        set_calibration(cpu, range | tuning);
+        Ok(reference.measure()?.abs_diff(expected))
+    };
+    let mut best = (low, error(low)?);
+    if low > 0 {
+        let below = error(low - 1)?;
+        if below < best.1 {
+            best = (low - 1, below);
+        }
+    }
+
+    if best.1 > expected / 50 {
+        return Err(CalibrationError::OutOfRange);
+    }
+    Ok(range | best.0)
+}
+
+/// Store a calibration value in the EEPROM at `offset`.
+///
+/// The value takes two bytes, as its complement is stored after it to detect an erased or
+/// corrupted EEPROM.  Nothing is written if the value is already stored.
+///
+/// Fails if the two bytes do not fit into the EEPROM at `offset`.
+pub fn store_calibration<H, EEPROM: EepromOps<H>>(
+    eeprom: &mut Eeprom<H, EEPROM>,
+    offset: u16,
+    value: u8,
+) -> Result<(), OutOfBoundsError> {
+    if !fits(eeprom, offset) {
+        return Err(OutOfBoundsError);
+    }
+    if load_calibration(eeprom, offset) != Some(value) {
+        eeprom.write_byte(offset, value);
+        eeprom.write_byte(offset + 1, !value);
+    }
+    Ok(())
+}
+
+/// Load a calibration value stored with [`store_calibration()`].
+///
+/// Returns `None` if no value was stored there or `offset` is outside of the EEPROM.
+pub fn load_calibration<H, EEPROM: EepromOps<H>>(
+    eeprom: &Eeprom<H, EEPROM>,
+    offset: u16,
+) -> Option<u8> {
+    if !fits(eeprom, offset) {
+        return None;
+    }
+    let value = eeprom.read_byte(offset);
+    (eeprom.read_byte(offset + 1) == !value).then_some(value)
+}
+
+/// Whether a stored calibration value at `offset` fits into the EEPROM.
+fn fits<H, EEPROM: EepromOps<H>>(eeprom: &Eeprom<H, EEPROM>, offset: u16) -> bool {
+    offset
+        .checked_add(1)
+        .is_some_and(|last| last < eeprom.capacity())
+}
+
+/// Apply the calibration value stored with [`store_calibration()`], if there is one.
+///
+/// Returns whether a stored value was found.
+pub fn restore_calibration<H, CPU, EEPROM>(
+    cpu: &mut CPU,
+    eeprom: &Eeprom<H, EEPROM>,
+    offset: u16,
+) -> bool
+where
+    CPU: OscCalOps<H>,
+    EEPROM: EepromOps<H>,
+{
+    match load_calibration(eeprom, offset) {
+        Some(value) => {
+            set_calibration(cpu, value);
+            true
+        }
+        None => false,
+    }
+}
+
+#[macro_export]
+macro_rules! impl_osccal {
+    (
+        hal: $HAL:ty,
+        peripheral: $CPU:ty,
+        tuning_mask: $mask:literal,
+    ) => {
+        impl $crate::osccal::OscCalOps<$HAL> for $CPU {
+            const TUNING_MASK: u8 = $mask;
+
+            #[inline]
+            fn raw_read_osccal(&self) -> u8 {
+                self.osccal().read().bits()
+            }
+
+            #[inline]
+            fn raw_write_osccal(&mut self, value: u8) {
+                // SAFETY: All values are valid calibration settings.
+                self.osccal().write(|w| unsafe { w.bits(value) });
+            }
+        }
+    };
+}
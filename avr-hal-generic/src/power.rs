@@ -0,0 +1,538 @@
+//! Sleep modes and power management
+//!
+//! [`sleep()`] puts the CPU to sleep until an interrupt wakes it up again.  Which interrupts can do
+//! so depends on the sleep mode; the deeper modes only leave a few wake-up sources running:
+//!
+//! - The watchdog, see [`wake_on_watchdog()`].
+//! - External interrupts (INTn), see [`wake_on_interrupt()`].  In the modes which stop the I/O
+//!   clock, most MCUs can only detect a low level on these pins, not an edge.
+//! - Pin change interrupts (PCINTn), see [`wake_on_pin_change()`].
+//! - The asynchronous timer, clocked by a 32.768 kHz watch crystal, in power-save mode.  See
+//!   [`set_async_timer_clock()`].
+//!
+//! An interrupt handler must be defined for every enabled wake-up source, even if it is empty.
+//!
+//! # Power Reduction
+//! Most MCUs can stop the clocks of unused peripherals in the Power Reduction Register (PRR) to
+//! save power while awake.  The `Usart`, `Spi`, `I2c`, `Adc` and `Timer` drivers power their
+//! peripheral up when they are constructed and down again when they are released, so calling
+//! `power_down_unused()` at boot is enough to keep the other peripherals off.  Peripherals which
+//! are used directly or by other drivers must be powered up with [`set_powered()`].
+use crate::wdt::{Mode, Timeout, Wdt, WdtInterruptOps};
+
+/// Internal trait for low-level sleep control.
+///
+/// **Prefer using [`sleep()`] or the drivers which put the CPU to sleep instead of this trait.**
+pub trait SleepOps<H> {
+    /// Sleep modes supported by this MCU.
+    type Mode: Copy;
+
+    /// Select the sleep mode which is entered by the next `sleep` instruction.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_mode(&mut self, mode: Self::Mode);
+
+    /// Set or clear the sleep enable bit.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_enabled(&mut self, enable: bool);
+}
+
+/// Internal trait for MCUs which can turn off the brown-out detector during sleep.
+///
+/// **Prefer using [`sleep_with_bod_disabled()`] instead of this trait.**
+pub trait SleepBodOps<H>: SleepOps<H> {
+    /// Disable the brown-out detector, enable interrupts and enter sleep mode.
+    ///
+    /// The BOD disable bit only stays set for three cycles, so this has to happen in one timed
+    /// sequence.  Interrupts must be disabled and sleep must be enabled beforehand.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_disable_bod_and_sleep(&mut self);
+}
+
+/// Internal trait for external interrupt (INTn) control.
+///
+/// **Prefer using [`wake_on_interrupt()`] instead of this trait.**
+pub trait ExternalInterruptOps<H> {
+    /// External interrupts of this MCU.
+    type Interrupt: Copy;
+
+    /// Select the pin condition which triggers the interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_sense(&mut self, int: Self::Interrupt, sense: Sense);
+
+    /// Enable or disable the interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_interrupt(&mut self, int: Self::Interrupt, enable: bool);
+
+    /// Clear the interrupt flag.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_flag(&mut self, int: Self::Interrupt);
+}
+
+/// Internal trait for pin change interrupt (PCINTn) control.
+///
+/// **Prefer using [`wake_on_pin_change()`] instead of this trait.**
+pub trait PinChangeOps<H> {
+    /// Enable or disable the pin change interrupt for the given PCINT number.
+    ///
+    /// The interrupt of the whole group is enabled as long as any of its pins are.  Returns
+    /// `false` if the MCU has no such pin change interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_pin_change(&mut self, pcint: u8, enable: bool) -> bool;
+}
+
+/// Internal trait for timers which can run from an asynchronous clock.
+///
+/// **Prefer using [`set_async_timer_clock()`] instead of this trait.**
+pub trait AsyncTimerOps<H> {
+    /// Clock the timer from the TOSC1/TOSC2 oscillator instead of the I/O clock.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_async(&mut self, enable: bool);
+
+    /// Whether writes to timer registers are still being transferred to the asynchronous clock
+    /// domain.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_updating(&self) -> bool;
+}
+
+/// Internal trait for peripherals whose clock can be stopped in the Power Reduction Register.
+///
+/// **Prefer using the HAL drivers or [`set_powered()`] instead of this trait.**
+pub trait PowerReductionOps<H> {
+    /// Start or stop the clock of this peripheral.
+    ///
+    /// The registers of a stopped peripheral cannot be accessed.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_powered(&mut self, powered: bool);
+}
+
+/// Errors while configuring a wake-up source.
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeError {
+    /// The watchdog does not support the timeout or has no interrupt mode.
+    UnsupportedTimeout,
+    /// The MCU has no pin change interrupt for this PCINT number.
+    NoSuchPinChange,
+}
+
+/// Pin condition which triggers an external interrupt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sense {
+    /// The pin is low.  The interrupt keeps triggering as long as it stays low.
+    LowLevel,
+    /// The pin changed in either direction.
+    AnyEdge,
+    /// The pin changed from high to low.
+    FallingEdge,
+    /// The pin changed from low to high.
+    RisingEdge,
+}
+
+/// Enable interrupts and enter sleep mode as one uninterruptible sequence.
+///
+/// `sei` only takes effect after the following instruction, so an interrupt which becomes pending
+/// in between wakes the CPU up again instead of being handled right before going to sleep.  The
+/// sleep mode must have been configured and enabled beforehand.
+///
+/// # Safety
+/// This enables interrupts, so it must not be called inside a critical section.  See
+/// [`avr_device::interrupt::enable()`].
+#[inline(always)]
+pub unsafe fn enable_interrupts_and_sleep() {
+    #[cfg(target_arch = "avr")]
+    core::arch::asm!("sei", "sleep");
+
+    #[cfg(not(target_arch = "avr"))]
+    unimplemented!("Implementation is only available for avr targets!")
+}
+
+/// Write the timed BOD disable sequence to `reg`, then enable interrupts and enter sleep mode.
+///
+/// BODS only gets set when BODSE was written right before, and it clears itself three cycles later,
+/// so the sleep instruction has to follow immediately.
+///
+/// # Safety
+/// `reg` must point to the register which holds the BOD disable bits.
+#[cfg(target_arch = "avr")]
+#[doc(hidden)]
+#[inline(always)]
+pub unsafe fn disable_bod_and_sleep(reg: *mut u8, enable: u8, value: u8) {
+    core::arch::asm!(
+        "st {reg}, {enable}",
+        "st {reg}, {value}",
+        "sei",
+        "sleep",
+        reg = in(reg_ptr) reg,
+        enable = in(reg) enable,
+        value = in(reg) value,
+    )
+}
+
+#[cfg(not(target_arch = "avr"))]
+#[doc(hidden)]
+pub unsafe fn disable_bod_and_sleep(_reg: *mut u8, _enable: u8, _value: u8) {
+    unimplemented!("Implementation is only available for avr targets!")
+}
+
+/// Sleep in the given mode until an interrupt wakes the CPU up.
+///
+/// Interrupts are disabled while the sleep mode is configured and enabled right before sleeping,
+/// so they are always enabled when this function returns.  To wait for a condition without missing
+/// the interrupt which sets it, disable interrupts before checking it:
+///
+/// ```
+/// let mut cpu = dp.CPU;
+///
+/// avr_device::interrupt::disable();
+/// while !DONE.load(Ordering::SeqCst) {
+///     unsafe { power::sleep(&mut cpu, SleepMode::PowerDown) };
+///     avr_device::interrupt::disable();
+/// }
+/// unsafe { avr_device::interrupt::enable() };
+/// ```
+///
+/// # Safety
+/// This enables interrupts, so it must not be called inside a critical section.  See
+/// [`avr_device::interrupt::enable()`].
+pub unsafe fn sleep<H, CPU: SleepOps<H>>(cpu: &mut CPU, mode: CPU::Mode) {
+    avr_device::interrupt::disable();
+    cpu.raw_set_mode(mode);
+    cpu.raw_set_enabled(true);
+    enable_interrupts_and_sleep();
+    cpu.raw_set_enabled(false);
+}
+
+/// Sleep like [`sleep()`], but turn off the brown-out detector while sleeping.
+///
+/// This saves about 20µA in power-down and power-save mode, where it has an effect at all.  The
+/// brown-out detector is turned on again when the CPU wakes up, so the supply voltage is not
+/// monitored for the time it takes to settle (about 60µs).
+///
+/// # Safety
+/// This enables interrupts, so it must not be called inside a critical section.  See
+/// [`avr_device::interrupt::enable()`].
+pub unsafe fn sleep_with_bod_disabled<H, CPU: SleepBodOps<H>>(cpu: &mut CPU, mode: CPU::Mode) {
+    avr_device::interrupt::disable();
+    cpu.raw_set_mode(mode);
+    cpu.raw_set_enabled(true);
+    cpu.raw_disable_bod_and_sleep();
+    cpu.raw_set_enabled(false);
+}
+
+/// Wake up from sleep when the watchdog times out.
+///
+/// This starts the watchdog in interrupt mode, so it does not reset the MCU.  The `WDT` interrupt
+/// handler must be defined.
+pub fn wake_on_watchdog<H, WDT: WdtInterruptOps<H>>(
+    wdt: &mut Wdt<H, WDT>,
+    timeout: Timeout,
+) -> Result<(), WakeError> {
+    wdt.start(timeout, Mode::Interrupt)
+        .map_err(|()| WakeError::UnsupportedTimeout)
+}
+
+/// Wake up from sleep when the given external interrupt triggers.
+///
+/// The interrupt flag is cleared first, so only new events wake the CPU up.  In the sleep modes
+/// which stop the I/O clock, only [`Sense::LowLevel`] is detected on most MCUs.
+pub fn wake_on_interrupt<H, EXINT: ExternalInterruptOps<H>>(
+    exint: &mut EXINT,
+    int: EXINT::Interrupt,
+    sense: Sense,
+) {
+    // Changing the sense can set the interrupt flag, so the interrupt is disabled in between.
+    exint.raw_set_interrupt(int, false);
+    exint.raw_set_sense(int, sense);
+    exint.raw_clear_flag(int);
+    exint.raw_set_interrupt(int, true);
+}
+
+/// Stop waking up from sleep when the given external interrupt triggers.
+pub fn disable_wake_on_interrupt<H, EXINT: ExternalInterruptOps<H>>(
+    exint: &mut EXINT,
+    int: EXINT::Interrupt,
+) {
+    exint.raw_set_interrupt(int, false);
+}
+
+/// Wake up from sleep when the pin with the given PCINT number changes.
+///
+/// The PCINT numbers are found in the pinout of the MCU datasheet.  All pins of a group share one
+/// interrupt (`PCINT0` for PCINT0 to PCINT7 and so on).  Fails with
+/// [`WakeError::NoSuchPinChange`] if the MCU has no such pin change interrupt.
+pub fn wake_on_pin_change<H, EXINT: PinChangeOps<H>>(
+    exint: &mut EXINT,
+    pcint: u8,
+) -> Result<(), WakeError> {
+    if exint.raw_set_pin_change(pcint, true) {
+        Ok(())
+    } else {
+        Err(WakeError::NoSuchPinChange)
+    }
+}
+
+/// Stop waking up from sleep when the pin with the given PCINT number changes.
+pub fn disable_wake_on_pin_change<H, EXINT: PinChangeOps<H>>(
+    exint: &mut EXINT,
+    pcint: u8,
+) -> Result<(), WakeError> {
+    if exint.raw_set_pin_change(pcint, false) {
+        Ok(())
+    } else {
+        Err(WakeError::NoSuchPinChange)
+    }
+}
+
+/// Clock the asynchronous timer from a 32.768 kHz watch crystal on TOSC1/TOSC2, so it keeps
+/// running (and can wake the CPU up) in power-save mode.
+///
+/// Switching the clock source can corrupt the timer registers, so the timer interrupts must be
+/// disabled before and the timer must be configured afterwards.  The crystal needs up to a second
+/// to stabilize after power-up.
+pub fn set_async_timer_clock<H, TC: AsyncTimerOps<H>>(tc: &mut TC, enable: bool) {
+    tc.raw_set_async(enable);
+}
+
+/// Wait until writes to the asynchronous timer registers have taken effect.
+///
+/// This must be done before entering power-save mode after writing to the timer, or the CPU might
+/// not wake up.  After waking up from power-save mode, write one of the timer registers (for
+/// example, the compare register with its own value) and wait again before going back to sleep,
+/// to make sure the wake-up logic has seen a whole oscillator cycle.
+pub fn wait_for_async_timer<H, TC: AsyncTimerOps<H>>(tc: &TC) {
+    while tc.raw_is_updating() {}
+}
+
+/// Start or stop the clock of a peripheral which is not used through a HAL driver.
+///
+/// The peripheral has to be disabled before stopping its clock, so it does not stay in an active
+/// state.  Its registers cannot be accessed while the clock is stopped.
+pub fn set_powered<H, P: PowerReductionOps<H>>(p: &mut P, powered: bool) {
+    p.raw_set_powered(powered);
+}
+
+#[macro_export]
+macro_rules! impl_sleep {
+    (
+        hal: $HAL:ty,
+        peripheral: $CPU:ty,
+        mode: $Mode:ty,
+        register: $reg:ident,
+        set_mode: |$mode:ident, $w:ident| $set_mode:expr,
+    ) => {
+        impl $crate::power::SleepOps<$HAL> for $CPU {
+            type Mode = $Mode;
+
+            #[inline]
+            fn raw_set_mode(&mut self, mode: Self::Mode) {
+                self.$reg().modify(|_, w| {
+                    let $mode = mode;
+                    let $w = w;
+                    $set_mode
+                });
+            }
+
+            #[inline]
+            fn raw_set_enabled(&mut self, enable: bool) {
+                self.$reg().modify(|_, w| w.se().bit(enable));
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_sleep_bod_disable {
+    (
+        hal: $HAL:ty,
+        peripheral: $CPU:ty,
+        register: $reg:ident,
+        bods: $bods:literal,
+        bodse: $bodse:literal,
+    ) => {
+        impl $crate::power::SleepBodOps<$HAL> for $CPU {
+            #[inline]
+            fn raw_disable_bod_and_sleep(&mut self) {
+                let value = self.$reg().read().bits() | (1 << $bods);
+                // SAFETY: The pointer refers to the register which holds the BOD disable bits.
+                unsafe {
+                    $crate::power::disable_bod_and_sleep(
+                        self.$reg().as_ptr(),
+                        value | (1 << $bodse),
+                        value & !(1 << $bodse),
+                    )
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_external_interrupts {
+    (
+        hal: $HAL:ty,
+        peripheral: $EXINT:ty,
+        interrupt: $Interrupt:ty,
+        mask: $mask:ident,
+        flags: $flags:ident,
+        bit: |$bint:ident| $bit:expr,
+        set_sense: |$exint:ident, $sint:ident, $isc:ident| $set_sense:expr,
+        $(pin_change: {
+            control: $pcicr:ident,
+            first_bit: $first:literal,
+            masks: [$($group:literal: $pcmsk:ident),+ $(,)?],
+        },)?
+    ) => {
+        impl $crate::power::ExternalInterruptOps<$HAL> for $EXINT {
+            type Interrupt = $Interrupt;
+
+            #[inline]
+            fn raw_set_sense(&mut self, int: Self::Interrupt, sense: $crate::power::Sense) {
+                // The same encoding is used for the interrupt sense control bits on all MCUs.
+                let $isc: u8 = match sense {
+                    $crate::power::Sense::LowLevel => 0b00,
+                    $crate::power::Sense::AnyEdge => 0b01,
+                    $crate::power::Sense::FallingEdge => 0b10,
+                    $crate::power::Sense::RisingEdge => 0b11,
+                };
+                let $exint = self;
+                let $sint = int;
+                $set_sense
+            }
+
+            #[inline]
+            fn raw_set_interrupt(&mut self, int: Self::Interrupt, enable: bool) {
+                let $bint = int;
+                let mask: u8 = 1 << $bit;
+                // SAFETY: Only the enable bit of this interrupt is changed.
+                self.$mask().modify(|r, w| unsafe {
+                    w.bits(if enable {
+                        r.bits() | mask
+                    } else {
+                        r.bits() & !mask
+                    })
+                });
+            }
+
+            #[inline]
+            fn raw_clear_flag(&mut self, int: Self::Interrupt) {
+                let $bint = int;
+                // SAFETY: Writing a one clears the flag, zeros leave the other flags alone.
+                self.$flags().write(|w| unsafe { w.bits(1 << $bit) });
+            }
+        }
+
+        $(
+            impl $crate::power::PinChangeOps<$HAL> for $EXINT {
+                #[inline]
+                fn raw_set_pin_change(&mut self, pcint: u8, enable: bool) -> bool {
+                    let group = pcint / 8;
+                    let pin: u8 = 1 << (pcint % 8);
+                    // SAFETY: Only the mask bit of this pin is changed.
+                    let used = match group {
+                        $(
+                            $group => {
+                                self.$pcmsk().modify(|r, w| unsafe {
+                                    w.bits(if enable { r.bits() | pin } else { r.bits() & !pin })
+                                });
+                                self.$pcmsk().read().bits() != 0
+                            }
+                        )+
+                        _ => return false,
+                    };
+                    let control: u8 = 1 << ($first + group);
+                    // SAFETY: Only the enable bit of this group is changed.
+                    self.$pcicr().modify(|r, w| unsafe {
+                        w.bits(if used {
+                            r.bits() | control
+                        } else {
+                            r.bits() & !control
+                        })
+                    });
+                    true
+                }
+            }
+        )?
+    };
+}
+
+#[macro_export]
+macro_rules! impl_async_timer {
+    (
+        hal: $HAL:ty,
+        peripheral: $TC:ty,
+        async_clock: $as:ident,
+        busy_mask: $busy:literal,
+    ) => {
+        impl $crate::power::AsyncTimerOps<$HAL> for $TC {
+            #[inline]
+            fn raw_set_async(&mut self, enable: bool) {
+                self.assr().modify(|_, w| w.$as().bit(enable));
+            }
+
+            #[inline]
+            fn raw_is_updating(&self) -> bool {
+                self.assr().read().bits() & $busy != 0
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_power_reduction {
+    (
+        hal: $HAL:ty,
+        peripheral: $CPU:ty,
+        gates: [$($P:ty: $reg:ident.$bit:ident),+ $(,)?],
+    ) => {
+        $(
+            impl $crate::power::PowerReductionOps<$HAL> for $P {
+                #[inline]
+                fn raw_set_powered(&mut self, powered: bool) {
+                    // SAFETY: The power reduction registers are shared by all peripherals, so they
+                    // are only modified in a critical section and only the bit of this peripheral
+                    // is changed.
+                    let cpu = unsafe { &*<$CPU>::ptr() };
+                    $crate::avr_device::interrupt::free(|_| {
+                        cpu.$reg().modify(|_, w| w.$bit().bit(!powered));
+                    });
+                }
+            }
+        )+
+
+        /// Stop the clocks of all peripherals which can be turned off in the Power Reduction
+        /// Register.
+        ///
+        /// This is meant to be called at boot, before constructing any drivers.  The HAL drivers
+        /// start the clock of their peripheral again, everything else has to be powered up with
+        /// [`set_powered()`].
+        pub fn power_down_unused(cpu: &mut $CPU) {
+            $crate::avr_device::interrupt::free(|_| {
+                $(cpu.$reg().modify(|_, w| w.$bit().set_bit());)+
+            });
+        }
+    };
+    (
+        hal: $HAL:ty,
+        peripheral: $CPU:ty,
+        always_on: [$($P:ty),+ $(,)?],
+    ) => {
+        $(
+            impl $crate::power::PowerReductionOps<$HAL> for $P {
+                #[inline]
+                fn raw_set_powered(&mut self, _powered: bool) {}
+            }
+        )+
+
+        /// Does nothing, as this MCU has no Power Reduction Register.
+        pub fn power_down_unused(_cpu: &mut $CPU) {}
+    };
+}
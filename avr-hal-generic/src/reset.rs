@@ -0,0 +1,242 @@
+//! Reset cause reporting
+//!
+//! The MCU Status Register (MCUSR) records which sources caused the last reset.  The flags stay
+//! set until they are cleared by software, so without clearing them on every boot, flags of
+//! earlier resets accumulate.  When the HAL is built with the `reset-cause` feature, a hook in the
+//! `.init3` section takes a snapshot of MCUSR before `main()` runs and clears it afterwards.
+//!
+//! # Bootloaders
+//! Arduino bootloaders clear MCUSR before starting the application.  Optiboot (and compatible
+//! bootloaders) pass the original value in register `r2`, which the startup hook falls back to if
+//! MCUSR is empty.  With other bootloaders, the reset cause is lost and [`ResetCause::is_empty()`]
+//! is true.
+
+/// Source of a reset
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetSource {
+    /// The supply voltage rose above the power-on reset threshold.
+    PowerOn,
+    /// The external reset pin was pulled low.
+    External,
+    /// The supply voltage dropped below the brown-out detection level.
+    BrownOut,
+    /// The watchdog timed out in reset mode.
+    Watchdog,
+    /// A reset was requested through the JTAG interface.
+    Jtag,
+    /// The USB controller detected a USB reset (only on MCUs with USB).
+    Usb,
+}
+
+impl ResetSource {
+    const ALL: [ResetSource; 6] = [
+        ResetSource::PowerOn,
+        ResetSource::External,
+        ResetSource::BrownOut,
+        ResetSource::Watchdog,
+        ResetSource::Jtag,
+        ResetSource::Usb,
+    ];
+
+    /// The reset flags in MCUSR are in the same order on all AVR MCUs.
+    const fn mask(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Set of reset sources, decoded from MCUSR
+///
+/// # Example
+/// ```
+/// let cause = ResetCause::read(&dp.CPU);
+///
+/// if cause.contains(ResetSource::Watchdog) {
+///     // recover from the crash
+/// }
+///
+/// if cause.source() == Some(ResetSource::BrownOut) {
+///     // check the battery
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResetCause {
+    bits: u8,
+}
+
+impl ResetCause {
+    /// Mask of all reset flags in MCUSR.
+    pub const MASK: u8 = 0x3f;
+
+    /// Decode the raw value of MCUSR.
+    pub const fn from_bits(bits: u8) -> Self {
+        Self {
+            bits: bits & Self::MASK,
+        }
+    }
+
+    /// Raw reset flags, as found in MCUSR.
+    pub const fn bits(self) -> u8 {
+        self.bits
+    }
+
+    /// Whether `source` caused a reset.
+    pub const fn contains(self, source: ResetSource) -> bool {
+        self.bits & source.mask() != 0
+    }
+
+    /// Whether no reset flag is set, i.e. the reset cause is unknown.
+    pub const fn is_empty(self) -> bool {
+        self.bits == 0
+    }
+
+    /// The reset sources which are set.
+    pub fn iter(self) -> impl Iterator<Item = ResetSource> {
+        ResetSource::ALL
+            .into_iter()
+            .filter(move |source| self.contains(*source))
+    }
+
+    /// The source which most likely caused the last reset, if the flags were not cleared since an
+    /// earlier one.
+    ///
+    /// A power-on reset sets the power-on flag along with whatever the ramping supply voltage
+    /// triggered, so it takes priority, followed by brown-out.
+    pub fn source(self) -> Option<ResetSource> {
+        [
+            ResetSource::PowerOn,
+            ResetSource::BrownOut,
+            ResetSource::Watchdog,
+            ResetSource::External,
+            ResetSource::Jtag,
+            ResetSource::Usb,
+        ]
+        .into_iter()
+        .find(|source| self.contains(*source))
+    }
+
+    /// Get the reset cause of the current run.
+    ///
+    /// This is the snapshot taken at startup if the HAL was built with the `reset-cause` feature,
+    /// or the current content of MCUSR otherwise.
+    pub fn read<H, CPU: ResetCauseOps<H>>(cpu: &CPU) -> Self {
+        Self::from_bits(cpu.raw_snapshot().unwrap_or_else(|| cpu.raw_read_flags()))
+    }
+
+    /// Get the reset cause like [`ResetCause::read()`] and clear the flags in MCUSR, so the next
+    /// reset is reported on its own.
+    ///
+    /// Note that [`Wdt::new()`][crate::wdt::Wdt::new] also clears the watchdog flag.
+    pub fn take<H, CPU: ResetCauseOps<H>>(cpu: &mut CPU) -> Self {
+        let cause = Self::read(cpu);
+        cpu.raw_clear_flags();
+        cause
+    }
+}
+
+/// Internal trait for low-level reset flag access.
+///
+/// **HAL users should use the [`ResetCause`] type instead.**
+pub trait ResetCauseOps<H> {
+    /// Read the reset flags from MCUSR.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_flags(&self) -> u8;
+
+    /// Clear the reset flags in MCUSR.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_flags(&mut self);
+
+    /// Reset flags captured at startup, if the startup hook is enabled.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_snapshot(&self) -> Option<u8>;
+}
+
+#[macro_export]
+macro_rules! impl_reset_cause {
+    (
+        hal: $HAL:ty,
+        peripheral: $CPU:ty,
+        register: $reg:ident,
+        snapshot: $snapshot:expr,
+    ) => {
+        impl $crate::reset::ResetCauseOps<$HAL> for $CPU {
+            #[inline]
+            fn raw_read_flags(&self) -> u8 {
+                self.$reg().read().bits() & $crate::reset::ResetCause::MASK
+            }
+
+            #[inline]
+            fn raw_clear_flags(&mut self) {
+                // Some MCUs keep unrelated bits like JTD in the same register, so only the reset
+                // flags are cleared (by writing zeros).
+                // SAFETY: Only the reset flags are changed.
+                self.$reg()
+                    .modify(|r, w| unsafe { w.bits(r.bits() & !$crate::reset::ResetCause::MASK) });
+            }
+
+            #[inline]
+            fn raw_snapshot(&self) -> Option<u8> {
+                $snapshot
+            }
+        }
+    };
+}
+
+/// Install the startup hook which takes a snapshot of the reset flags.
+///
+/// The hook runs in the `.init3` section, after the stack pointer was set up but before `.data`
+/// and `.bss` are initialized, so the snapshot is kept in `.noinit`.  `$io_addr` is the I/O
+/// address of MCUSR (MCUCSR on older MCUs) of the MCU, for the `in`/`out` instructions.
+#[macro_export]
+macro_rules! impl_reset_cause_hook {
+    (
+        io_address: $io_addr:literal,
+    ) => {
+        #[link_section = ".noinit"]
+        static mut RESET_CAUSE_SNAPSHOT: core::mem::MaybeUninit<u8> =
+            core::mem::MaybeUninit::uninit();
+
+        /// Reset flags captured by the startup hook.
+        pub(crate) fn reset_cause_snapshot() -> u8 {
+            // SAFETY: Initialized by the startup hook before `main()` and never written again.
+            unsafe {
+                core::ptr::addr_of!(RESET_CAUSE_SNAPSHOT)
+                    .read_volatile()
+                    .assume_init()
+            }
+        }
+
+        // Take the reset flags from MCUSR or, if a bootloader cleared them, from `r2` where
+        // Optiboot leaves them.  Then clear the flags in MCUSR, keeping unrelated bits like JTD.
+        //
+        // Module-level assembly is assembled for the minimal AVR core regardless of the target
+        // CPU (LLVM does so when the crate is compiled to bitcode for LTO), and that core has no
+        // SRAM store instructions.  `st Z, r24` is rejected there, so the `st_z` macro encodes
+        // `st Z, Rr` (`1000 001r rrrr 0000`) from the register number instead.
+        core::arch::global_asm!(
+            ".pushsection .init3,\"ax\",@progbits",
+            ".macro st_z reg",
+            ".word 0x8200 | (\\reg << 4)",
+            ".endm",
+            "in r24, {mcusr}",
+            "andi r24, {mask}",
+            "brne 1f",
+            "mov r24, r2",
+            "andi r24, {mask}",
+            "1:",
+            "in r25, {mcusr}",
+            "cbr r25, {mask}",
+            "out {mcusr}, r25",
+            "ldi r30, lo8({snapshot})",
+            "ldi r31, hi8({snapshot})",
+            "st_z 24",
+            ".purgem st_z",
+            ".popsection",
+            mcusr = const $io_addr,
+            mask = const $crate::reset::ResetCause::MASK,
+            snapshot = sym RESET_CAUSE_SNAPSHOT,
+        );
+    };
+}
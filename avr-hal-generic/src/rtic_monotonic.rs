@@ -0,0 +1,231 @@
+//! [RTIC](https://rtic.rs) monotonic timers
+//!
+//! A 16-bit timer/counter runs freely at the tick rate of the monotonic and its overflows are
+//! counted in software to extend it to 64 bits.  Compare channel A is used to schedule the next
+//! wakeup of the RTIC timer queue.
+//!
+//! The MCU crates define the monotonic types for specific timers using [`impl_rtic_monotonic!`]
+//! and implement their interrupt handlers.  The tick rate is a const generic parameter and must
+//! be the core clock divided by one of the prescalers of the timer.
+//!
+//! [`impl_rtic_monotonic!`]: crate::impl_rtic_monotonic
+use core::cell::RefCell;
+
+use avr_device::interrupt::Mutex;
+
+use crate::timer::{Event, ExtendedCounter, OutputCompare, TimerOps};
+
+pub use rtic_time;
+
+/// Timer state shared between a monotonic and its interrupt handlers
+///
+/// **Prefer using the monotonic types of the MCU crate instead of this type.**
+pub struct MonotonicTimer<H, TC> {
+    counter: Mutex<RefCell<Option<ExtendedCounter<H, TC>>>>,
+}
+
+impl<H, TC> MonotonicTimer<H, TC> {
+    pub const fn new() -> Self {
+        Self {
+            counter: Mutex::new(RefCell::new(None)),
+        }
+    }
+}
+
+impl<H, TC> Default for MonotonicTimer<H, TC> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H, TC: TimerOps<H>> MonotonicTimer<H, TC> {
+    /// Start the timer at the given tick rate.
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
+    ///
+    /// # Panics
+    /// Panics if the timer cannot run at `tick_hz` with the given core clock.
+    pub fn start<CLOCK: crate::clock::Clock>(&self, timer: TC, tick_hz: u32)
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        avr_device::interrupt::free(|cs| {
+            let counter = ExtendedCounter::start::<CLOCK>(timer, tick_hz as u64);
+            self.counter.borrow(cs).replace(Some(counter));
+        });
+    }
+
+    /// Current time in ticks.
+    pub fn now(&self) -> u64 {
+        avr_device::interrupt::free(|cs| {
+            self.counter
+                .borrow(cs)
+                .borrow()
+                .as_ref()
+                .map_or(0, ExtendedCounter::now)
+        })
+    }
+
+    /// Schedule the compare interrupt for `instant`.
+    ///
+    /// Instants more than one counter period away produce an early interrupt, after which the
+    /// timer queue schedules the compare interrupt again.
+    pub fn set_compare(&self, instant: u64) {
+        self.with_timer(|timer| {
+            timer.raw_set_compare(OutputCompare::A, (instant % (TC::MAX as u64 + 1)) as u16)
+        });
+    }
+
+    /// Clear the compare interrupt flag.
+    pub fn clear_compare_flag(&self) {
+        self.with_timer(|timer| timer.raw_clear_pending(Event::CompareA));
+    }
+
+    /// Enable or disable the compare interrupt.  The counter keeps running either way.
+    pub fn set_compare_enabled(&self, enable: bool) {
+        self.with_timer(|timer| timer.raw_set_interrupt(Event::CompareA, enable));
+    }
+
+    /// Let the hardware raise the compare interrupt as soon as possible.
+    ///
+    /// AVR cannot pend an interrupt from software, so compare register A is set just ahead of the
+    /// counter and the compare interrupt is enabled.  If the counter already passed the compare
+    /// value when it was written, the distance is doubled and the write repeated.
+    pub fn pend_compare(&self) {
+        self.with_timer(|timer| {
+            let mut ahead: u16 = 1;
+            loop {
+                let target = timer.raw_read_counter().wrapping_add(ahead) & TC::MAX;
+                timer.raw_set_compare(OutputCompare::A, target);
+                let left = target.wrapping_sub(timer.raw_read_counter()) & TC::MAX;
+                if (1..=ahead).contains(&left) || timer.raw_is_pending(Event::CompareA) {
+                    break;
+                }
+                ahead = ahead.saturating_mul(2);
+            }
+            timer.raw_set_interrupt(Event::CompareA, true);
+        });
+    }
+
+    /// Handler for the overflow interrupt of the timer.
+    pub fn on_overflow(&self) {
+        avr_device::interrupt::free(|cs| {
+            if let Some(counter) = self.counter.borrow(cs).borrow_mut().as_mut() {
+                counter.on_overflow();
+            }
+        })
+    }
+
+    fn with_timer(&self, f: impl FnOnce(&mut TC)) {
+        avr_device::interrupt::free(|cs| {
+            if let Some(counter) = self.counter.borrow(cs).borrow_mut().as_mut() {
+                f(counter.timer());
+            }
+        })
+    }
+}
+
+/// Define an RTIC monotonic for a timer.
+///
+/// This creates a backend type, which holds the timer state and the timer queue, and the
+/// monotonic type `$Mono<CLOCK, TICK_HZ>`.  The interrupt handlers for overflow and compare A of
+/// the timer must call `$Backend::on_overflow()` and `$Backend::on_compare()`.
+#[macro_export]
+macro_rules! impl_rtic_monotonic {
+    (
+        $(#[$mono_attr:meta])*
+        pub struct $Mono:ident {
+            backend: $Backend:ident,
+            hal: $HAL:ty,
+            timer: $TC:ty,
+        }
+    ) => {
+        /// Timer queue backend of
+        #[doc = concat!("[`", stringify!($Mono), "`].")]
+        pub struct $Backend;
+
+        impl $Backend {
+            #[inline]
+            fn timer() -> &'static $crate::rtic_monotonic::MonotonicTimer<$HAL, $TC> {
+                static TIMER: $crate::rtic_monotonic::MonotonicTimer<$HAL, $TC> =
+                    $crate::rtic_monotonic::MonotonicTimer::new();
+                &TIMER
+            }
+
+            /// Handler for the overflow interrupt of the timer.
+            #[inline]
+            pub fn on_overflow() {
+                Self::timer().on_overflow();
+            }
+
+            /// Handler for the compare A interrupt of the timer.
+            #[inline]
+            pub fn on_compare() {
+                use $crate::rtic_monotonic::rtic_time::timer_queue::TimerQueueBackend;
+                // SAFETY: Called from the interrupt handler of the timer.
+                unsafe { Self::timer_queue().on_monotonic_interrupt() };
+            }
+        }
+
+        impl $crate::rtic_monotonic::rtic_time::timer_queue::TimerQueueBackend for $Backend {
+            type Ticks = u64;
+
+            fn now() -> u64 {
+                Self::timer().now()
+            }
+
+            fn set_compare(instant: u64) {
+                Self::timer().set_compare(instant);
+            }
+
+            fn clear_compare_flag() {
+                Self::timer().clear_compare_flag();
+            }
+
+            fn pend_interrupt() {
+                Self::timer().pend_compare();
+            }
+
+            fn enable_timer() {
+                Self::timer().set_compare_enabled(true);
+            }
+
+            fn disable_timer() {
+                Self::timer().set_compare_enabled(false);
+            }
+
+            fn timer_queue() -> &'static $crate::rtic_monotonic::rtic_time::timer_queue::TimerQueue<Self> {
+                static QUEUE: $crate::rtic_monotonic::rtic_time::timer_queue::TimerQueue<$Backend> =
+                    $crate::rtic_monotonic::rtic_time::timer_queue::TimerQueue::new();
+                &QUEUE
+            }
+        }
+
+        $(#[$mono_attr])*
+        pub struct $Mono<CLOCK, const TICK_HZ: u32> {
+            _clock: ::core::marker::PhantomData<CLOCK>,
+        }
+
+        impl<CLOCK: $crate::clock::Clock, const TICK_HZ: u32> $Mono<CLOCK, TICK_HZ> {
+            /// Start the monotonic on the timer.
+            ///
+            /// Global interrupts must be enabled for the time to advance.
+            ///
+            /// # Panics
+            /// Panics if the timer cannot be clocked at `TICK_HZ` from `CLOCK`.
+            pub fn start(timer: $TC) {
+                use $crate::rtic_monotonic::rtic_time::timer_queue::TimerQueueBackend;
+                $Backend::timer().start::<CLOCK>(timer, TICK_HZ);
+                $Backend::timer_queue().initialize($Backend);
+            }
+        }
+
+        impl<CLOCK, const TICK_HZ: u32> $crate::rtic_monotonic::rtic_time::monotonic::TimerQueueBasedMonotonic
+            for $Mono<CLOCK, TICK_HZ>
+        {
+            type Backend = $Backend;
+            type Instant = $crate::fugit::Instant<u64, 1, TICK_HZ>;
+            type Duration = $crate::fugit::Duration<u64, 1, TICK_HZ>;
+        }
+    };
+}
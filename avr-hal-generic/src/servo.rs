@@ -0,0 +1,381 @@
+//! Hobby servo control
+//!
+//! Servos expect a pulse every 20 ms ([`REFRESH_RATE`]) whose width sets the position, nominally
+//! 1 ms for one end and 2 ms for the other end of the range.  Most servos actually move further,
+//! so the pulse widths for 0° and 180° can be calibrated per servo.
+//!
+//! There are two drivers:
+//!
+//! - [`Servo`] uses a hardware PWM output of a 16-bit timer running [`Pwm16`] at
+//!   [`REFRESH_RATE`].  Each timer drives up to two or three servos (one per output compare pin)
+//!   without any CPU load.
+//! - [`ServoMux`] generates the pulses in software from the compare A interrupt of a timer, one
+//!   servo after the other.  It drives up to 12 servos on arbitrary pins, at the cost of some
+//!   jitter when other interrupts delay the handler.
+//!
+//! # Example
+//! ```
+//! let pwm = Pwm16::<_, CoreClock>::new(dp.TC1, servo::REFRESH_RATE).unwrap();
+//!
+//! let mut servo = Servo::new(pins.pb1.into_output(), &pwm);
+//! servo.calibrate(500.micros(), 2400.micros());
+//! servo.set_angle(90);
+//! ```
+use core::marker::PhantomData;
+
+use crate::port::{mode, Pin, PinOps};
+use crate::simple_pwm::{Pwm16, Pwm16Ops, PwmPinOps};
+use crate::timer::{Duration, Event, Hertz, OutputCompare, TimerError, TimerMode, TimerOps};
+
+/// Pulse repetition rate expected by servos (50 Hz).
+pub const REFRESH_RATE: Hertz = Hertz::from_raw(50);
+
+/// Longest supported pulse width.  Longer pulses are clamped.
+pub const MAX_PULSE_WIDTH: Duration = Duration::from_ticks(3000);
+
+/// Default pulse width for 0°, the same as in the Arduino `Servo` library.
+pub const DEFAULT_MIN_PULSE_WIDTH: Duration = Duration::from_ticks(544);
+
+/// Default pulse width for 180°, the same as in the Arduino `Servo` library.
+pub const DEFAULT_MAX_PULSE_WIDTH: Duration = Duration::from_ticks(2400);
+
+/// Pulse widths in µs for 0° and 180°.
+#[derive(Debug, Clone, Copy)]
+struct Calibration {
+    min: u16,
+    max: u16,
+}
+
+impl Calibration {
+    const DEFAULT: Self = Self {
+        min: DEFAULT_MIN_PULSE_WIDTH.ticks() as u16,
+        max: DEFAULT_MAX_PULSE_WIDTH.ticks() as u16,
+    };
+
+    fn new(min: Duration, max: Duration) -> Self {
+        Self {
+            min: clamp_pulse(min),
+            max: clamp_pulse(max),
+        }
+    }
+
+    /// Pulse width in µs for an angle of at most 180°.
+    fn pulse_for_angle(&self, degrees: u8) -> u16 {
+        let degrees = degrees.min(180) as i32;
+        let (min, max) = (self.min as i32, self.max as i32);
+        (min + (max - min) * degrees / 180) as u16
+    }
+}
+
+fn clamp_pulse(width: Duration) -> u16 {
+    width.to_micros().min(MAX_PULSE_WIDTH.ticks()) as u16
+}
+
+/// Servo on a hardware PWM output of a 16-bit timer.
+///
+/// The timer must run fast PWM at [`REFRESH_RATE`]; all of its PWM pins can drive a servo.
+///
+/// Creating the servo centers it (1.5 ms pulses) and enables the output.
+pub struct Servo<TC, PIN> {
+    pin: Pin<mode::PwmOutput<TC>, PIN>,
+    calibration: Calibration,
+    /// Timer ticks per millisecond.
+    ticks_per_ms: u32,
+    /// Current pulse width in µs.
+    pulse: u16,
+}
+
+impl<H, TC, CLOCK, PIN> Servo<Pwm16<H, TC, CLOCK>, PIN>
+where
+    TC: Pwm16Ops<H>,
+    CLOCK: crate::clock::Clock,
+    PIN: PwmPinOps<Pwm16<H, TC, CLOCK>, Duty = u16>,
+{
+    /// Attach a servo to a PWM pin of `timer`.
+    pub fn new(pin: Pin<mode::Output, PIN>, timer: &Pwm16<H, TC, CLOCK>) -> Self {
+        let mut servo = Self {
+            pin: crate::simple_pwm::IntoPwmPin::into_pwm(pin, timer),
+            calibration: Calibration::DEFAULT,
+            ticks_per_ms: CLOCK::FREQ / 1000 / timer.prescaler().divider(),
+            pulse: 0,
+        };
+        servo.set_pulse_width(Duration::from_ticks(1500));
+        servo.enable();
+        servo
+    }
+
+    /// Set the pulse widths for 0° and 180°.
+    ///
+    /// `min` may also be larger than `max` to reverse the direction of rotation.  The current
+    /// position is not changed.
+    pub fn calibrate(&mut self, min: Duration, max: Duration) {
+        self.calibration = Calibration::new(min, max);
+    }
+
+    /// Move to an angle between 0° and 180°.  Larger angles are clamped.
+    pub fn set_angle(&mut self, degrees: u8) {
+        let pulse = self.calibration.pulse_for_angle(degrees);
+        self.set_pulse_width(Duration::from_ticks(pulse as u32));
+    }
+
+    /// Set the raw pulse width, independent of the calibration.
+    ///
+    /// Pulses longer than [`MAX_PULSE_WIDTH`] are clamped.
+    pub fn set_pulse_width(&mut self, width: Duration) {
+        self.pulse = clamp_pulse(width);
+        let ticks = self.pulse as u32 * self.ticks_per_ms / 1000;
+        self.pin.set_duty(ticks as u16);
+    }
+
+    /// Current pulse width.
+    pub fn pulse_width(&self) -> Duration {
+        Duration::from_ticks(self.pulse as u32)
+    }
+
+    /// Resume sending pulses.
+    pub fn enable(&mut self) {
+        self.pin.enable();
+    }
+
+    /// Stop sending pulses.  Most servos then stop holding their position.
+    pub fn disable(&mut self) {
+        self.pin.disable();
+    }
+
+    /// Detach the servo and return the PWM pin.
+    pub fn release(self) -> Pin<mode::PwmOutput<Pwm16<H, TC, CLOCK>>, PIN> {
+        self.pin
+    }
+}
+
+struct Channel<PIN> {
+    pin: Option<Pin<mode::Output, PIN>>,
+    calibration: Calibration,
+    /// Pulse width in µs.
+    pulse: u16,
+}
+
+/// Software-multiplexed servo driver for up to `N` servos on arbitrary pins.
+///
+/// The timer counts freely and the driver schedules compare A interrupts at the end of each
+/// pulse.  The servos are pulsed one after the other, followed by a pause until 20 ms have passed
+/// since the start of the first pulse.  With many servos, a frame takes longer than 20 ms, which
+/// the servos tolerate.
+///
+/// **[`on_compare()`][ServoMux::on_compare] must be called from the `TIMERn_COMPA` interrupt
+/// handler.**  The driver is usually kept in a `Mutex<RefCell<_>>` which the interrupt handler
+/// and the main program share.
+///
+/// # Example
+/// ```
+/// static SERVOS: Mutex<RefCell<Option<ServoMux<_, CoreClock, Dynamic, 4>>>> = ...;
+///
+/// let mut servos = ServoMux::new(dp.TC1).unwrap();
+/// servos.attach(0, pins.pd2.into_output().downgrade());
+/// servos.attach(1, pins.pd3.into_output().downgrade());
+/// servos.set_angle(1, 45);
+/// interrupt::free(|cs| SERVOS.borrow(cs).replace(Some(servos)));
+///
+/// #[avr_device::interrupt(atmega328p)]
+/// fn TIMER1_COMPA() {
+///     interrupt::free(|cs| {
+///         if let Some(servos) = SERVOS.borrow(cs).borrow_mut().as_mut() {
+///             servos.on_compare();
+///         }
+///     })
+/// }
+/// ```
+pub struct ServoMux<H, TC, CLOCK, PIN, const N: usize> {
+    timer: TC,
+    channels: [Channel<PIN>; N],
+    /// Channel whose pulse is running, or `N` during the pause at the end of a frame.
+    current: usize,
+    /// Timer ticks since the start of the frame at which the next interrupt happens.
+    elapsed: u16,
+    /// Timer ticks per millisecond.
+    ticks_per_ms: u32,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC, CLOCK, PIN, const N: usize> ServoMux<H, TC, CLOCK, PIN, N>
+where
+    TC: TimerOps<H>,
+    CLOCK: crate::clock::Clock,
+    PIN: PinOps,
+{
+    /// Start the timer and enable its compare A interrupt.
+    ///
+    /// The prescaler is chosen such that a frame with `N` pulses of [`MAX_PULSE_WIDTH`] fits into
+    /// one counter period.  This usually needs a 16-bit timer.  The clock of the peripheral is
+    /// started in the Power Reduction Register, if necessary.
+    pub fn new(mut timer: TC) -> Result<Self, TimerError>
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        let frame_ms = (N as u32 * MAX_PULSE_WIDTH.ticks() / 1000 + 1).max(20);
+        let (prescaler, _) = crate::timer::Prescaler::for_cycles(
+            CLOCK::FREQ / 1000 * frame_ms,
+            TC::PRESCALERS,
+            TC::MAX,
+        )?;
+
+        let ticks_per_ms = CLOCK::FREQ / 1000 / prescaler.divider();
+        // The first interrupt starts the first frame.  A compare match on 0 would be blocked by
+        // the write to the counter and only happen after a full counter period.
+        let start = (ticks_per_ms / 10).max(1) as u16;
+
+        timer.raw_set_powered(true);
+        timer.raw_set_clock(None);
+        timer.raw_set_mode(TimerMode::Normal);
+        timer.raw_write_counter(0);
+        timer.raw_set_compare(OutputCompare::A, start);
+        timer.raw_clear_pending(Event::CompareA);
+        timer.raw_set_interrupt(Event::CompareA, true);
+
+        let mut mux = Self {
+            timer,
+            channels: core::array::from_fn(|_| Channel {
+                pin: None,
+                calibration: Calibration::DEFAULT,
+                pulse: 1500,
+            }),
+            current: N,
+            elapsed: start,
+            ticks_per_ms,
+            _clock: PhantomData,
+            _h: PhantomData,
+        };
+        mux.timer.raw_set_clock(Some(prescaler));
+        Ok(mux)
+    }
+
+    /// Attach a servo to `channel`, centered (1.5 ms pulses) with the default calibration.
+    ///
+    /// Returns the pin which was previously attached to this channel.
+    ///
+    /// # Panics
+    /// If `channel` is not below `N`.
+    pub fn attach(
+        &mut self,
+        channel: usize,
+        mut pin: Pin<mode::Output, PIN>,
+    ) -> Option<Pin<mode::Output, PIN>> {
+        pin.set_low();
+        let previous = self.detach(channel);
+        self.channels[channel] = Channel {
+            pin: Some(pin),
+            calibration: Calibration::DEFAULT,
+            pulse: 1500,
+        };
+        previous
+    }
+
+    /// Detach the servo from `channel` and return its pin, which is left low.
+    ///
+    /// # Panics
+    /// If `channel` is not below `N`.
+    pub fn detach(&mut self, channel: usize) -> Option<Pin<mode::Output, PIN>> {
+        let mut pin = self.channels[channel].pin.take()?;
+        pin.set_low();
+        Some(pin)
+    }
+
+    /// Set the pulse widths for 0° and 180° of the servo on `channel`.
+    ///
+    /// `min` may also be larger than `max` to reverse the direction of rotation.  The current
+    /// position is not changed.
+    ///
+    /// # Panics
+    /// If `channel` is not below `N`.
+    pub fn calibrate(&mut self, channel: usize, min: Duration, max: Duration) {
+        self.channels[channel].calibration = Calibration::new(min, max);
+    }
+
+    /// Move the servo on `channel` to an angle between 0° and 180°.  Larger angles are clamped.
+    ///
+    /// # Panics
+    /// If `channel` is not below `N`.
+    pub fn set_angle(&mut self, channel: usize, degrees: u8) {
+        let channel = &mut self.channels[channel];
+        channel.pulse = channel.calibration.pulse_for_angle(degrees);
+    }
+
+    /// Set the raw pulse width of the servo on `channel`, independent of the calibration.
+    ///
+    /// Pulses longer than [`MAX_PULSE_WIDTH`] are clamped.
+    ///
+    /// # Panics
+    /// If `channel` is not below `N`.
+    pub fn set_pulse_width(&mut self, channel: usize, width: Duration) {
+        self.channels[channel].pulse = clamp_pulse(width);
+    }
+
+    /// Current pulse width of the servo on `channel`.
+    ///
+    /// # Panics
+    /// If `channel` is not below `N`.
+    pub fn pulse_width(&self, channel: usize) -> Duration {
+        Duration::from_ticks(self.channels[channel].pulse as u32)
+    }
+
+    /// End the current pulse and start the next one.
+    ///
+    /// Call this from the `TIMERn_COMPA` interrupt handler.
+    pub fn on_compare(&mut self) {
+        if let Some(pin) = self
+            .channels
+            .get_mut(self.current)
+            .and_then(|c| c.pin.as_mut())
+        {
+            pin.set_low();
+        }
+
+        if self.current >= N {
+            // Start of a new frame.
+            self.current = 0;
+            self.elapsed = 0;
+            self.timer.raw_write_counter(0);
+        } else {
+            self.current += 1;
+        }
+
+        while self.current < N && self.channels[self.current].pin.is_none() {
+            self.current += 1;
+        }
+
+        if let Some(channel) = self.channels.get_mut(self.current) {
+            if let Some(pin) = channel.pin.as_mut() {
+                pin.set_high();
+            }
+            let ticks = channel.pulse as u32 * self.ticks_per_ms / 1000;
+            self.elapsed = (self.elapsed as u32 + ticks).min(TC::MAX as u32) as u16;
+        } else {
+            // Pause until the end of the frame, but leave the handler some time to return.
+            let frame = self.ticks_per_ms * 20;
+            let min_pause = self.ticks_per_ms / 10;
+            let end = frame.max(self.elapsed as u32 + min_pause);
+            self.elapsed = end.min(TC::MAX as u32) as u16;
+        }
+        self.timer.raw_set_compare(OutputCompare::A, self.elapsed);
+    }
+
+    /// Stop the timer and return it together with all attached pins (left low).
+    ///
+    /// The clock of the peripheral is stopped in the Power Reduction Register, if available.
+    pub fn release(mut self) -> (TC, [Option<Pin<mode::Output, PIN>>; N])
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        self.timer.raw_set_interrupt(Event::CompareA, false);
+        self.timer.raw_set_clock(None);
+        self.timer.raw_set_powered(false);
+        let pins = self.channels.map(|channel| {
+            channel.pin.map(|mut pin| {
+                pin.set_low();
+                pin
+            })
+        });
+        (self.timer, pins)
+    }
+}
@@ -5,24 +5,13 @@ use embedded_hal::pwm;
 use embedded_hal::pwm::{ErrorKind, ErrorType, SetDutyCycle};
 
 use crate::port::mode;
-use crate::port::Pin;
+use crate::port::{Pin, PinOps};
+use crate::timer::{Hertz, TimerError, TimerOps};
 
 /// Clock prescaler for PWM
 ///
-/// The prescaler dictates the PWM frequency, together with the IO clock.  The formula is as
-/// follows:
-///
-/// ```text
-/// F_pwm = CLK_io / (Prescaler * 256);
-/// ```
-///
-/// | Prescaler | 16 MHz Clock | 8 MHz Clock |
-/// | --- | --- | ---|
-/// | `Direct` | 62.5 kHz | 31.3 kHz |
-/// | `Prescale8` | 7.81 kHz | 3.91 kHz |
-/// | `Prescale64` | 977 Hz | 488 Hz |
-/// | `Prescale256` | 244 Hz | 122 Hz |
-/// | `Prescale1024` | 61.0 Hz | 30.5 Hz |
+/// The prescaler dictates the PWM frequency, together with the IO clock and the [`PwmMode`].  The
+/// resulting frequency can be queried with [`PwmTimer::frequency()`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Prescaler {
     /// No prescaling, the IO clock drives the timer directly.
@@ -37,16 +26,103 @@ pub enum Prescaler {
     Prescale1024,
 }
 
+impl Prescaler {
+    /// Division factor of this prescaler setting.
+    pub const fn divider(self) -> u32 {
+        match self {
+            Prescaler::Direct => 1,
+            Prescaler::Prescale8 => 8,
+            Prescaler::Prescale64 => 64,
+            Prescaler::Prescale256 => 256,
+            Prescaler::Prescale1024 => 1024,
+        }
+    }
+}
+
+/// Waveform generation mode for PWM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PwmMode {
+    /// Fast PWM: The counter counts from BOTTOM to TOP and then restarts at BOTTOM.
+    ///
+    /// The outputs are set at BOTTOM and cleared on compare match, so pulses are aligned at the
+    /// start of the period.
+    Fast,
+    /// Phase-correct PWM: The counter counts up from BOTTOM to TOP and back down again.
+    ///
+    /// Pulses are centered in the period, at half the frequency of fast PWM.  Duty cycle updates
+    /// take effect at TOP.
+    PhaseCorrect,
+    /// Phase- and frequency-correct PWM: Like [`PwmMode::PhaseCorrect`], but duty cycle updates
+    /// take effect at BOTTOM, so each period is symmetric.
+    ///
+    /// Only supported by 16-bit timers.
+    PhaseFrequencyCorrect,
+}
+
+impl PwmMode {
+    /// Number of timer ticks per PWM period for the given TOP value.
+    ///
+    /// This is `top + 1` in fast PWM mode and `2 * top` in the phase-correct modes, where the
+    /// counter counts up and down again.
+    pub const fn period_ticks(self, top: u16) -> u32 {
+        match self {
+            PwmMode::Fast => top as u32 + 1,
+            PwmMode::PhaseCorrect | PwmMode::PhaseFrequencyCorrect => 2 * top as u32,
+        }
+    }
+}
+
 /// Implement traits and types for PWM timers
 pub trait PwmPinOps<TC> {
     type Duty;
 
     fn enable(&mut self);
+    fn enable_inverted(&mut self);
     fn disable(&mut self);
     fn get_duty(&self) -> Self::Duty;
     fn get_max_duty(&self) -> Self::Duty;
 
-    fn set_duty(&mut self, value: u8);
+    fn set_duty(&mut self, value: Self::Duty);
+}
+
+/// Internal trait giving access to the timer peripheral behind a PWM timer.
+///
+/// Other drivers which share the timer with the PWM outputs use this, for example
+/// [`SysTime::with_pwm_timer()`][crate::time::SysTime::with_pwm_timer].
+pub trait PwmTimer {
+    /// The timer peripheral.
+    type Timer;
+
+    /// Prescaler the timer was configured with.
+    fn prescaler(&self) -> Prescaler;
+
+    /// TOP value of the counter.
+    fn top(&self) -> u16;
+
+    /// Waveform generation mode the timer was configured with.
+    fn mode(&self) -> PwmMode;
+
+    /// Number of timer ticks per PWM period, see [`PwmMode::period_ticks()`].
+    fn period_ticks(&self) -> u32 {
+        self.mode().period_ticks(self.top())
+    }
+
+    /// The PWM frequency for the given core clock.
+    ///
+    /// # Example
+    /// ```
+    /// let timer0 = Timer0Pwm::new(dp.TC0, Prescaler::Prescale64);
+    /// // 976 Hz for a 16 MHz clock
+    /// let frequency = timer0.frequency::<CoreClock>();
+    /// ```
+    fn frequency<CLOCK: crate::clock::Clock>(&self) -> Hertz {
+        Hertz::from_raw(CLOCK::FREQ / (self.prescaler().divider() * self.period_ticks()))
+    }
+
+    /// Give up the PWM timer and return the timer peripheral.
+    ///
+    /// The timer keeps running, so PWM outputs which were already set up continue to work.
+    fn into_timer(self) -> Self::Timer;
 }
 
 pub trait IntoPwmPin<TC, PIN> {
@@ -63,10 +139,16 @@ impl<TC, PIN: PwmPinOps<TC>> IntoPwmPin<TC, PIN> for Pin<mode::Output, PIN> {
 }
 
 impl<TC, PIN: PwmPinOps<TC>> Pin<mode::PwmOutput<TC>, PIN> {
+    /// Enable the PWM output (non-inverting: high from BOTTOM until the compare match).
     pub fn enable(&mut self) {
         self.pin.enable();
     }
 
+    /// Enable the inverted PWM output (low from BOTTOM until the compare match).
+    pub fn enable_inverted(&mut self) {
+        self.pin.enable_inverted();
+    }
+
     pub fn disable(&mut self) {
         self.pin.disable();
     }
@@ -79,16 +161,20 @@ impl<TC, PIN: PwmPinOps<TC>> Pin<mode::PwmOutput<TC>, PIN> {
         self.pin.get_max_duty()
     }
 
-    pub fn set_duty(&mut self, duty: u8) {
+    pub fn set_duty(&mut self, duty: <PIN as PwmPinOps<TC>>::Duty) {
         self.pin.set_duty(duty);
     }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum PwmError {
-    /// `embedded-hal` supports duty cycles up to `u16`, however `avr` devices only support up to `u8`.
-    /// Passing a duty cycle larger than [`u8::MAX`] will result in this error.
+    /// The duty cycle is larger than the maximum duty cycle of the timer: [`u8::MAX`] for 8-bit
+    /// PWM and the TOP value for [`Pwm16`].
     DutyCycleTooLarge,
+    /// The timer does not support the requested [`PwmMode`].
+    UnsupportedMode,
+    /// The dead time is longer than the dead-time generator supports.
+    DeadTimeTooLong,
 }
 
 impl pwm::Error for PwmError {
@@ -101,31 +187,400 @@ impl<TC, PIN: PwmPinOps<TC>> ErrorType for Pin<mode::PwmOutput<TC>, PIN> {
     type Error = PwmError;
 }
 
-impl<TC, PIN: PwmPinOps<TC, Duty = u8>> SetDutyCycle for Pin<mode::PwmOutput<TC>, PIN> {
+impl<TC, PIN> SetDutyCycle for Pin<mode::PwmOutput<TC>, PIN>
+where
+    PIN: PwmPinOps<TC>,
+    PIN::Duty: Into<u16> + TryFrom<u16>,
+{
     fn max_duty_cycle(&self) -> u16 {
-        self.get_max_duty() as u16
+        self.get_max_duty().into()
     }
 
     fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
-        if duty > u8::MAX as u16 {
+        if duty > self.max_duty_cycle() {
             return Err(PwmError::DutyCycleTooLarge);
         }
-        self.set_duty(duty as u8);
+        let duty = PIN::Duty::try_from(duty).map_err(|_| PwmError::DutyCycleTooLarge)?;
+        self.set_duty(duty);
+        Ok(())
+    }
+}
+
+/// Clock divider of the dead-time generator
+///
+/// The dead-time generator runs from the clock source of the timer before the timer prescaler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadTimePrescaler {
+    Direct,
+    Prescale2,
+    Prescale4,
+    Prescale8,
+}
+
+/// Internal trait for PWM outputs with a complementary output (`!OCnx`) and a dead-time generator.
+///
+/// **Prefer using the [`ComplementaryPwm`] API instead of this trait.**
+pub trait ComplementaryPwmOps<TC>: PwmPinOps<TC> {
+    /// The complementary output pin (`!OCnx`).
+    type Complement: PinOps;
+
+    /// Enable both the output and the complementary output.
+    fn enable_complementary(&mut self);
+
+    /// Delay the rising edges of the output and the complementary output by the given number of
+    /// dead-time generator clock cycles (at most [`ComplementaryPwm::MAX_DEAD_TIME`]).
+    fn set_dead_time(&mut self, prescaler: DeadTimePrescaler, output: u8, complement: u8);
+}
+
+/// PWM output pair (`OCnx` and `!OCnx`) for driving a half-bridge
+///
+/// The complementary output is the inverse of the output, and the dead-time generator delays the
+/// rising edge of each output, so both are never high at the same time.
+///
+/// The dead-time generator is not always separate for each pair: The ATmega32U4 `TC4` has one
+/// dead time and one dead-time prescaler for all of its outputs, and the ATtiny85 `TC1` shares the
+/// dead-time prescaler between its two outputs.  Setting the dead time of one pair then changes
+/// it for the other pairs of the same timer as well.
+///
+/// # Example
+/// ```
+/// let timer1 = Timer1Pwm::new(dp.TC1, Prescaler::Prescale8);
+///
+/// let mut bridge = ComplementaryPwm::new(
+///     pins.pb1.into_output(),
+///     pins.pb0.into_output(),
+///     &timer1,
+/// );
+/// // 4 clock cycles between one output going low and the other going high
+/// bridge.set_dead_time(DeadTimePrescaler::Direct, 4, 4).unwrap();
+/// bridge.set_duty(128);
+/// bridge.enable();
+/// ```
+pub struct ComplementaryPwm<TC, PIN: ComplementaryPwmOps<TC>> {
+    pin: Pin<mode::PwmOutput<TC>, PIN>,
+    complement: Pin<mode::Output, PIN::Complement>,
+}
+
+impl<TC, PIN: ComplementaryPwmOps<TC>> ComplementaryPwm<TC, PIN> {
+    /// Longest dead time, in dead-time generator clock cycles.
+    pub const MAX_DEAD_TIME: u8 = 15;
+
+    /// Combine the output pin and the complementary output pin of `timer`.
+    ///
+    /// The outputs stay disabled until [`enable()`][ComplementaryPwm::enable] is called.
+    pub fn new(
+        pin: Pin<mode::Output, PIN>,
+        complement: Pin<mode::Output, PIN::Complement>,
+        timer: &TC,
+    ) -> Self {
+        Self {
+            pin: pin.into_pwm(timer),
+            complement,
+        }
+    }
+
+    /// Enable both outputs.
+    pub fn enable(&mut self) {
+        self.pin.pin.enable_complementary();
+    }
+
+    /// Disable both outputs.  The pins are driven by their port registers again.
+    pub fn disable(&mut self) {
+        self.pin.disable();
+    }
+
+    /// Set the dead time, in cycles of the dead-time generator clock, after which the output and
+    /// the complementary output go high.
+    ///
+    /// On timers with a shared dead-time generator (see [`ComplementaryPwm`]) this also applies
+    /// to all other complementary outputs of the timer.
+    pub fn set_dead_time(
+        &mut self,
+        prescaler: DeadTimePrescaler,
+        output: u8,
+        complement: u8,
+    ) -> Result<(), PwmError> {
+        if output > Self::MAX_DEAD_TIME || complement > Self::MAX_DEAD_TIME {
+            return Err(PwmError::DeadTimeTooLong);
+        }
+        self.pin.pin.set_dead_time(prescaler, output, complement);
+        Ok(())
+    }
+
+    pub fn get_duty(&self) -> <PIN as PwmPinOps<TC>>::Duty {
+        self.pin.get_duty()
+    }
+
+    pub fn get_max_duty(&self) -> <PIN as PwmPinOps<TC>>::Duty {
+        self.pin.get_max_duty()
+    }
+
+    pub fn set_duty(&mut self, duty: <PIN as PwmPinOps<TC>>::Duty) {
+        self.pin.set_duty(duty);
+    }
+
+    /// Disable the outputs and return both pins.
+    pub fn release(
+        mut self,
+    ) -> (
+        Pin<mode::PwmOutput<TC>, PIN>,
+        Pin<mode::Output, PIN::Complement>,
+    ) {
+        self.disable();
+        (self.pin, self.complement)
+    }
+}
+
+/// Internal trait for 16-bit timers which support PWM with a variable TOP value.
+///
+/// **Prefer using the [`Pwm16`] API instead of this trait.**
+pub trait Pwm16Ops<H>: TimerOps<H> {
+    /// Select a PWM mode with the input capture register (`ICRn`) as TOP.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_init_pwm16(&mut self, mode: PwmMode);
+
+    /// Write the TOP value.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_top(&mut self, top: u16);
+}
+
+/// 16-bit PWM with configurable frequency
+///
+/// Unlike the 8-bit PWM timers, which always count to 255, this uses the input capture register
+/// (`ICRn`) as TOP.  The PWM frequency can therefore be chosen freely and the duty cycle has a
+/// resolution of up to 16 bits.  The maximum duty cycle of the pins is the TOP value, which is
+/// also reported as [`SetDutyCycle::max_duty_cycle()`].
+///
+/// All [`PwmMode`]s are supported.  In the phase-correct modes, the frequency for a given TOP
+/// value is halved.
+///
+/// # Example
+/// ```
+/// let mut pwm = Pwm16::<_, CoreClock>::new(dp.TC1, 50.Hz()).unwrap();
+///
+/// let mut d9 = pins.d9.into_output().into_pwm(&pwm);
+///
+/// // 1.5 ms pulses for a servo
+/// d9.set_duty((pwm.top() as u32 * 15 / 200) as u16);
+/// d9.enable();
+/// ```
+pub struct Pwm16<H, TC, CLOCK> {
+    timer: TC,
+    mode: PwmMode,
+    prescaler: crate::timer::Prescaler,
+    top: u16,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC, CLOCK> Pwm16<H, TC, CLOCK>
+where
+    TC: Pwm16Ops<H>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Smallest TOP value supported by the hardware (2-bit resolution).
+    pub const MIN_TOP: u16 = 3;
+
+    /// Start fast PWM at the given frequency.
+    ///
+    /// The prescaler and TOP value are chosen for the best possible duty cycle resolution.  The
+    /// clock of the peripheral is started in the Power Reduction Register, if necessary.
+    pub fn new(timer: TC, frequency: Hertz) -> Result<Self, TimerError>
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        Self::with_mode(timer, PwmMode::Fast, frequency)
+    }
+
+    /// Start PWM in the given mode at the given frequency.
+    ///
+    /// The prescaler and TOP value are chosen for the best possible duty cycle resolution.
+    pub fn with_mode(timer: TC, mode: PwmMode, frequency: Hertz) -> Result<Self, TimerError>
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        let mut pwm = Self::init(timer, mode);
+        pwm.set_frequency(frequency)?;
+        Ok(pwm)
+    }
+
+    /// Start fast PWM with a manually chosen prescaler and TOP value.
+    ///
+    /// The PWM frequency is `CLOCK / (prescaler * (top + 1))`.
+    pub fn with_top(
+        timer: TC,
+        prescaler: crate::timer::Prescaler,
+        top: u16,
+    ) -> Result<Self, TimerError>
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        let mut pwm = Self::init(timer, PwmMode::Fast);
+        pwm.set_period(prescaler, top)?;
+        Ok(pwm)
+    }
+
+    fn init(mut timer: TC, mode: PwmMode) -> Self
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        timer.raw_set_powered(true);
+        timer.raw_set_clock(None);
+        timer.raw_init_pwm16(mode);
+        timer.raw_write_counter(0);
+        Self {
+            timer,
+            mode,
+            prescaler: crate::timer::Prescaler::Direct,
+            top: 0,
+            _clock: PhantomData,
+            _h: PhantomData,
+        }
+    }
+
+    /// Change the PWM frequency.
+    ///
+    /// The duty cycles of the pins are not scaled, so they must be set again afterwards.
+    pub fn set_frequency(&mut self, frequency: Hertz) -> Result<(), TimerError> {
+        let hz = frequency.to_Hz();
+        if hz == 0 {
+            return Err(TimerError::PeriodTooLong);
+        }
+        let cycles = CLOCK::FREQ / hz;
+        let (prescaler, top) = match self.mode {
+            PwmMode::Fast => crate::timer::Prescaler::for_cycles(cycles, TC::PRESCALERS, TC::MAX)?,
+            PwmMode::PhaseCorrect | PwmMode::PhaseFrequencyCorrect => {
+                // One period takes 2 * TOP ticks.
+                let (prescaler, steps) =
+                    crate::timer::Prescaler::for_cycles(cycles / 2, TC::PRESCALERS, TC::MAX - 1)?;
+                (prescaler, steps + 1)
+            }
+        };
+        self.set_period(prescaler, top)
+    }
+
+    /// Change the prescaler and TOP value.
+    ///
+    /// The duty cycles of the pins are not scaled, so they must be set again afterwards.
+    pub fn set_period(
+        &mut self,
+        prescaler: crate::timer::Prescaler,
+        top: u16,
+    ) -> Result<(), TimerError> {
+        if !TC::PRESCALERS.contains(&prescaler) {
+            return Err(TimerError::UnsupportedPrescaler);
+        }
+        if top < Self::MIN_TOP {
+            return Err(TimerError::PeriodTooShort);
+        }
+        // TOP is not double-buffered: If it is lowered below the current counter value, the
+        // counter would run up to its maximum before wrapping around.
+        self.timer.raw_set_clock(None);
+        self.timer.raw_set_top(top);
+        if self.timer.raw_read_counter() > top {
+            self.timer.raw_write_counter(0);
+        }
+        self.timer.raw_set_clock(Some(prescaler));
+        self.prescaler = prescaler;
+        self.top = top;
         Ok(())
     }
+
+    /// The actual PWM frequency.
+    pub fn frequency(&self) -> Hertz {
+        let period_ticks = self.mode.period_ticks(self.top);
+        Hertz::from_raw(CLOCK::FREQ / (self.prescaler.divider() * period_ticks))
+    }
+
+    /// Waveform generation mode of the timer.
+    #[inline]
+    pub fn mode(&self) -> PwmMode {
+        self.mode
+    }
+
+    /// Prescaler the timer is running with.
+    #[inline]
+    pub fn prescaler(&self) -> crate::timer::Prescaler {
+        self.prescaler
+    }
+
+    /// TOP value of the counter, which is also the maximum duty cycle.
+    #[inline]
+    pub fn top(&self) -> u16 {
+        self.top
+    }
+
+    /// Stop the timer and return the peripheral.
+    ///
+    /// The clock of the peripheral is stopped in the Power Reduction Register, if available.
+    pub fn release(mut self) -> TC
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        self.timer.raw_set_clock(None);
+        self.timer.raw_set_powered(false);
+        self.timer
+    }
 }
 
 #[macro_export]
 macro_rules! impl_simple_pwm {
+    // 16-bit timers running with 8-bit resolution: The init block gets the WGMn1:0 and WGMn3:2
+    // bits for the mode instead of the mode itself.
     (
         $(#[$timer_pwm_attr:meta])*
         pub struct $TimerPwm:ident {
+            hal: $HAL:ty,
             timer: $TIMER:ty,
-            init: |$init_timer:ident, $prescaler:ident| $init_block:block,
+            modes: [$($supported_mode:ident),+ $(,)?],
+            $(default_mode: $default_mode:ident,)?
+            top: $icr:ident,
+            init: |$init_timer:ident, $prescaler:ident, $wgm_a:ident, $wgm_b:ident|
+                $init_block:block,
+            pins: {$($pins:tt)+},
+        }
+    ) => {
+        $crate::impl_simple_pwm! {
+            $(#[$timer_pwm_attr])*
+            pub struct $TimerPwm {
+                hal: $HAL,
+                timer: $TIMER,
+                modes: [$($supported_mode),+],
+                $(default_mode: $default_mode,)?
+                init: |$init_timer, $prescaler, mode| {
+                    // Fast PWM 8-bit (0101), phase-correct PWM 8-bit (0001) and phase- and
+                    // frequency-correct PWM with ICRn as TOP (1000).
+                    let ($wgm_a, $wgm_b) = match mode {
+                        $crate::simple_pwm::PwmMode::Fast => (0b01, 0b01),
+                        $crate::simple_pwm::PwmMode::PhaseCorrect => (0b01, 0b00),
+                        $crate::simple_pwm::PwmMode::PhaseFrequencyCorrect => (0b00, 0b10),
+                    };
+                    if mode == $crate::simple_pwm::PwmMode::PhaseFrequencyCorrect {
+                        $crate::timer::access_16bit(|| {
+                            // SAFETY: Any value is a valid TOP.
+                            $init_timer.$icr().write(|w| unsafe { w.bits(0xff) });
+                        });
+                    }
+                    $init_block
+                },
+                pins: {$($pins)+},
+            }
+        }
+    };
+    (
+        $(#[$timer_pwm_attr:meta])*
+        pub struct $TimerPwm:ident {
+            hal: $HAL:ty,
+            timer: $TIMER:ty,
+            modes: [$($supported_mode:ident),+ $(,)?],
+            $(default_mode: $default_mode:ident,)?
+            init: |$init_timer:ident, $prescaler:ident, $mode:ident| $init_block:block,
             pins: {$(
                 $PXi:ident: {
                     ocr: $ocr:ident,
-                    $into_pwm:ident: |$pin_timer:ident| if enable
+                    $into_pwm:ident: |$pin_timer:ident, $inverted:ident| if enable
                         $pin_enable_block:block else $pin_disable_block:block,
                 },
             )+},
@@ -134,15 +589,56 @@ macro_rules! impl_simple_pwm {
         $(#[$timer_pwm_attr])*
         pub struct $TimerPwm {
             timer: $TIMER,
+            prescaler: $crate::simple_pwm::Prescaler,
+            mode: $crate::simple_pwm::PwmMode,
         }
 
         impl $TimerPwm {
+            /// PWM modes supported by this timer.
+            pub const MODES: &'static [$crate::simple_pwm::PwmMode] =
+                &[$($crate::simple_pwm::PwmMode::$supported_mode),+];
+
+            /// PWM mode used by [`new()`][Self::new].
+            pub const DEFAULT_MODE: $crate::simple_pwm::PwmMode =
+                $crate::impl_simple_pwm!(@default_mode $($default_mode)?);
+
+            /// Start PWM in [`DEFAULT_MODE`][Self::DEFAULT_MODE] with the given prescaler.
+            ///
+            /// The clock of the peripheral is started in the Power Reduction Register, if
+            /// necessary.  Use [`with_mode()`][Self::with_mode] to select a different mode.
             pub fn new(timer: $TIMER, prescaler: $crate::simple_pwm::Prescaler) -> $TimerPwm {
-                let mut t = $TimerPwm { timer };
+                Self::init(timer, prescaler, Self::DEFAULT_MODE)
+            }
+
+            /// Start PWM in the given mode with the given prescaler.
+            ///
+            /// Fails with [`PwmError::UnsupportedMode`][$crate::simple_pwm::PwmError::UnsupportedMode]
+            /// if the mode is not in [`MODES`][Self::MODES].
+            pub fn with_mode(
+                timer: $TIMER,
+                prescaler: $crate::simple_pwm::Prescaler,
+                mode: $crate::simple_pwm::PwmMode,
+            ) -> Result<$TimerPwm, $crate::simple_pwm::PwmError> {
+                if !Self::MODES.contains(&mode) {
+                    return Err($crate::simple_pwm::PwmError::UnsupportedMode);
+                }
+                Ok(Self::init(timer, prescaler, mode))
+            }
+
+            fn init(
+                mut timer: $TIMER,
+                prescaler: $crate::simple_pwm::Prescaler,
+                mode: $crate::simple_pwm::PwmMode,
+            ) -> $TimerPwm {
+                <$TIMER as $crate::power::PowerReductionOps<$HAL>>::raw_set_powered(
+                    &mut timer, true,
+                );
+                let mut t = $TimerPwm { timer, prescaler, mode };
 
                 {
                     let $init_timer = &mut t.timer;
                     let $prescaler = prescaler;
+                    let $mode = mode;
                     $init_block
                 }
 
@@ -150,6 +646,30 @@ macro_rules! impl_simple_pwm {
             }
         }
 
+        impl $crate::simple_pwm::PwmTimer for $TimerPwm {
+            type Timer = $TIMER;
+
+            #[inline]
+            fn prescaler(&self) -> $crate::simple_pwm::Prescaler {
+                self.prescaler
+            }
+
+            #[inline]
+            fn top(&self) -> u16 {
+                u8::MAX as u16
+            }
+
+            #[inline]
+            fn mode(&self) -> $crate::simple_pwm::PwmMode {
+                self.mode
+            }
+
+            #[inline]
+            fn into_timer(self) -> $TIMER {
+                self.timer
+            }
+        }
+
         $(
             impl avr_hal_generic::simple_pwm::PwmPinOps<$TimerPwm> for $PXi {
                 type Duty = u8;
@@ -160,6 +680,16 @@ macro_rules! impl_simple_pwm {
                     // ensures we will never hit a race-condition here.
                     $crate::avr_device::interrupt::free(|_| {
                         let $pin_timer = unsafe { &*<$TIMER>::ptr() };
+                        let $inverted = false;
+                        $pin_enable_block
+                    });
+                }
+
+                fn enable_inverted(&mut self) {
+                    // SAFETY: See `enable()`.
+                    $crate::avr_device::interrupt::free(|_| {
+                        let $pin_timer = unsafe { &*<$TIMER>::ptr() };
+                        let $inverted = true;
                         $pin_enable_block
                     });
                 }
@@ -189,5 +719,154 @@ macro_rules! impl_simple_pwm {
                 }
             }
         )+
-    }
+    };
+    (@default_mode $mode:ident) => {
+        $crate::simple_pwm::PwmMode::$mode
+    };
+    (@default_mode) => {
+        $crate::simple_pwm::PwmMode::Fast
+    };
+}
+
+/// Implement [`Pwm16Ops`] for a 16-bit timer and [`PwmPinOps`] for its output pins.
+#[macro_export]
+macro_rules! impl_simple_pwm_16 {
+    (
+        hal: $HAL:ty,
+        timer: $TIMER:ty,
+        init: |$init_timer:ident, $wgm_a:ident, $wgm_b:ident| $init_block:block,
+        top: $icr:ident,
+        pins: {$(
+            $PXi:ident: {
+                ocr: $ocr:ident,
+                $into_pwm:ident: |$pin_timer:ident, $inverted:ident| if enable
+                    $pin_enable_block:block else $pin_disable_block:block,
+            },
+        )+},
+    ) => {
+        impl $crate::simple_pwm::Pwm16Ops<$HAL> for $TIMER {
+            #[inline]
+            fn raw_init_pwm16(&mut self, mode: $crate::simple_pwm::PwmMode) {
+                // WGMn1:0 and WGMn3:2 of fast PWM (1110), phase-correct PWM (1010) and phase- and
+                // frequency-correct PWM (1000), all with ICRn as TOP.
+                let ($wgm_a, $wgm_b) = match mode {
+                    $crate::simple_pwm::PwmMode::Fast => (0b10, 0b11),
+                    $crate::simple_pwm::PwmMode::PhaseCorrect => (0b10, 0b10),
+                    $crate::simple_pwm::PwmMode::PhaseFrequencyCorrect => (0b00, 0b10),
+                };
+                let $init_timer = &*self;
+                $init_block
+            }
+
+            #[inline]
+            fn raw_set_top(&mut self, top: u16) {
+                $crate::timer::access_16bit(|| {
+                    self.$icr().write(|w| unsafe { w.bits(top) });
+                })
+            }
+        }
+
+        $(
+            impl<CLOCK> $crate::simple_pwm::PwmPinOps<$crate::simple_pwm::Pwm16<$HAL, $TIMER, CLOCK>>
+                for $PXi
+            {
+                type Duty = u16;
+
+                fn enable(&mut self) {
+                    // SAFETY: This block will usually result in a read-modify-write sequence which
+                    // is not concurrency safe.  Thus, it is wrapped in a critical section which
+                    // ensures we will never hit a race-condition here.
+                    $crate::avr_device::interrupt::free(|_| {
+                        let $pin_timer = unsafe { &*<$TIMER>::ptr() };
+                        let $inverted = false;
+                        $pin_enable_block
+                    });
+                }
+
+                fn enable_inverted(&mut self) {
+                    // SAFETY: See `enable()`.
+                    $crate::avr_device::interrupt::free(|_| {
+                        let $pin_timer = unsafe { &*<$TIMER>::ptr() };
+                        let $inverted = true;
+                        $pin_enable_block
+                    });
+                }
+
+                fn disable(&mut self) {
+                    // SAFETY: This block will usually result in a read-modify-write sequence which
+                    // is not concurrency safe.  Thus, it is wrapped in a critical section which
+                    // ensures we will never hit a race-condition here.
+                    $crate::avr_device::interrupt::free(|_| {
+                        let $pin_timer = unsafe { &*<$TIMER>::ptr() };
+                        $pin_disable_block
+                    });
+                }
+
+                fn get_duty(&self) -> Self::Duty {
+                    $crate::timer::access_16bit(|| {
+                        unsafe { (&*<$TIMER>::ptr()) }.$ocr().read().bits()
+                    })
+                }
+
+                fn get_max_duty(&self) -> Self::Duty {
+                    $crate::timer::access_16bit(|| {
+                        unsafe { (&*<$TIMER>::ptr()) }.$icr().read().bits()
+                    })
+                }
+
+                fn set_duty(&mut self, duty: Self::Duty) {
+                    $crate::timer::access_16bit(|| {
+                        unsafe { (&*<$TIMER>::ptr()).$ocr().write(|w| w.bits(duty)) };
+                    });
+                }
+            }
+        )+
+    };
+}
+
+/// Implement [`ComplementaryPwmOps`] for PWM pins which have a complementary output.
+#[macro_export]
+macro_rules! impl_complementary_pwm {
+    (
+        timer: $TimerPwm:ty,
+        peripheral: $TIMER:ty,
+        pins: {$(
+            $PXi:ident: {
+                complement: $NPXi:ident,
+                enable: |$enable_timer:ident| $enable_block:block,
+                dead_time: |$dt_timer:ident, $prescaler:ident, $output:ident, $complement:ident|
+                    $dead_time_block:block,
+            },
+        )+},
+    ) => {
+        $(
+            impl $crate::simple_pwm::ComplementaryPwmOps<$TimerPwm> for $PXi {
+                type Complement = $NPXi;
+
+                fn enable_complementary(&mut self) {
+                    // SAFETY: The read-modify-write sequence is wrapped in a critical section.
+                    $crate::avr_device::interrupt::free(|_| {
+                        let $enable_timer = unsafe { &*<$TIMER>::ptr() };
+                        $enable_block
+                    });
+                }
+
+                fn set_dead_time(
+                    &mut self,
+                    prescaler: $crate::simple_pwm::DeadTimePrescaler,
+                    output: u8,
+                    complement: u8,
+                ) {
+                    // SAFETY: The read-modify-write sequence is wrapped in a critical section.
+                    $crate::avr_device::interrupt::free(|_| {
+                        let $dt_timer = unsafe { &*<$TIMER>::ptr() };
+                        let $prescaler = prescaler;
+                        let $output = output;
+                        let $complement = complement;
+                        $dead_time_block
+                    });
+                }
+            }
+        )+
+    };
 }
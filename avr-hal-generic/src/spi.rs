@@ -222,7 +222,10 @@ where
         miso: port::Pin<port::mode::Input<port::mode::PullUp>, MISOPIN>,
         cs: port::Pin<port::mode::Output, CSPIN>,
         settings: Settings,
-    ) -> (Self, ChipSelectPin<CSPIN>) {
+    ) -> (Self, ChipSelectPin<CSPIN>)
+    where
+        SPI: crate::power::PowerReductionOps<H>,
+    {
         let mut spi = Self {
             p,
             sclk,
@@ -232,6 +235,7 @@ where
             _cs: PhantomData,
             _h: PhantomData,
         };
+        spi.p.raw_set_powered(true);
         spi.p.raw_setup(&settings);
         (spi, ChipSelectPin(cs))
     }
@@ -249,7 +253,10 @@ where
         miso: port::Pin<port::mode::Input<port::mode::Floating>, MISOPIN>,
         cs: port::Pin<port::mode::Output, CSPIN>,
         settings: Settings,
-    ) -> (Self, ChipSelectPin<CSPIN>) {
+    ) -> (Self, ChipSelectPin<CSPIN>)
+    where
+        SPI: crate::power::PowerReductionOps<H>,
+    {
         let mut spi = Self {
             p,
             sclk,
@@ -259,6 +266,7 @@ where
             _cs: PhantomData,
             _h: PhantomData,
         };
+        spi.p.raw_set_powered(true);
         spi.p.raw_setup(&settings);
         (spi, ChipSelectPin(cs))
     }
@@ -274,6 +282,8 @@ where
     /// Disable the SPI device and release ownership of the peripheral
     /// and pins.  Instance can no-longer be used after this is
     /// invoked.
+    ///
+    /// The clock of the peripheral is stopped in the Power Reduction Register, if available.
     pub fn release(
         mut self,
         cs: ChipSelectPin<CSPIN>,
@@ -283,8 +293,12 @@ where
         port::Pin<port::mode::Output, MOSIPIN>,
         port::Pin<port::mode::Input, MISOPIN>,
         port::Pin<port::mode::Output, CSPIN>,
-    ) {
+    )
+    where
+        SPI: crate::power::PowerReductionOps<H>,
+    {
         self.p.raw_release();
+        self.p.raw_set_powered(false);
         (self.p, self.sclk, self.mosi, self.miso, cs.0)
     }
 
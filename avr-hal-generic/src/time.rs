@@ -0,0 +1,309 @@
+//! Monotonic system time
+//!
+//! [`SysTime`] counts timer interrupts to keep track of the time since it was started, similar to
+//! `millis()` and `micros()` of the Arduino core.  Between two interrupts, the live counter value
+//! of the timer is taken into account, so [`micros()`][SysTime::micros] has a resolution of a
+//! single timer tick.
+//!
+//! There are two ways to drive the clock:
+//!
+//! - [`SysTime::new()`] takes a dedicated timer and runs it in CTC mode at a configurable tick
+//!   rate.  The periodic interrupt is `TIMERn_COMPA`.
+//! - [`SysTime::with_pwm_timer()`] takes over a timer which was set up for PWM (for example
+//!   `Timer0Pwm`) and uses its overflow interrupt `TIMERn_OVF`.  PWM outputs on this timer keep
+//!   working.  This is the same approach the Arduino core uses with `TC0`.
+//!
+//! In both cases, the application must define the interrupt handler and call [`tick()`] from it:
+//!
+//! ```
+//! #[avr_device::interrupt(atmega328p)]
+//! fn TIMER0_OVF() {
+//!     avr_hal_generic::time::tick();
+//! }
+//! ```
+//!
+//! The clock state is kept in a global variable, so only one `SysTime` can be in use at a time.
+//! Starting a second one fails with [`TimerError::AlreadyRunning`] until the first one is
+//! released.
+//!
+//! # Wrap-around
+//! [`millis()`][SysTime::millis] wraps around after about 49.7 days and
+//! [`micros()`][SysTime::micros] after about 71.6 minutes.  Use [`SysTime::now()`] and
+//! [`SysTime::now_micros()`] to get instants which compare correctly across a wrap-around, as
+//! long as they are less than half of this range apart.
+use core::cell::Cell;
+use core::marker::PhantomData;
+
+use avr_device::interrupt::Mutex;
+
+use crate::simple_pwm::{PwmMode, PwmTimer};
+use crate::timer::{Event, Hertz, OutputCompare, Prescaler, TimerError, TimerMode, TimerOps};
+
+/// Point in time with millisecond resolution.
+pub type MillisInstant = fugit::TimerInstantU32<1_000>;
+
+/// Point in time with microsecond resolution.
+pub type MicrosInstant = fugit::TimerInstantU32<1_000_000>;
+
+/// Length of a single tick, split up so the interrupt handler only needs additions.
+#[derive(Clone, Copy)]
+struct Config {
+    /// Whole milliseconds per tick.
+    ms: u32,
+    /// Remaining whole microseconds per tick (`< 1000`).
+    us: u16,
+    /// Remaining clock cycles per tick (`< cycles_per_us`).
+    cycles: u16,
+    cycles_per_us: u16,
+}
+
+/// Time of the last tick.
+#[derive(Clone, Copy)]
+struct State {
+    ms: u32,
+    us: u16,
+    cycles: u16,
+}
+
+impl State {
+    const ZERO: Self = Self {
+        ms: 0,
+        us: 0,
+        cycles: 0,
+    };
+
+    fn advance(mut self, config: &Config) -> Self {
+        self.cycles += config.cycles;
+        self.us += config.us;
+        if self.cycles >= config.cycles_per_us {
+            self.cycles -= config.cycles_per_us;
+            self.us += 1;
+        }
+        self.ms = self.ms.wrapping_add(config.ms);
+        if self.us >= 1000 {
+            self.us -= 1000;
+            self.ms = self.ms.wrapping_add(1);
+        }
+        self
+    }
+}
+
+/// Length of a tick of the running `SysTime`, if any.
+static CONFIG: Mutex<Cell<Option<Config>>> = Mutex::new(Cell::new(None));
+static STATE: Mutex<Cell<State>> = Mutex::new(Cell::new(State::ZERO));
+
+/// Advance the system time by one tick.
+///
+/// This must be called from the interrupt handler of the timer used by [`SysTime`].
+#[inline]
+pub fn tick() {
+    avr_device::interrupt::free(|cs| {
+        if let Some(config) = CONFIG.borrow(cs).get() {
+            let state = STATE.borrow(cs);
+            state.set(state.get().advance(&config));
+        }
+    })
+}
+
+/// Monotonic system time, driven by a timer interrupt
+///
+/// See the [module documentation][self] for an overview.  Global interrupts must be enabled for
+/// the clock to advance.
+///
+/// The core clock (`CLOCK`) must be a multiple of 1 MHz.
+pub struct SysTime<H, TC, CLOCK> {
+    p: TC,
+    event: Event,
+    prescaler: Prescaler,
+    /// Timer ticks between two `tick()` calls.
+    period: u32,
+    /// Whether the counter value tells the time since the last tick.
+    interpolate: bool,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC, CLOCK> SysTime<H, TC, CLOCK>
+where
+    TC: TimerOps<H>,
+    CLOCK: crate::clock::Clock,
+{
+    const CYCLES_PER_US: u32 = {
+        assert!(
+            CLOCK::FREQ % 1_000_000 == 0,
+            "SysTime needs a core clock which is a multiple of 1 MHz"
+        );
+        CLOCK::FREQ / 1_000_000
+    };
+
+    /// Start the system time on a dedicated timer, ticking at the given rate.
+    ///
+    /// The timer runs in CTC mode and raises `TIMERn_COMPA` on every tick.  A higher tick rate
+    /// does not improve the resolution of [`micros()`][SysTime::micros], which is always one
+    /// timer tick, but it allows a smaller prescaler.  `1.kHz()` is a good default.
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
+    /// Fails with [`TimerError::AlreadyRunning`] if another `SysTime` is running.
+    pub fn new(mut p: TC, rate: Hertz) -> Result<Self, TimerError>
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        let hz = rate.to_Hz();
+        if hz == 0 {
+            return Err(TimerError::PeriodTooLong);
+        }
+        let (prescaler, top) = Prescaler::for_cycles(CLOCK::FREQ / hz, TC::PRESCALERS, TC::MAX)?;
+
+        p.raw_set_powered(true);
+        p.raw_set_clock(None);
+        for event in [Event::Overflow, Event::CompareA, Event::CompareB] {
+            p.raw_set_interrupt(event, false);
+        }
+        p.raw_set_mode(TimerMode::Ctc);
+        p.raw_set_compare(OutputCompare::A, top);
+        p.raw_write_counter(0);
+
+        let mut systime = Self::start(p, Event::CompareA, prescaler, top as u32 + 1, true)?;
+        systime.p.raw_set_clock(Some(prescaler));
+        Ok(systime)
+    }
+
+    /// Start the system time on a timer which is already used for PWM.
+    ///
+    /// The PWM timer keeps running unchanged and pins which were converted with `into_pwm()`
+    /// continue to work.  The system time ticks on each timer overflow and the interrupt handler
+    /// must be defined for `TIMERn_OVF`.
+    ///
+    /// With `Timer0Pwm` and [`Prescale64`][crate::simple_pwm::Prescaler::Prescale64], this is
+    /// equivalent to the setup of the Arduino core: One tick every 1.024 ms at 16 MHz.
+    ///
+    /// In the phase-correct PWM modes, the counter counts up and down again, so its value does not
+    /// tell the time since the last overflow.  [`micros()`][SysTime::micros] then only has the
+    /// resolution of one PWM period.
+    ///
+    /// Fails with [`TimerError::AlreadyRunning`] if another `SysTime` is running.
+    ///
+    /// # Example
+    /// ```
+    /// let timer0 = Timer0Pwm::new(dp.TC0, Prescaler::Prescale64);
+    /// let mut d6 = pins.d6.into_output().into_pwm(&timer0);
+    ///
+    /// let systime = SysTime::with_pwm_timer(timer0).unwrap();
+    /// ```
+    pub fn with_pwm_timer<P>(pwm: P) -> Result<Self, TimerError>
+    where
+        P: PwmTimer<Timer = TC>,
+    {
+        let prescaler = pwm.prescaler().into();
+        let period = pwm.period_ticks();
+        let interpolate = pwm.mode() == PwmMode::Fast;
+        Self::start(
+            pwm.into_timer(),
+            Event::Overflow,
+            prescaler,
+            period,
+            interpolate,
+        )
+    }
+
+    fn start(
+        mut p: TC,
+        event: Event,
+        prescaler: Prescaler,
+        period: u32,
+        interpolate: bool,
+    ) -> Result<Self, TimerError> {
+        let cycles_per_us = Self::CYCLES_PER_US;
+        let period_cycles = period * prescaler.divider();
+        let period_us = period_cycles / cycles_per_us;
+        let config = Config {
+            ms: period_us / 1000,
+            us: (period_us % 1000) as u16,
+            cycles: (period_cycles % cycles_per_us) as u16,
+            cycles_per_us: cycles_per_us as u16,
+        };
+
+        avr_device::interrupt::free(|cs| {
+            if CONFIG.borrow(cs).get().is_some() {
+                return Err(TimerError::AlreadyRunning);
+            }
+            CONFIG.borrow(cs).set(Some(config));
+            STATE.borrow(cs).set(State::ZERO);
+            p.raw_clear_pending(event);
+            p.raw_set_interrupt(event, true);
+            Ok(())
+        })?;
+
+        Ok(Self {
+            p,
+            event,
+            prescaler,
+            period,
+            interpolate,
+            _clock: PhantomData,
+            _h: PhantomData,
+        })
+    }
+
+    /// Take a consistent snapshot of the last tick and the microseconds elapsed since then.
+    fn sample(&self) -> (State, u32) {
+        avr_device::interrupt::free(|cs| {
+            let state = STATE.borrow(cs).get();
+            let mut cycles = state.cycles as u32;
+            let mut counter = self.p.raw_read_counter();
+            // When the tick interrupt is pending, the counter has already wrapped around but
+            // `tick()` did not run yet.  As the flag might have been set right after reading the
+            // counter, the counter must be read again.
+            if self.p.raw_is_pending(self.event) {
+                counter = self.p.raw_read_counter();
+                cycles += self.period * self.prescaler.divider();
+            }
+            if self.interpolate {
+                cycles += counter as u32 * self.prescaler.divider();
+            }
+            (state, cycles / Self::CYCLES_PER_US)
+        })
+    }
+
+    /// Milliseconds since the system time was started.
+    ///
+    /// Wraps around after about 49.7 days.
+    pub fn millis(&self) -> u32 {
+        let (state, us) = self.sample();
+        state.ms.wrapping_add((state.us as u32 + us) / 1000)
+    }
+
+    /// Microseconds since the system time was started.
+    ///
+    /// Wraps around after about 71.6 minutes.
+    pub fn micros(&self) -> u32 {
+        let (state, us) = self.sample();
+        state
+            .ms
+            .wrapping_mul(1000)
+            .wrapping_add(state.us as u32 + us)
+    }
+
+    /// Current time as an instant with millisecond resolution.
+    #[inline]
+    pub fn now(&self) -> MillisInstant {
+        MillisInstant::from_ticks(self.millis())
+    }
+
+    /// Current time as an instant with microsecond resolution.
+    #[inline]
+    pub fn now_micros(&self) -> MicrosInstant {
+        MicrosInstant::from_ticks(self.micros())
+    }
+
+    /// Disable the tick interrupt and return the timer peripheral.
+    ///
+    /// The timer itself is not stopped.
+    pub fn release(mut self) -> TC {
+        avr_device::interrupt::free(|cs| {
+            self.p.raw_set_interrupt(self.event, false);
+            CONFIG.borrow(cs).set(None);
+        });
+        self.p
+    }
+}
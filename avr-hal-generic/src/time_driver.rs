@@ -0,0 +1,164 @@
+//! [`embassy-time`](https://docs.rs/embassy-time) driver
+//!
+//! [`TimeDriver`] implements [`embassy_time_driver::Driver`] on top of a timer/counter.  The
+//! counter runs freely at the tick rate of `embassy-time` and overflows are counted in software
+//! to extend it to 64 bits.  Compare channel A is used as the alarm.
+//!
+//! The tick rate is selected with one of the `tick-hz-*` features of `embassy-time` and must be
+//! the core clock divided by one of the prescalers of the timer.  For a 16 MHz clock this is
+//! `tick-hz-2_000_000` (prescaler 8), for an 8 MHz clock `tick-hz-1_000_000` (prescaler 8).
+//!
+//! The MCU crates instantiate the driver for a specific timer and define its interrupt handlers.
+use core::cell::RefCell;
+use core::task::Waker;
+
+use avr_device::interrupt::Mutex;
+use embassy_time_queue_utils::Queue;
+
+use crate::timer::{Event, ExtendedCounter, OutputCompare, TimerOps};
+
+pub use embassy_time_driver;
+pub use embassy_time_driver::TICK_HZ;
+
+struct State<H, TC> {
+    counter: Option<ExtendedCounter<H, TC>>,
+    /// Timestamp of the next alarm, `u64::MAX` if there is none.
+    alarm: u64,
+    queue: Queue,
+}
+
+/// `embassy-time` driver on top of a timer/counter
+///
+/// This is meant to be placed in a `static` and registered with
+/// [`time_driver_impl!`][embassy_time_driver::time_driver_impl].  The interrupt handlers of the
+/// timer must call [`on_overflow()`][TimeDriver::on_overflow] and
+/// [`on_compare()`][TimeDriver::on_compare].
+pub struct TimeDriver<H, TC> {
+    state: Mutex<RefCell<State<H, TC>>>,
+}
+
+impl<H, TC> TimeDriver<H, TC> {
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(RefCell::new(State {
+                counter: None,
+                alarm: u64::MAX,
+                queue: Queue::new(),
+            })),
+        }
+    }
+}
+
+impl<H, TC> Default for TimeDriver<H, TC> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H, TC: TimerOps<H>> TimeDriver<H, TC> {
+    /// Start the driver on the given timer.
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
+    ///
+    /// # Panics
+    /// Panics if the timer cannot run at [`TICK_HZ`] with the given core clock.
+    pub fn init<CLOCK: crate::clock::Clock>(&self, timer: TC)
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        avr_device::interrupt::free(|cs| {
+            let mut state = self.state.borrow(cs).borrow_mut();
+            state.counter = Some(ExtendedCounter::start::<CLOCK>(timer, TICK_HZ));
+            Self::check_alarm(&mut state);
+        });
+    }
+
+    /// Handler for the overflow interrupt of the timer.
+    pub fn on_overflow(&self) {
+        avr_device::interrupt::free(|cs| {
+            let mut state = self.state.borrow(cs).borrow_mut();
+            if let Some(counter) = state.counter.as_mut() {
+                counter.on_overflow();
+            }
+            // The alarm might be in the period which just started.
+            Self::check_alarm(&mut state);
+        })
+    }
+
+    /// Handler for the compare A interrupt of the timer.
+    pub fn on_compare(&self) {
+        avr_device::interrupt::free(|cs| {
+            let mut state = self.state.borrow(cs).borrow_mut();
+            Self::check_alarm(&mut state);
+        })
+    }
+
+    fn timestamp(state: &State<H, TC>) -> u64 {
+        state.counter.as_ref().map_or(0, ExtendedCounter::now)
+    }
+
+    /// Program the alarm for `at`.  Returns `false` if `at` has already passed.
+    fn set_alarm(state: &mut State<H, TC>, at: u64) -> bool {
+        state.alarm = at;
+        let now = Self::timestamp(state);
+        let Some(timer) = state.counter.as_mut().map(ExtendedCounter::timer) else {
+            return true;
+        };
+
+        if at <= now {
+            timer.raw_set_interrupt(Event::CompareA, false);
+            return false;
+        }
+
+        if at - now <= TC::MAX as u64 {
+            // The alarm is less than one counter period away, so the compare match will fire at
+            // the right time.  Otherwise, `on_overflow()` will come back here later.
+            timer.raw_set_compare(OutputCompare::A, (at % (TC::MAX as u64 + 1)) as u16);
+            timer.raw_clear_pending(Event::CompareA);
+            timer.raw_set_interrupt(Event::CompareA, true);
+
+            // The counter might have passed the compare value while it was being written.
+            if at <= Self::timestamp(state) {
+                if let Some(counter) = state.counter.as_mut() {
+                    counter.timer().raw_set_interrupt(Event::CompareA, false);
+                }
+                return false;
+            }
+        } else {
+            timer.raw_set_interrupt(Event::CompareA, false);
+        }
+        true
+    }
+
+    /// Wake all expired timers and program the alarm for the next one.
+    fn check_alarm(state: &mut State<H, TC>) {
+        let mut at = state.alarm;
+        while !Self::set_alarm(state, at) {
+            let now = Self::timestamp(state);
+            at = state.queue.next_expiration(now);
+        }
+    }
+}
+
+impl<H, TC> embassy_time_driver::Driver for TimeDriver<H, TC>
+where
+    H: 'static,
+    TC: TimerOps<H> + Send + 'static,
+{
+    fn now(&self) -> u64 {
+        avr_device::interrupt::free(|cs| Self::timestamp(&self.state.borrow(cs).borrow()))
+    }
+
+    fn schedule_wake(&self, at: u64, waker: &Waker) {
+        avr_device::interrupt::free(|cs| {
+            let mut state = self.state.borrow(cs).borrow_mut();
+            if state.queue.schedule_wake(at, waker) {
+                let now = Self::timestamp(&state);
+                state.alarm = state.queue.next_expiration(now);
+                Self::check_alarm(&mut state);
+            }
+        })
+    }
+}
+
+pub use crate::power::enable_interrupts_and_sleep;
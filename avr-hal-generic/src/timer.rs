@@ -0,0 +1,661 @@
+//! General-purpose Timer/Counter abstraction
+//!
+//! The [`Timer`] type wraps one of the timer/counter peripherals (`TC0`, `TC1`, ...) and makes it
+//! usable as a periodic interrupt source or as a free-running counter.  The prescaler and the
+//! compare value (TOP) for a requested rate are calculated from the core clock speed
+//! ([`Clock`][crate::clock::Clock]).
+//!
+//! # Example
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//!
+//! let mut timer = atmega_hal::timer::Timer::<_, CoreClock>::new(dp.TC1);
+//! timer.start(4.Hz()).unwrap();
+//! timer.listen(Event::CompareA).unwrap();
+//!
+//! // ... and handle the `TIMER1_COMPA` interrupt.
+//! ```
+use core::marker::PhantomData;
+
+pub use fugit::HertzU32 as Hertz;
+pub use fugit::MicrosDurationU32 as Duration;
+
+/// Clock prescaler for a timer
+///
+/// Not all timers support all prescaler values; the supported ones are listed in
+/// [`TimerOps::PRESCALERS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Prescaler {
+    /// No prescaling, the IO clock drives the timer directly.
+    Direct,
+    /// Divide the IO clock by 8.
+    Prescale8,
+    /// Divide the IO clock by 32.
+    Prescale32,
+    /// Divide the IO clock by 64.
+    Prescale64,
+    /// Divide the IO clock by 128.
+    Prescale128,
+    /// Divide the IO clock by 256.
+    Prescale256,
+    /// Divide the IO clock by 1024.
+    Prescale1024,
+}
+
+impl Prescaler {
+    /// Division factor of this prescaler setting.
+    pub const fn divider(self) -> u32 {
+        match self {
+            Prescaler::Direct => 1,
+            Prescaler::Prescale8 => 8,
+            Prescaler::Prescale32 => 32,
+            Prescaler::Prescale64 => 64,
+            Prescaler::Prescale128 => 128,
+            Prescaler::Prescale256 => 256,
+            Prescaler::Prescale1024 => 1024,
+        }
+    }
+
+    /// Find the smallest prescaler from `available` which allows counting `cycles` core clock
+    /// cycles with a counter of at most `max + 1` steps.
+    ///
+    /// Returns the prescaler and the TOP value (number of steps minus one).
+    pub fn for_cycles(
+        cycles: u32,
+        available: &[Prescaler],
+        max: u16,
+    ) -> Result<(Prescaler, u16), TimerError> {
+        for &prescaler in available {
+            // In 64 bits, as rounding would overflow for periods close to `u32::MAX` cycles.
+            let divider = prescaler.divider() as u64;
+            let steps = (cycles as u64 + divider / 2) / divider;
+            if steps == 0 {
+                return Err(TimerError::PeriodTooShort);
+            }
+            if steps <= max as u64 + 1 {
+                return Ok((prescaler, (steps - 1) as u16));
+            }
+        }
+        Err(TimerError::PeriodTooLong)
+    }
+}
+
+impl From<crate::simple_pwm::Prescaler> for Prescaler {
+    fn from(prescaler: crate::simple_pwm::Prescaler) -> Self {
+        match prescaler {
+            crate::simple_pwm::Prescaler::Direct => Prescaler::Direct,
+            crate::simple_pwm::Prescaler::Prescale8 => Prescaler::Prescale8,
+            crate::simple_pwm::Prescaler::Prescale64 => Prescaler::Prescale64,
+            crate::simple_pwm::Prescaler::Prescale256 => Prescaler::Prescale256,
+            crate::simple_pwm::Prescaler::Prescale1024 => Prescaler::Prescale1024,
+        }
+    }
+}
+
+/// Waveform generation mode used by the [`Timer`] driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerMode {
+    /// The counter runs from 0 to its maximum value and then overflows.
+    Normal,
+    /// Clear Timer on Compare match: The counter runs from 0 to the value of compare register A.
+    Ctc,
+}
+
+/// Output compare channel of a timer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputCompare {
+    A,
+    B,
+}
+
+/// Timer events which can raise an interrupt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// The counter overflowed (`TIMERn_OVF`).
+    Overflow,
+    /// The counter matched compare register A (`TIMERn_COMPA`).
+    ///
+    /// In CTC mode, this is the periodic event.
+    CompareA,
+    /// The counter matched compare register B (`TIMERn_COMPB`).
+    CompareB,
+}
+
+/// Errors while configuring a timer.
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerError {
+    /// The requested period is longer than what the timer can count with its largest prescaler.
+    PeriodTooLong,
+    /// The requested period is shorter than a single timer tick.
+    PeriodTooShort,
+    /// The timer does not support the requested prescaler.
+    UnsupportedPrescaler,
+    /// The timer has no output compare register B.
+    NoCompareB,
+    /// A driver which keeps its state in a global variable is already running.
+    AlreadyRunning,
+}
+
+/// Access the 16-bit registers of a timer atomically.
+///
+/// The CPU reads and writes 16-bit timer registers (`TCNTn`, `OCRnx`, `ICRn`) one byte at a time.
+/// The high byte goes through a TEMP register which all 16-bit timer registers share: Reading the
+/// low byte latches the high byte into TEMP and writing the low byte copies TEMP into the high
+/// byte.  An interrupt handler which accesses any 16-bit register between the two byte accesses
+/// clobbers TEMP, so all drivers in this crate run every such access through this function, which
+/// disables interrupts for its duration.
+#[inline(always)]
+pub fn access_16bit<R>(f: impl FnOnce() -> R) -> R {
+    avr_device::interrupt::free(|_| f())
+}
+
+/// Internal trait for low-level timer/counter peripherals.
+///
+/// **Prefer using the [`Timer`] API instead of this trait.**
+pub trait TimerOps<H> {
+    /// Maximum value of the counter register (`0xff` for 8-bit and `0xffff` for 16-bit timers).
+    const MAX: u16;
+
+    /// Prescaler settings supported by this timer, in ascending order.
+    const PRESCALERS: &'static [Prescaler];
+
+    /// Whether the timer has output compare register B.  If not, compare B is ignored by
+    /// [`raw_set_compare()`][TimerOps::raw_set_compare] and the other low-level methods.
+    const HAS_COMPARE_B: bool;
+
+    /// Select the clock source of the timer.  `None` stops the timer.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_clock(&mut self, prescaler: Option<Prescaler>);
+
+    /// Select the waveform generation mode.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_mode(&mut self, mode: TimerMode);
+
+    /// Read the current value of the counter register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_counter(&self) -> u16;
+
+    /// Overwrite the counter register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_counter(&mut self, value: u16);
+
+    /// Write an output compare register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_compare(&mut self, channel: OutputCompare, value: u16);
+
+    /// Enable or disable the interrupt for an event.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_interrupt(&mut self, event: Event, enable: bool);
+
+    /// Check whether the flag for an event is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_pending(&self, event: Event) -> bool;
+
+    /// Clear the flag for an event.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_pending(&mut self, event: Event);
+}
+
+/// General-purpose timer/counter driver
+///
+/// The timer is stopped and all its interrupts are disabled on construction.  Use
+/// [`start()`][Timer::start] or [`start_period()`][Timer::start_period] for a periodic event and
+/// [`start_free_running()`][Timer::start_free_running] for an overflowing counter.
+///
+/// Interrupts are only raised for events enabled with [`listen()`][Timer::listen].  The handler
+/// itself must be defined by the application, for example:
+///
+/// ```
+/// #[avr_device::interrupt(atmega328p)]
+/// fn TIMER1_COMPA() {
+///     // ...
+/// }
+/// ```
+pub struct Timer<H, TC, CLOCK> {
+    p: TC,
+    prescaler: Option<Prescaler>,
+    mode: TimerMode,
+    top: u16,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC, CLOCK> Timer<H, TC, CLOCK>
+where
+    TC: TimerOps<H>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Take ownership of a timer peripheral.
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
+    pub fn new(p: TC) -> Self
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        let mut timer = Self {
+            p,
+            prescaler: None,
+            mode: TimerMode::Normal,
+            top: TC::MAX,
+            _clock: PhantomData,
+            _h: PhantomData,
+        };
+        timer.p.raw_set_powered(true);
+        timer.stop();
+        for event in [Event::Overflow, Event::CompareA, Event::CompareB] {
+            timer.unlisten(event);
+        }
+        timer
+    }
+
+    /// Start generating [`Event::CompareA`] at the given rate.
+    ///
+    /// The timer is put into CTC mode and the prescaler and TOP value are chosen for the best
+    /// possible resolution.
+    pub fn start(&mut self, rate: Hertz) -> Result<(), TimerError> {
+        let hz = rate.to_Hz();
+        if hz == 0 {
+            return Err(TimerError::PeriodTooLong);
+        }
+        self.start_cycles(CLOCK::FREQ / hz)
+    }
+
+    /// Start generating [`Event::CompareA`] with the given period.
+    ///
+    /// The timer is put into CTC mode and the prescaler and TOP value are chosen for the best
+    /// possible resolution.
+    pub fn start_period(&mut self, period: Duration) -> Result<(), TimerError> {
+        let cycles = CLOCK::FREQ as u64 * period.to_micros() as u64 / 1_000_000;
+        self.start_cycles(u32::try_from(cycles).map_err(|_| TimerError::PeriodTooLong)?)
+    }
+
+    fn start_cycles(&mut self, cycles: u32) -> Result<(), TimerError> {
+        let (prescaler, top) = Prescaler::for_cycles(cycles, TC::PRESCALERS, TC::MAX)?;
+        self.start_ctc(prescaler, top)
+    }
+
+    /// Start generating [`Event::CompareA`] with a manually chosen prescaler and TOP value.
+    ///
+    /// The event rate is `CLOCK / (prescaler * (top + 1))`.
+    pub fn start_ctc(&mut self, prescaler: Prescaler, top: u16) -> Result<(), TimerError> {
+        if !TC::PRESCALERS.contains(&prescaler) {
+            return Err(TimerError::UnsupportedPrescaler);
+        }
+        if top > TC::MAX {
+            return Err(TimerError::PeriodTooLong);
+        }
+        self.p.raw_set_clock(None);
+        self.p.raw_set_mode(TimerMode::Ctc);
+        self.p.raw_set_compare(OutputCompare::A, top);
+        self.p.raw_write_counter(0);
+        self.p.raw_clear_pending(Event::CompareA);
+        self.mode = TimerMode::Ctc;
+        self.top = top;
+        self.prescaler = Some(prescaler);
+        self.p.raw_set_clock(Some(prescaler));
+        Ok(())
+    }
+
+    /// Start the counter in normal mode, overflowing after its maximum value.
+    ///
+    /// [`Event::Overflow`] is raised on each overflow.
+    pub fn start_free_running(&mut self, prescaler: Prescaler) -> Result<(), TimerError> {
+        if !TC::PRESCALERS.contains(&prescaler) {
+            return Err(TimerError::UnsupportedPrescaler);
+        }
+        self.p.raw_set_clock(None);
+        self.p.raw_set_mode(TimerMode::Normal);
+        self.p.raw_write_counter(0);
+        self.p.raw_clear_pending(Event::Overflow);
+        self.mode = TimerMode::Normal;
+        self.top = TC::MAX;
+        self.prescaler = Some(prescaler);
+        self.p.raw_set_clock(Some(prescaler));
+        Ok(())
+    }
+
+    /// Stop the timer.  The counter value is retained.
+    pub fn stop(&mut self) {
+        self.p.raw_set_clock(None);
+        self.prescaler = None;
+    }
+
+    /// Whether the timer is currently running.
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.prescaler.is_some()
+    }
+
+    /// Prescaler the timer is running with, if it is running.
+    #[inline]
+    pub fn prescaler(&self) -> Option<Prescaler> {
+        self.prescaler
+    }
+
+    /// TOP value of the counter: The compare value in CTC mode or the counter maximum in normal
+    /// mode.
+    #[inline]
+    pub fn top(&self) -> u16 {
+        self.top
+    }
+
+    /// Frequency at which the counter is incremented, if the timer is running.
+    pub fn tick_rate(&self) -> Option<Hertz> {
+        self.prescaler
+            .map(|p| Hertz::from_raw(CLOCK::FREQ / p.divider()))
+    }
+
+    /// Read the current counter value.
+    #[inline]
+    pub fn counter(&self) -> u16 {
+        self.p.raw_read_counter()
+    }
+
+    /// Reset the counter to zero.
+    #[inline]
+    pub fn reset_counter(&mut self) {
+        self.p.raw_write_counter(0);
+    }
+
+    /// Set the value of output compare register B.
+    ///
+    /// This raises [`Event::CompareB`] each time the counter passes `value`.  Fails with
+    /// [`TimerError::NoCompareB`] on timers without a second compare channel.
+    #[inline]
+    pub fn set_compare_b(&mut self, value: u16) -> Result<(), TimerError> {
+        if !TC::HAS_COMPARE_B {
+            return Err(TimerError::NoCompareB);
+        }
+        self.p.raw_set_compare(OutputCompare::B, value);
+        Ok(())
+    }
+
+    /// Enable the interrupt for an event.
+    ///
+    /// Fails with [`TimerError::NoCompareB`] for [`Event::CompareB`] on timers without a second
+    /// compare channel.
+    #[inline]
+    pub fn listen(&mut self, event: Event) -> Result<(), TimerError> {
+        if event == Event::CompareB && !TC::HAS_COMPARE_B {
+            return Err(TimerError::NoCompareB);
+        }
+        self.p.raw_set_interrupt(event, true);
+        Ok(())
+    }
+
+    /// Disable the interrupt for an event.
+    #[inline]
+    pub fn unlisten(&mut self, event: Event) {
+        self.p.raw_set_interrupt(event, false);
+    }
+
+    /// Check whether an event occurred since its flag was last cleared.
+    ///
+    /// Flags are cleared automatically by hardware when the corresponding interrupt handler runs.
+    #[inline]
+    pub fn is_pending(&self, event: Event) -> bool {
+        self.p.raw_is_pending(event)
+    }
+
+    /// Clear the flag of an event.
+    #[inline]
+    pub fn clear_pending(&mut self, event: Event) {
+        self.p.raw_clear_pending(event);
+    }
+
+    /// Wait for the next period to elapse.
+    ///
+    /// This polls the [`Event::CompareA`] flag in CTC mode and the [`Event::Overflow`] flag in
+    /// normal mode, so it cannot be used when the corresponding interrupt is enabled.
+    pub fn wait(&mut self) -> nb::Result<(), core::convert::Infallible> {
+        let event = match self.mode {
+            TimerMode::Normal => Event::Overflow,
+            TimerMode::Ctc => Event::CompareA,
+        };
+        if self.p.raw_is_pending(event) {
+            self.p.raw_clear_pending(event);
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Stop the timer and return the peripheral.
+    ///
+    /// The clock of the peripheral is stopped in the Power Reduction Register, if available.
+    pub fn release(mut self) -> TC
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        self.stop();
+        for event in [Event::Overflow, Event::CompareA, Event::CompareB] {
+            self.unlisten(event);
+        }
+        self.p.raw_set_powered(false);
+        self.p
+    }
+}
+
+/// Free-running counter which is extended to 64 bits by counting its overflows in software
+///
+/// This is the time base of the `embassy-time` driver and the RTIC monotonics.  The timer runs in
+/// normal mode at a fixed tick rate with the overflow interrupt enabled, and its handler must
+/// call [`on_overflow()`][ExtendedCounter::on_overflow].  The compare channels are left to the
+/// owner of the counter.
+pub struct ExtendedCounter<H, TC> {
+    timer: TC,
+    /// Number of counter overflows since the counter was started.
+    overflows: u64,
+    _h: PhantomData<fn() -> H>,
+}
+
+impl<H, TC: TimerOps<H>> ExtendedCounter<H, TC> {
+    /// Start `timer` counting from zero at `tick_hz`.
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
+    ///
+    /// # Panics
+    /// Panics if the timer cannot run at `tick_hz` with the given core clock.
+    pub fn start<CLOCK: crate::clock::Clock>(mut timer: TC, tick_hz: u64) -> Self
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        let prescaler = TC::PRESCALERS
+            .iter()
+            .copied()
+            .find(|p| CLOCK::FREQ as u64 == tick_hz * p.divider() as u64)
+            .expect("tick rate not reachable with this timer");
+
+        timer.raw_set_powered(true);
+        timer.raw_set_clock(None);
+        for event in [Event::Overflow, Event::CompareA, Event::CompareB] {
+            timer.raw_set_interrupt(event, false);
+        }
+        timer.raw_set_mode(TimerMode::Normal);
+        timer.raw_write_counter(0);
+        timer.raw_clear_pending(Event::Overflow);
+        timer.raw_clear_pending(Event::CompareA);
+        timer.raw_set_interrupt(Event::Overflow, true);
+        timer.raw_set_clock(Some(prescaler));
+
+        Self {
+            timer,
+            overflows: 0,
+            _h: PhantomData,
+        }
+    }
+
+    /// Current value of the extended counter.
+    ///
+    /// Must be called in a critical section, so the overflow interrupt cannot run in between.
+    pub fn now(&self) -> u64 {
+        let mut overflows = self.overflows;
+        let mut counter = self.timer.raw_read_counter();
+        // An overflow which was not yet handled by `on_overflow()`.  The flag might have been set
+        // right after reading the counter, so the counter must be read again.
+        if self.timer.raw_is_pending(Event::Overflow) {
+            counter = self.timer.raw_read_counter();
+            overflows += 1;
+        }
+        overflows * (TC::MAX as u64 + 1) + counter as u64
+    }
+
+    /// Handler for the overflow interrupt of the timer.
+    #[inline]
+    pub fn on_overflow(&mut self) {
+        self.overflows += 1;
+    }
+
+    /// The underlying timer, e.g. to use its compare channels.
+    #[inline]
+    pub fn timer(&mut self) -> &mut TC {
+        &mut self.timer
+    }
+}
+
+#[macro_export]
+macro_rules! impl_timer {
+    (
+        hal: $HAL:ty,
+        peripheral: $TC:ty,
+        counter: $Counter:ty,
+        prescalers: [$($prescaler:ident),+ $(,)?],
+        set_clock: |$clock_periph:ident, $clock:ident| $set_clock:block,
+        set_mode: |$mode_periph:ident, $mode:ident| $set_mode:block,
+        registers: {
+            counter: $tcnt:ident,
+            compare_a: $ocra:ident,
+            $(compare_b: $ocrb:ident,)?
+            $(top: $top:ident,)?
+            mask: $timsk:ident,
+            flags: $tifr:ident,
+        },
+        interrupts: {
+            overflow: ($toie:ident, $tov:ident),
+            compare_a: ($ociea:ident, $ocfa:ident),
+            $(compare_b: ($ocieb:ident, $ocfb:ident),)?
+        },
+    ) => {
+        impl $crate::timer::TimerOps<$HAL> for $TC {
+            const MAX: u16 = <$Counter>::MAX as u16;
+
+            const PRESCALERS: &'static [$crate::timer::Prescaler] =
+                &[$($crate::timer::Prescaler::$prescaler),+];
+
+            const HAS_COMPARE_B: bool = $crate::impl_timer!(@present $($ocrb)?);
+
+            #[inline]
+            fn raw_set_clock(&mut self, prescaler: Option<$crate::timer::Prescaler>) {
+                let $clock_periph = &*self;
+                let $clock = prescaler;
+                $set_clock
+            }
+
+            #[inline]
+            fn raw_set_mode(&mut self, mode: $crate::timer::TimerMode) {
+                let $mode_periph = &*self;
+                let $mode = mode;
+                $set_mode
+            }
+
+            #[inline]
+            fn raw_read_counter(&self) -> u16 {
+                $crate::timer::access_16bit(|| self.$tcnt().read().bits().into())
+            }
+
+            #[inline]
+            fn raw_write_counter(&mut self, value: u16) {
+                $crate::timer::access_16bit(|| {
+                    self.$tcnt().write(|w| unsafe { w.bits(value as $Counter) });
+                })
+            }
+
+            #[inline]
+            fn raw_set_compare(&mut self, channel: $crate::timer::OutputCompare, value: u16) {
+                $crate::timer::access_16bit(|| match channel {
+                    $crate::timer::OutputCompare::A => {
+                        self.$ocra().write(|w| unsafe { w.bits(value as $Counter) });
+                        // Some timers use a separate register as TOP in CTC mode.
+                        $(self.$top().write(|w| unsafe { w.bits(value as $Counter) });)?
+                    }
+                    $(
+                    $crate::timer::OutputCompare::B => {
+                        self.$ocrb().write(|w| unsafe { w.bits(value as $Counter) });
+                    }
+                    )?
+                    #[allow(unreachable_patterns)]
+                    _ => (), // This timer has no second compare channel
+                })
+            }
+
+            #[inline]
+            fn raw_set_interrupt(&mut self, event: $crate::timer::Event, enable: bool) {
+                // SAFETY: The mask register may be shared with other timers, so the
+                // read-modify-write sequence is wrapped in a critical section.
+                $crate::avr_device::interrupt::free(|_| match event {
+                    $crate::timer::Event::Overflow => {
+                        self.$timsk().modify(|_, w| w.$toie().bit(enable));
+                    }
+                    $crate::timer::Event::CompareA => {
+                        self.$timsk().modify(|_, w| w.$ociea().bit(enable));
+                    }
+                    $(
+                    $crate::timer::Event::CompareB => {
+                        self.$timsk().modify(|_, w| w.$ocieb().bit(enable));
+                    }
+                    )?
+                    #[allow(unreachable_patterns)]
+                    _ => (),
+                })
+            }
+
+            #[inline]
+            fn raw_is_pending(&self, event: $crate::timer::Event) -> bool {
+                match event {
+                    $crate::timer::Event::Overflow => self.$tifr().read().$tov().bit_is_set(),
+                    $crate::timer::Event::CompareA => self.$tifr().read().$ocfa().bit_is_set(),
+                    $(
+                    $crate::timer::Event::CompareB => self.$tifr().read().$ocfb().bit_is_set(),
+                    )?
+                    #[allow(unreachable_patterns)]
+                    _ => false,
+                }
+            }
+
+            #[inline]
+            fn raw_clear_pending(&mut self, event: $crate::timer::Event) {
+                // Flags are cleared by writing a logical one.  Writing the whole register (instead
+                // of a read-modify-write) makes sure no other flags are cleared by accident.
+                match event {
+                    $crate::timer::Event::Overflow => {
+                        self.$tifr().write(|w| w.$tov().set_bit());
+                    }
+                    $crate::timer::Event::CompareA => {
+                        self.$tifr().write(|w| w.$ocfa().set_bit());
+                    }
+                    $(
+                    $crate::timer::Event::CompareB => {
+                        self.$tifr().write(|w| w.$ocfb().set_bit());
+                    }
+                    )?
+                    #[allow(unreachable_patterns)]
+                    _ => (),
+                }
+            }
+        }
+    };
+    (@present $x:ident) => {
+        true
+    };
+    (@present) => {
+        false
+    };
+}
@@ -0,0 +1,290 @@
+//! Square-wave tone generation, like `tone()` in the Arduino core
+//!
+//! The [`Tone`] driver runs a timer in CTC mode with twice the tone frequency, so every compare
+//! match flips the output.  There are two ways to drive the output pin:
+//!
+//! - On the compare output pin of the timer (`OCnA`), the hardware toggles the pin, which gives
+//!   exact frequencies without any CPU load ([`Tone::new()`]).
+//! - Any other pin is toggled from the `TIMERn_COMPA` interrupt handler
+//!   ([`Tone::with_pin()`]).
+//!
+//! Tones with a duration and melodies ([`Tone::play_melody()`]) are timed by counting the compare
+//! matches, so they also need the interrupt handler to call [`Tone::on_compare()`].  Only an
+//! endless tone on the `OCnA` pin works without the interrupt.
+//!
+//! # Example
+//! ```
+//! let mut tone = Tone::<_, CoreClock, _>::new(dp.TC2, pins.pb3.into_output());
+//!
+//! // A4 until stopped
+//! tone.play(440.Hz()).unwrap();
+//! delay.delay_ms(500);
+//! tone.stop();
+//! ```
+use core::marker::PhantomData;
+
+use crate::port::{mode, Pin, PinOps};
+use crate::timer::{
+    Duration, Event, Hertz, OutputCompare, Prescaler, TimerError, TimerMode, TimerOps,
+};
+
+/// Internal trait for timers which can toggle their `OCnA` pin on compare match.
+///
+/// **Prefer using the [`Tone`] API instead of this trait.**
+pub trait ToneOps<H>: TimerOps<H> {
+    /// The `OCnA` pin of this timer.
+    type OcaPin: PinOps;
+
+    /// Toggle (or stop toggling) the `OCnA` pin on compare match A.
+    fn raw_set_toggle(&mut self, enable: bool);
+}
+
+/// One note of a melody.
+///
+/// A frequency of 0 Hz is a rest.
+///
+/// # Example
+/// ```
+/// const MELODY: &[Note] = &[Note::new(262, 250), Note::rest(125), Note::new(392, 500)];
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Note {
+    pub frequency: Hertz,
+    pub duration: Duration,
+}
+
+impl Note {
+    /// Note of `frequency` Hz for `duration` milliseconds.
+    pub const fn new(frequency: u32, duration: u32) -> Self {
+        Self {
+            frequency: Hertz::from_raw(frequency),
+            duration: Duration::millis(duration),
+        }
+    }
+
+    /// Silence for `duration` milliseconds.
+    pub const fn rest(duration: u32) -> Self {
+        Self::new(0, duration)
+    }
+}
+
+/// Rests are timed with the timer running at this frequency while the output stays low.
+const REST_FREQUENCY: Hertz = Hertz::from_raw(1000);
+
+/// Tone generator on a timer
+pub struct Tone<H, TC, CLOCK, PIN> {
+    timer: TC,
+    pin: Pin<mode::Output, PIN>,
+    /// Whether the pin is toggled by the timer hardware instead of the interrupt handler.
+    hardware: bool,
+    playing: bool,
+    /// The current note is a rest.
+    silent: bool,
+    /// Compare matches until the current note ends, `None` for an endless tone.
+    remaining: Option<u32>,
+    /// Notes after the current one.
+    melody: &'static [Note],
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC, CLOCK> Tone<H, TC, CLOCK, <TC as ToneOps<H>>::OcaPin>
+where
+    TC: ToneOps<H>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Generate tones on the `OCnA` pin of the timer, which is toggled by hardware.
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
+    pub fn new(timer: TC, pin: Pin<mode::Output, TC::OcaPin>) -> Self
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        Self::init(timer, pin, true)
+    }
+}
+
+impl<H, TC, CLOCK, PIN> Tone<H, TC, CLOCK, PIN>
+where
+    TC: ToneOps<H>,
+    CLOCK: crate::clock::Clock,
+    PIN: PinOps,
+{
+    /// Generate tones on any pin, toggled from the `TIMERn_COMPA` interrupt handler.
+    ///
+    /// **[`on_compare()`][Tone::on_compare] must be called from the interrupt handler.**
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
+    pub fn with_pin(timer: TC, pin: Pin<mode::Output, PIN>) -> Self
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        Self::init(timer, pin, false)
+    }
+
+    fn init(mut timer: TC, mut pin: Pin<mode::Output, PIN>, hardware: bool) -> Self
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        timer.raw_set_powered(true);
+        timer.raw_set_clock(None);
+        timer.raw_set_interrupt(Event::CompareA, false);
+        timer.raw_set_toggle(false);
+        timer.raw_set_mode(TimerMode::Ctc);
+        pin.set_low();
+        Self {
+            timer,
+            pin,
+            hardware,
+            playing: false,
+            silent: false,
+            remaining: None,
+            melody: &[],
+            _clock: PhantomData,
+            _h: PhantomData,
+        }
+    }
+
+    /// Play a tone until [`stop()`][Tone::stop] is called or another tone is started.
+    pub fn play(&mut self, frequency: Hertz) -> Result<(), TimerError> {
+        self.melody = &[];
+        self.start(frequency, None)
+    }
+
+    /// Play a tone for the given duration, in the background.
+    ///
+    /// The duration is counted in the interrupt handler.
+    pub fn play_for(&mut self, frequency: Hertz, duration: Duration) -> Result<(), TimerError> {
+        self.melody = &[];
+        self.start(frequency, Some(duration))
+    }
+
+    /// Play the notes of `melody` one after the other, in the background.
+    ///
+    /// The melody is advanced in the interrupt handler.  All frequencies are checked before the
+    /// first note starts.
+    pub fn play_melody(&mut self, melody: &'static [Note]) -> Result<(), TimerError> {
+        for note in melody {
+            if note.frequency.raw() != 0 {
+                Self::settings(note.frequency)?;
+            }
+        }
+        self.melody = melody;
+        self.next_note();
+        Ok(())
+    }
+
+    /// Stop the tone or melody.  The pin is left low.
+    pub fn stop(&mut self) {
+        self.timer.raw_set_clock(None);
+        self.timer.raw_set_interrupt(Event::CompareA, false);
+        self.timer.raw_set_toggle(false);
+        self.pin.set_low();
+        self.melody = &[];
+        self.remaining = None;
+        self.playing = false;
+    }
+
+    /// Whether a tone or melody is playing.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Toggle the pin and time the current note.
+    ///
+    /// Call this from the `TIMERn_COMPA` interrupt handler.
+    pub fn on_compare(&mut self) {
+        if !self.playing {
+            return;
+        }
+        if !self.hardware && !self.silent {
+            self.pin.toggle();
+        }
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining = remaining.saturating_sub(1);
+            if *remaining == 0 {
+                self.next_note();
+            }
+        }
+    }
+
+    /// Stop the tone and return the timer and the pin.
+    ///
+    /// The clock of the peripheral is stopped in the Power Reduction Register, if available.
+    pub fn release(mut self) -> (TC, Pin<mode::Output, PIN>)
+    where
+        TC: crate::power::PowerReductionOps<H>,
+    {
+        self.stop();
+        self.timer.raw_set_powered(false);
+        (self.timer, self.pin)
+    }
+
+    /// Prescaler and compare value for a tone of `frequency`.
+    fn settings(frequency: Hertz) -> Result<(Prescaler, u16), TimerError> {
+        let hz = frequency.to_Hz();
+        if hz == 0 {
+            return Err(TimerError::PeriodTooLong);
+        }
+        // The output toggles on every compare match, so the timer runs at twice the frequency.
+        Prescaler::for_cycles(CLOCK::FREQ / (2 * hz), TC::PRESCALERS, TC::MAX)
+    }
+
+    fn next_note(&mut self) {
+        let Some((note, rest)) = self.melody.split_first() else {
+            self.stop();
+            return;
+        };
+        self.melody = rest;
+        // The frequencies were checked in `play_melody()`.
+        let _ = self.start(note.frequency, Some(note.duration));
+    }
+
+    fn start(&mut self, frequency: Hertz, duration: Option<Duration>) -> Result<(), TimerError> {
+        let silent = frequency.raw() == 0;
+        let frequency = if silent { REST_FREQUENCY } else { frequency };
+        let (prescaler, top) = Self::settings(frequency)?;
+
+        self.timer.raw_set_clock(None);
+        self.timer.raw_set_compare(OutputCompare::A, top);
+        self.timer.raw_write_counter(0);
+        self.timer.raw_clear_pending(Event::CompareA);
+        self.timer.raw_set_toggle(self.hardware && !silent);
+        self.pin.set_low();
+
+        self.silent = silent;
+        self.remaining = duration.map(|duration| {
+            let toggles = (2 * frequency.to_Hz()).saturating_mul(duration.to_millis()) / 1000;
+            toggles.max(1)
+        });
+        self.playing = true;
+
+        let needs_interrupt = !self.hardware || self.remaining.is_some();
+        self.timer
+            .raw_set_interrupt(Event::CompareA, needs_interrupt);
+        self.timer.raw_set_clock(Some(prescaler));
+        Ok(())
+    }
+}
+
+#[macro_export]
+macro_rules! impl_tone {
+    (
+        hal: $HAL:ty,
+        peripheral: $TC:ty,
+        pin: $PIN:ty,
+        set_toggle: |$periph:ident, $enable:ident| $set_toggle:block,
+    ) => {
+        impl $crate::tone::ToneOps<$HAL> for $TC {
+            type OcaPin = $PIN;
+
+            #[inline]
+            fn raw_set_toggle(&mut self, enable: bool) {
+                let $periph = &*self;
+                let $enable = enable;
+                $set_toggle
+            }
+        }
+    };
+}
@@ -244,7 +244,8 @@ impl<H, USART, RXPIN, TXPIN, CLOCK>
         CLOCK,
     >
 where
-    USART: UsartOps<H, port::Pin<port::mode::Input, RXPIN>, port::Pin<port::mode::Output, TXPIN>>,
+    USART: UsartOps<H, port::Pin<port::mode::Input, RXPIN>, port::Pin<port::mode::Output, TXPIN>>
+        + crate::power::PowerReductionOps<H>,
     RXPIN: port::PinOps,
     TXPIN: port::PinOps,
 {
@@ -252,6 +253,8 @@ where
     ///
     /// Note that the RX and TX pins are hardwired for each USART peripheral and you *must* pass
     /// the correct ones.  This is enforced at compile time.
+    ///
+    /// The clock of the peripheral is started in the Power Reduction Register, if necessary.
     pub fn new<IMODE: port::mode::InputMode>(
         p: USART,
         rx: port::Pin<port::mode::Input<IMODE>, RXPIN>,
@@ -265,6 +268,7 @@ where
             _clock: marker::PhantomData,
             _h: marker::PhantomData,
         };
+        usart.p.raw_set_powered(true);
         usart.p.raw_init(baudrate);
         usart
     }
@@ -272,11 +276,33 @@ where
 
 impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> Usart<H, USART, RX, TX, CLOCK> {
     /// Deinitialize/disable this peripheral and release the pins.
-    pub fn release(mut self) -> (USART, RX, TX) {
+    ///
+    /// The clock of the peripheral is stopped in the Power Reduction Register, if available.
+    pub fn release(mut self) -> (USART, RX, TX)
+    where
+        USART: crate::power::PowerReductionOps<H>,
+    {
         self.p.raw_deinit();
+        self.p.raw_set_powered(false);
         (self.p, self.rx, self.tx)
     }
 
+    /// Reinitialize the peripheral with a baudrate for a different clock speed.
+    ///
+    /// This is used to keep the USART working after switching the system clock prescaler, see
+    /// [`SystemClock`][crate::clock::SystemClock].  Remaining data is transmitted before.
+    pub fn with_baudrate<NEW>(mut self, baudrate: Baudrate<NEW>) -> Usart<H, USART, RX, TX, NEW> {
+        self.flush();
+        self.p.raw_init(baudrate);
+        Usart {
+            p: self.p,
+            rx: self.rx,
+            tx: self.tx,
+            _clock: marker::PhantomData,
+            _h: marker::PhantomData,
+        }
+    }
+
     /// Block until all remaining data has been transmitted.
     pub fn flush(&mut self) {
         nb::block!(self.p.raw_flush()).unwrap_infallible()
@@ -26,6 +26,23 @@ pub enum Timeout {
     Ms8000,
 }
 
+/// What happens when the watchdog times out
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mode {
+    /// Reset the device.
+    Reset,
+    /// Run the `WDT` interrupt handler and keep running.
+    ///
+    /// This is useful for waking up from sleep modes periodically.
+    Interrupt,
+    /// Run the `WDT` interrupt handler first and reset the device on the next timeout.
+    ///
+    /// The hardware disables the interrupt when it fires, so the handler gets one timeout period to
+    /// e.g. log the reason for the crash.  Calling [`Wdt::listen()`] again keeps the device from
+    /// being reset.
+    InterruptThenReset,
+}
+
 /// Internal trait for low-level watchdog operations.
 ///
 /// **HAL users should use the [`Wdt`] type instead.**
@@ -37,12 +54,12 @@ pub trait WdtOps<H> {
     /// **Warning**: This is a low-level method and should not be called directly from user code.
     fn raw_init(&mut self, m: &Self::MCUSR);
 
-    /// Start the watchdog timer with the specified timeout.
+    /// Start the watchdog timer with the specified timeout and mode.
     ///
-    /// If the timeout value is not supported, `Err(())` should be returned.
+    /// If the timeout value or mode is not supported, `Err(())` should be returned.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_start(&mut self, timeout: Timeout) -> Result<(), ()>;
+    fn raw_start(&mut self, timeout: Timeout, mode: Mode) -> Result<(), ()>;
 
     /// Feed this watchdog, to reset its period.
     ///
@@ -55,6 +72,26 @@ pub trait WdtOps<H> {
     fn raw_stop(&mut self);
 }
 
+/// Internal trait for watchdogs which can trigger an interrupt.
+///
+/// **HAL users should use the [`Wdt`] type instead.**
+pub trait WdtInterruptOps<H>: WdtOps<H> {
+    /// Enable or disable the watchdog interrupt without touching the reset behavior.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_interrupt(&mut self, enable: bool);
+
+    /// Check whether the watchdog interrupt flag is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_interrupt_flag(&self) -> bool;
+
+    /// Clear the watchdog interrupt flag.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_interrupt_flag(&mut self);
+}
+
 pub struct Wdt<H, WDT> {
     p: WDT,
     _h: PhantomData<H>,
@@ -66,8 +103,12 @@ impl<H, WDT: WdtOps<H>> Wdt<H, WDT> {
         Self { p, _h: PhantomData }
     }
 
-    pub fn start(&mut self, timeout: Timeout) -> Result<(), ()> {
-        self.p.raw_start(timeout)
+    /// Start the watchdog with the given timeout.
+    ///
+    /// Returns `Err(())` if the MCU does not support the timeout or, for the interrupt modes, has
+    /// no watchdog interrupt.
+    pub fn start(&mut self, timeout: Timeout, mode: Mode) -> Result<(), ()> {
+        self.p.raw_start(timeout, mode)
     }
 
     pub fn feed(&mut self) {
@@ -79,6 +120,36 @@ impl<H, WDT: WdtOps<H>> Wdt<H, WDT> {
     }
 }
 
+impl<H, WDT: WdtInterruptOps<H>> Wdt<H, WDT> {
+    /// Re-enable the watchdog interrupt.
+    ///
+    /// In [`Mode::InterruptThenReset`], this has to be done after every interrupt to prevent the
+    /// next timeout from resetting the device.
+    pub fn listen(&mut self) {
+        self.p.raw_set_interrupt(true)
+    }
+
+    /// Disable the watchdog interrupt.
+    ///
+    /// In [`Mode::InterruptThenReset`], the next timeout then resets the device right away.
+    pub fn unlisten(&mut self) {
+        self.p.raw_set_interrupt(false)
+    }
+
+    /// Check whether the watchdog timed out while its interrupt was not handled.
+    ///
+    /// The flag is cleared automatically when the interrupt handler runs, so this is only useful
+    /// for polling the watchdog with interrupts disabled.
+    pub fn interrupt_flag(&self) -> bool {
+        self.p.raw_interrupt_flag()
+    }
+
+    /// Clear the watchdog interrupt flag.
+    pub fn clear_interrupt_flag(&mut self) {
+        self.p.raw_clear_interrupt_flag()
+    }
+}
+
 #[macro_export]
 macro_rules! impl_wdt {
     (
@@ -86,6 +157,7 @@ macro_rules! impl_wdt {
         peripheral: $WDT:ty,
         mcusr: $MCUSR:ty,
         wdtcsr_name: $wdtcsr:ident,
+        $(interrupt: $wdie:ident, $wdif:ident,)?
         timeout: |$to:ident, $w:ident| $to_match:expr,
     ) => {
         impl $crate::wdt::WdtOps<$HAL> for $WDT {
@@ -99,7 +171,17 @@ macro_rules! impl_wdt {
             }
 
             #[inline]
-            fn raw_start(&mut self, timeout: Timeout) -> Result<(), ()> {
+            fn raw_start(
+                &mut self,
+                timeout: Timeout,
+                mode: $crate::wdt::Mode,
+            ) -> Result<(), ()> {
+                let reset = mode != $crate::wdt::Mode::Interrupt;
+                let interrupt = mode != $crate::wdt::Mode::Reset;
+                if interrupt && !$crate::impl_wdt!(@has_interrupt $($wdie)?) {
+                    return Err(());
+                }
+
                 // The sequence for changing time-out configuration is as follows:
                 //
                 //     1. In the same operation, write a logic one to the Watchdog change enable bit
@@ -118,7 +200,9 @@ macro_rules! impl_wdt {
                     self.$wdtcsr().write(|w| {
                         let $to = timeout;
                         let $w = w;
-                        ($to_match).wde().set_bit().wdce().clear_bit()
+                        let w = ($to_match).wde().bit(reset).wdce().clear_bit();
+                        $(w.$wdie().bit(interrupt);)?
+                        w
                     });
 
                     Ok(())
@@ -150,5 +234,32 @@ macro_rules! impl_wdt {
                 })
             }
         }
+
+        $(
+            impl $crate::wdt::WdtInterruptOps<$HAL> for $WDT {
+                #[inline]
+                fn raw_set_interrupt(&mut self, enable: bool) {
+                    // Writing a one to the interrupt flag clears it, so leave it alone here.
+                    self.$wdtcsr()
+                        .modify(|_, w| w.$wdie().bit(enable).$wdif().clear_bit());
+                }
+
+                #[inline]
+                fn raw_interrupt_flag(&self) -> bool {
+                    self.$wdtcsr().read().$wdif().bit_is_set()
+                }
+
+                #[inline]
+                fn raw_clear_interrupt_flag(&mut self) {
+                    self.$wdtcsr().modify(|_, w| w.$wdif().set_bit());
+                }
+            }
+        )?
+    };
+    (@has_interrupt) => {
+        false
+    };
+    (@has_interrupt $wdie:ident) => {
+        true
     };
 }
@@ -0,0 +1,43 @@
+/*!
+ * 10-bit high-speed PWM from `TC4` clocked by the 64 MHz PLL, with fault protection.
+ *
+ * D10 outputs a 62.5 kHz PWM signal with 10-bit resolution, e.g. for a motor driver.  Pulling D3
+ * low (an overcurrent comparator or an emergency stop) immediately disconnects the output in
+ * hardware.  The output is enabled again once D3 is released.
+ *
+ * Connections
+ * -----------
+ *  - `D10`: PWM input of the motor driver
+ *  - `D3`: Active-low fault signal
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::prelude::*;
+use arduino_hal::simple_pwm::*;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let mut timer4 = Timer4HsPwm::new(dp.TC4, &dp.PLL, Timer4Clock::Pll64MHz, 62500.Hz()).unwrap();
+    let fault = pins.d3.into_pull_up_input();
+    timer4.enable_fault_protection(FaultSource::Int0, FaultEdge::Falling, true);
+
+    let mut d10 = pins.d10.into_output().into_pwm(&timer4);
+    // 1023 at 62.5 kHz
+    let max = d10.get_max_duty();
+    d10.set_duty(max / 4);
+    d10.enable();
+
+    loop {
+        if timer4.is_faulted() {
+            while fault.is_low() {}
+            timer4.clear_fault();
+            d10.enable();
+        }
+        arduino_hal::delay_ms(10);
+    }
+}
@@ -0,0 +1,36 @@
+/*!
+ * Measure the voltage between A1 and A0 using a differential ADC channel.
+ *
+ * The differential channels return signed values: A1 below A0 gives a negative reading.  At 200x
+ * gain, a difference of a few millivolts is enough to show up in the readings.
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::prelude::*;
+use panic_halt as _;
+
+use arduino_hal::adc::Differential;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut adc = arduino_hal::Adc::new(dp.ADC, Default::default());
+
+    let a0 = pins.a0.into_analog_input(&mut adc);
+    let a1 = pins.a1.into_analog_input(&mut adc);
+
+    let x10 = Differential::<_, _, 10>::new(&a1, &a0);
+    let x200 = Differential::<_, _, 200>::new(&a1, &a0);
+
+    loop {
+        let low = adc.read_differential(&x10);
+        let high = adc.read_differential(&x200);
+        ufmt::uwriteln!(&mut serial, "A1 - A0: {} (10x) {} (200x)", low, high).unwrap_infallible();
+
+        arduino_hal::delay_ms(1000);
+    }
+}
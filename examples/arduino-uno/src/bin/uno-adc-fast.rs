@@ -0,0 +1,52 @@
+/*!
+ * Capture a burst of fast 8-bit samples from A0 and read A1 against the internal reference.
+ *
+ * With the ADC clock at 1 MHz, a conversion takes 13 µs, so the burst of 256 samples covers
+ * about 3.4 ms.  The ADC runs too fast for full 10-bit accuracy, but the upper 8 bits are fine.
+ *
+ * A1 is read against the internal 1.1V reference, A0 against AVcc.  The ADC waits for the
+ * reference to settle whenever it changes.
+ *
+ * Connections
+ * -----------
+ *  - `A0`: Analog signal, e.g. the output of a microphone amplifier
+ *  - `A1`: Small voltage below 1.1V, e.g. a thermocouple amplifier
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::adc::{AdcSettings, ClockDivider, ReferenceVoltage};
+use arduino_hal::prelude::*;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let settings = AdcSettings {
+        clock_divider: ClockDivider::Factor16,
+        ..Default::default()
+    };
+    let mut adc = arduino_hal::Adc::new(dp.ADC, settings);
+    let a0 = pins.a0.into_analog_input(&mut adc);
+    let a1 = pins.a1.into_analog_input(&mut adc);
+
+    let mut burst = [0u8; 256];
+
+    loop {
+        let small = adc.read_with_reference(&a1, ReferenceVoltage::Internal);
+
+        adc.set_reference(ReferenceVoltage::AVcc);
+        for sample in burst.iter_mut() {
+            *sample = adc.read_blocking_8bit(&a0);
+        }
+
+        let min = burst.iter().copied().min().unwrap_or(0);
+        let max = burst.iter().copied().max().unwrap_or(0);
+        ufmt::uwriteln!(&mut serial, "A0: {}..{} A1: {}", min, max, small).unwrap_infallible();
+
+        arduino_hal::delay_ms(500);
+    }
+}
@@ -0,0 +1,51 @@
+/*!
+ * Compare plain, averaged, oversampled and noise-reduced readings of A0.
+ *
+ * Oversampling by 4^2 = 16 samples gives a 12-bit result (0..=4095) and averaging 16 samples
+ * gives a steadier 10-bit result.  The noise-reduced read puts the CPU into ADC Noise Reduction
+ * sleep during the conversion, which needs the (empty) `ADC` interrupt handler below.
+ *
+ * Connections
+ * -----------
+ *  - `A0`: Analog signal, e.g. the wiper of a potentiometer
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::prelude::*;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut adc = arduino_hal::Adc::new(dp.ADC, Default::default());
+    let mut cpu = dp.CPU;
+    let a0 = pins.a0.into_analog_input(&mut adc);
+
+    loop {
+        let plain = adc.read_blocking(&a0);
+        let averaged = adc.read_averaged(&a0, 16);
+        let oversampled = adc.read_oversampled(&a0, 2);
+        // SAFETY: Not called inside a critical section.
+        let quiet = unsafe { adc.read_noise_reduced(&a0, &mut cpu) };
+
+        ufmt::uwriteln!(
+            &mut serial,
+            "plain: {} averaged: {} 12-bit: {} noise reduced: {}",
+            plain,
+            averaged,
+            oversampled,
+            quiet
+        )
+        .unwrap_infallible();
+
+        arduino_hal::delay_ms(500);
+    }
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn ADC() {}
@@ -0,0 +1,77 @@
+/*!
+ * Sample A0 at a fixed rate in the background and print statistics of each block of samples.
+ *
+ * `TC0` triggers a conversion 4000 times per second and the `ADC` interrupt handler stores each
+ * result in a buffer.  Once the buffer is full, the main loop prints the minimum, maximum and
+ * average and starts the next block.
+ *
+ * Connections
+ * -----------
+ *  - `A0`: Analog signal, e.g. the output of a microphone amplifier
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::adc::{Sampler, TimerTrigger};
+use arduino_hal::prelude::*;
+use core::cell;
+use panic_halt as _;
+
+const SAMPLES: usize = 128;
+
+type AdcSampler = Sampler<'static, TimerTrigger<arduino_hal::pac::TC0>>;
+
+static SAMPLER: avr_device::interrupt::Mutex<cell::RefCell<Option<AdcSampler>>> =
+    avr_device::interrupt::Mutex::new(cell::RefCell::new(None));
+
+static mut BUFFER: [u16; SAMPLES] = [0; SAMPLES];
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut adc = arduino_hal::Adc::new(dp.ADC, Default::default());
+    let a0 = pins.a0.into_analog_input(&mut adc);
+
+    let trigger = TimerTrigger::new(dp.TC0, 4.kHz()).unwrap();
+    // SAFETY: The buffer is only accessed through the sampler.
+    let buffer = unsafe { &mut *core::ptr::addr_of_mut!(BUFFER) };
+    let sampler = Sampler::new(adc, &a0, trigger, buffer);
+    avr_device::interrupt::free(|cs| SAMPLER.borrow(cs).replace(Some(sampler)));
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    loop {
+        let stats = avr_device::interrupt::free(|cs| {
+            let mut sampler = SAMPLER.borrow(cs).borrow_mut();
+            let sampler = sampler.as_mut().unwrap();
+            if !sampler.is_full() {
+                return None;
+            }
+            let samples = sampler.samples();
+            let min = samples.iter().copied().min().unwrap_or(0);
+            let max = samples.iter().copied().max().unwrap_or(0);
+            let sum: u32 = samples.iter().map(|&s| s as u32).sum();
+            sampler.restart();
+            Some((min, max, sum / SAMPLES as u32))
+        });
+
+        if let Some((min, max, average)) = stats {
+            ufmt::uwriteln!(&mut serial, "min: {} max: {} avg: {}\r", min, max, average)
+                .unwrap_infallible();
+        }
+    }
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn ADC() {
+    avr_device::interrupt::free(|cs| {
+        if let Some(sampler) = SAMPLER.borrow(cs).borrow_mut().as_mut() {
+            sampler.on_conversion();
+        }
+    })
+}
@@ -0,0 +1,75 @@
+/*!
+ * Read A0 in the main loop and A1 from a timer interrupt through one shared ADC.
+ *
+ * The interrupt handler fires every 10 ms and may interrupt a reading of A0.  It waits for the
+ * running conversion to complete before it reads A1, and the reading of A0 is started again
+ * afterwards.  Connect A0 and A1 to different voltages (e.g. GND and 5V) to see that each input
+ * always gets its own value.
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::adc::{AnalogInput, SharedAdc};
+use arduino_hal::hal::port::PC1;
+use arduino_hal::port::{mode, Pin};
+use arduino_hal::prelude::*;
+use core::cell;
+use core::mem::MaybeUninit;
+use panic_halt as _;
+
+static mut ADC: MaybeUninit<SharedAdc> = MaybeUninit::uninit();
+
+static A1_INPUT: avr_device::interrupt::Mutex<
+    cell::RefCell<Option<AnalogInput<'static, Pin<mode::Analog, PC1>>>>,
+> = avr_device::interrupt::Mutex::new(cell::RefCell::new(None));
+static A1_VALUE: avr_device::interrupt::Mutex<cell::Cell<u16>> =
+    avr_device::interrupt::Mutex::new(cell::Cell::new(0));
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    // SAFETY: `ADC` is only written here, once, before interrupts are enabled.
+    let adc: &'static SharedAdc = unsafe {
+        (*core::ptr::addr_of_mut!(ADC)).write(SharedAdc::new(
+            arduino_hal::Adc::new(dp.ADC, Default::default()),
+            5000,
+        ))
+    };
+
+    let (a0, a1) = adc.with(|adc| {
+        (
+            pins.a0.into_analog_input(adc),
+            pins.a1.into_analog_input(adc),
+        )
+    });
+    let mut a0 = adc.input(a0);
+    avr_device::interrupt::free(|cs| A1_INPUT.borrow(cs).replace(Some(adc.input(a1))));
+
+    let mut timer = arduino_hal::Timer::new(dp.TC1);
+    timer.start(100.Hz()).unwrap();
+    timer.listen(arduino_hal::timer::Event::CompareA).unwrap();
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    loop {
+        let value = a0.read();
+        let a1_value = avr_device::interrupt::free(|cs| A1_VALUE.borrow(cs).get());
+        ufmt::uwriteln!(&mut serial, "A0: {}, A1: {}\r", value, a1_value).unwrap_infallible();
+
+        arduino_hal::delay_ms(100);
+    }
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn TIMER1_COMPA() {
+    avr_device::interrupt::free(|cs| {
+        if let Some(a1) = A1_INPUT.borrow(cs).borrow_mut().as_mut() {
+            A1_VALUE.borrow(cs).set(a1.read());
+        }
+    })
+}
@@ -0,0 +1,64 @@
+/*!
+ * Read a joystick on A0/A1 through per-channel handles sharing one ADC.
+ *
+ * The `Joystick` driver only knows about the `VoltageInput` trait, so it does not need access to
+ * the ADC itself.  A potentiometer on A2 is read through the embedded-hal 0.2 `OneShot` trait.
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::adc::VoltageInput;
+use arduino_hal::prelude::*;
+use panic_halt as _;
+
+/// Reference voltage (AVcc) in millivolts.
+const REFERENCE_MILLIVOLTS: u16 = 5000;
+
+struct Joystick<X, Y> {
+    x: X,
+    y: Y,
+}
+
+impl<X: VoltageInput, Y: VoltageInput> Joystick<X, Y> {
+    /// Deflection of both axes from the center position, in millivolts.
+    fn position(&mut self) -> (i16, i16) {
+        let center = (REFERENCE_MILLIVOLTS / 2) as i16;
+        (
+            self.x.read_millivolts() as i16 - center,
+            self.y.read_millivolts() as i16 - center,
+        )
+    }
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let adc = arduino_hal::adc::SharedAdc::new(
+        arduino_hal::Adc::new(dp.ADC, Default::default()),
+        REFERENCE_MILLIVOLTS,
+    );
+
+    let (a0, a1, mut a2) = adc.with(|adc| {
+        (
+            pins.a0.into_analog_input(adc),
+            pins.a1.into_analog_input(adc),
+            pins.a2.into_analog_input(adc),
+        )
+    });
+
+    let mut joystick = Joystick {
+        x: adc.input(a0),
+        y: adc.input(a1),
+    };
+
+    loop {
+        let (x, y) = joystick.position();
+        let pot: u16 = nb::block!((&adc).read(&mut a2)).unwrap_infallible();
+        ufmt::uwriteln!(&mut serial, "x: {}, y: {}, pot: {}", x, y, pot).unwrap_infallible();
+
+        arduino_hal::delay_ms(100);
+    }
+}
@@ -0,0 +1,52 @@
+/*!
+ * Print the supply voltage and the chip temperature, measured with the internal references.
+ *
+ * The calibration is kept in the EEPROM.  On the first run, the bandgap is calibrated against the
+ * supply voltage given in `MEASURED_VCC`, so set it to what a multimeter shows between 5V and GND.
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::adc::Calibration;
+use arduino_hal::prelude::*;
+use panic_halt as _;
+
+/// Supply voltage in millivolts, as measured with a multimeter.
+const MEASURED_VCC: u16 = 5000;
+
+/// EEPROM offset of the calibration record.
+const CALIBRATION_OFFSET: u16 = 0;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut adc = arduino_hal::Adc::new(dp.ADC, Default::default());
+    let mut eeprom = arduino_hal::Eeprom::new(dp.EEPROM);
+
+    if let Some(calibration) = Calibration::load(&eeprom, CALIBRATION_OFFSET) {
+        adc.set_calibration(calibration);
+    } else {
+        adc.calibrate_vcc(MEASURED_VCC);
+        adc.calibration()
+            .store(&mut eeprom, CALIBRATION_OFFSET)
+            .unwrap();
+        ufmt::uwriteln!(&mut serial, "Stored a new calibration.").unwrap_infallible();
+    }
+    ufmt::uwriteln!(
+        &mut serial,
+        "Bandgap: {} mV",
+        adc.calibration().bandgap_millivolts
+    )
+    .unwrap_infallible();
+
+    loop {
+        let vcc = adc.read_vcc_millivolts();
+        let temperature = adc.read_temperature_celsius();
+        ufmt::uwriteln!(&mut serial, "VCC: {} mV, {} °C", vcc, temperature).unwrap_infallible();
+
+        arduino_hal::delay_ms(1000);
+    }
+}
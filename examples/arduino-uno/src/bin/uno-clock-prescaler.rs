@@ -0,0 +1,52 @@
+/*!
+ * Divide the system clock down to 1 MHz at runtime to save power.
+ *
+ * The firmware alternates between 16 MHz and 1 MHz, blinking the builtin LED five times at each
+ * speed.  The serial port and the delay provider are rebuilt for the new clock speed after every
+ * switch, so the output stays readable and the blinking rate stays the same.
+ *
+ * The baudrate is kept low, so it can be generated accurately from the 1 MHz clock.
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::clock::{MHz1, MHz16, SystemClock};
+use arduino_hal::prelude::*;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::StatefulOutputPin;
+use panic_halt as _;
+
+fn blink(led: &mut impl StatefulOutputPin, delay: &mut impl DelayNs) {
+    for _ in 0..10 {
+        led.toggle().unwrap();
+        delay.delay_ms(200);
+    }
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 9600);
+    let mut led = pins.d13.into_output();
+
+    let mut cpu = dp.CPU;
+    let mut clock = SystemClock::<MHz16>::new(&mut cpu);
+
+    loop {
+        ufmt::uwriteln!(&mut serial, "Running at 16 MHz").unwrap_infallible();
+        blink(&mut led, &mut clock.delay());
+
+        // Finish the transmission before its timing changes.
+        serial.flush();
+        let slow = clock.switch::<MHz1>(&mut cpu);
+        let mut slow_serial = serial.with_baudrate(slow.baudrate(9600));
+
+        ufmt::uwriteln!(&mut slow_serial, "Running at 1 MHz").unwrap_infallible();
+        blink(&mut led, &mut slow.delay());
+
+        slow_serial.flush();
+        clock = slow.switch::<MHz16>(&mut cpu);
+        serial = slow_serial.with_baudrate(clock.baudrate(9600));
+    }
+}
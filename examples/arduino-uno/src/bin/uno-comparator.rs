@@ -0,0 +1,59 @@
+/*!
+ * Count zero crossings of a signal on A0 with the analog comparator.
+ *
+ * The signal on A0 is compared against the internal bandgap reference (about 1.1V), so it should
+ * swing around that voltage.  Every time it rises above the reference, the ANALOG_COMP interrupt
+ * increments a counter, which is printed together with the current comparator output once per
+ * second.
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::comparator::{Event, PositiveInput};
+use arduino_hal::prelude::*;
+use core::cell::Cell;
+use panic_halt as _;
+
+static CROSSINGS: avr_device::interrupt::Mutex<Cell<u16>> =
+    avr_device::interrupt::Mutex::new(Cell::new(0));
+
+#[avr_device::interrupt(atmega328p)]
+fn ANALOG_COMP() {
+    avr_device::interrupt::free(|cs| {
+        let crossings = CROSSINGS.borrow(cs);
+        crossings.set(crossings.get().wrapping_add(1));
+    })
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut adc = arduino_hal::Adc::new(dp.ADC, Default::default());
+    let a0 = pins.a0.into_analog_input(&mut adc);
+
+    let mut comparator = arduino_hal::AnalogComparator::new(dp.AC);
+    comparator.set_positive_input(PositiveInput::Bandgap);
+    // The ADC is handed back by `comparator.use_ain1()`.
+    let _muxed = comparator.use_adc_input(adc, &a0);
+
+    // The comparator output is set while the bandgap is above A0, so the signal rising above the
+    // reference is a falling edge of the output.
+    comparator.listen(Event::Falling);
+
+    // SAFETY: Not inside a critical section and any non-atomic operations have been completed by
+    // this point.
+    unsafe { avr_device::interrupt::enable() };
+
+    loop {
+        let crossings = avr_device::interrupt::free(|cs| CROSSINGS.borrow(cs).get());
+        let below = comparator.output();
+        ufmt::uwriteln!(&mut serial, "crossings: {}, below: {}", crossings, below)
+            .unwrap_infallible();
+
+        arduino_hal::delay_ms(1000);
+    }
+}
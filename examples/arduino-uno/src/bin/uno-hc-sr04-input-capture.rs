@@ -0,0 +1,54 @@
+/*!
+ * Example for using the HC-SR04 ultrasonic distance sensor with the input capture unit.
+ *
+ * Unlike `uno-hc-sr04.rs`, the echo pulse is not timed by polling the pin.  Instead, `TC1`
+ * captures the timestamps of both edges of the pulse in hardware.
+ *
+ * This example prints out the distance reported by the sensor over the serial console.
+ *
+ * Connections
+ * -----------
+ *   - `D2`: HC-SR04 `TRIG`
+ *   - `D8`: HC-SR04 `ECHO` (`ICP1`)
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::input_capture::Edge;
+use arduino_hal::prelude::*;
+use arduino_hal::timer::Prescaler;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut trig = pins.d2.into_output();
+
+    // With prescaling 8, the counter is incremented every 0.5 µs.
+    let mut icp = arduino_hal::InputCapture::new(dp.TC1, pins.d8, Prescaler::Prescale8).unwrap();
+    icp.set_noise_canceler(true);
+
+    loop {
+        // The trigger must be set to high for 10 µs as per the HC-SR04 datasheet
+        trig.set_high();
+        arduino_hal::delay_us(10);
+        trig.set_low();
+
+        match icp.measure_pulse(Edge::Rising, 50.millis()) {
+            Ok(ticks) => {
+                // 1/58 ≈ (34000 cm/s) * 1µs / 2
+                let cm = icp.to_duration(ticks).to_micros() / 58;
+                ufmt::uwriteln!(&mut serial, "Hello, we are {} cms away from target!\r", cm)
+                    .unwrap_infallible();
+            }
+            Err(_) => {
+                ufmt::uwriteln!(&mut serial, "Nothing was detected.\r").unwrap_infallible();
+            }
+        }
+
+        arduino_hal::delay_ms(100);
+    }
+}
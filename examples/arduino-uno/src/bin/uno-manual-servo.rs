@@ -1,9 +1,9 @@
 /*!
  * Sweep a standard SG90 compatible servo from its left limit all the way to its right limit.
  *
- * Because avr-hal does not have a dedicated servo driver library yet, we do this manually using
- * timer TC1.  The servo should be connected to D9 (AND D9 ONLY!  THIS DOES NOT WORK ON OTHER PINS
- * AS IT IS).
+ * This example configures timer TC1 manually to show what happens under the hood; check
+ * `uno-servo.rs` for using the servo driver instead.  The servo should be connected to D9 (AND D9
+ * ONLY!  THIS DOES NOT WORK ON OTHER PINS AS IT IS).
  *
  * As the limits are not precisely defined, we undershoot the datasheets 1ms left limit and
  * overshoot the 2ms right limit by a bit - you can figure out where exactly the limits are for
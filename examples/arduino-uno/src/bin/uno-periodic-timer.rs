@@ -0,0 +1,51 @@
+/*!
+ * Blink an LED from a periodic timer interrupt.
+ *
+ * Uses `arduino_hal::Timer` to configure TC1 in CTC mode for a 4 Hz interrupt.  The prescaler and
+ * compare value are calculated from the core clock automatically.
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::port::mode::Output;
+use arduino_hal::port::Pin;
+use arduino_hal::prelude::*;
+use arduino_hal::timer::Event;
+use core::cell;
+use panic_halt as _;
+
+static LED: avr_device::interrupt::Mutex<cell::RefCell<Option<Pin<Output>>>> =
+    avr_device::interrupt::Mutex::new(cell::RefCell::new(None));
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let led = pins.d13.into_output().downgrade();
+    avr_device::interrupt::free(|cs| LED.borrow(cs).replace(Some(led)));
+
+    let mut timer = arduino_hal::Timer::new(dp.TC1);
+    timer.start(4.Hz()).unwrap();
+    timer.listen(Event::CompareA).unwrap();
+
+    ufmt::uwriteln!(&mut serial, "Timer running with TOP = {}\r", timer.top()).unwrap_infallible();
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    loop {
+        avr_device::asm::sleep()
+    }
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn TIMER1_COMPA() {
+    avr_device::interrupt::free(|cs| {
+        if let Some(led) = LED.borrow(cs).borrow_mut().as_mut() {
+            led.toggle();
+        }
+    })
+}
@@ -0,0 +1,65 @@
+/*!
+ * Sleep in power-down mode and wake up on a button press or every 8 seconds.
+ *
+ * Connect a button between d2 and GND.  The pin change interrupt of d2 (PCINT18) and the watchdog
+ * wake the MCU up, which then prints the reason and goes back to sleep.  The brown-out detector is
+ * turned off while sleeping, if it is enabled in the fuses.
+ *
+ * All peripherals except the USART are powered down in the Power Reduction Register as well, which
+ * saves power while the MCU is awake.
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::hal::wdt;
+use arduino_hal::power::{self, SleepMode};
+use arduino_hal::prelude::*;
+use core::sync::atomic::{AtomicBool, Ordering};
+use panic_halt as _;
+
+/// PCINT number of d2 (PD2).
+const BUTTON_PCINT: u8 = 18;
+
+static BUTTON: AtomicBool = AtomicBool::new(false);
+
+#[avr_device::interrupt(atmega328p)]
+fn PCINT2() {
+    BUTTON.store(true, Ordering::SeqCst);
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn WDT() {}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let mut cpu = dp.CPU;
+    // The USART is powered up again when it is constructed.
+    power::power_down_unused(&mut cpu);
+
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut exint = dp.EXINT;
+    let _button = pins.d2.into_pull_up_input();
+    power::wake_on_pin_change(&mut exint, BUTTON_PCINT).unwrap();
+
+    let mut watchdog = wdt::Wdt::new(dp.WDT, &cpu.mcusr());
+    power::wake_on_watchdog(&mut watchdog, wdt::Timeout::Ms8000).unwrap();
+
+    loop {
+        ufmt::uwriteln!(&mut serial, "Going to sleep...").unwrap_infallible();
+        serial.flush();
+
+        // SAFETY: Not inside a critical section.
+        unsafe { power::sleep_with_bod_disabled(&mut cpu, SleepMode::PowerDown) };
+
+        if BUTTON.load(Ordering::SeqCst) {
+            BUTTON.store(false, Ordering::SeqCst);
+            ufmt::uwriteln!(&mut serial, "Woken up by the button").unwrap_infallible();
+        } else {
+            ufmt::uwriteln!(&mut serial, "Woken up by the watchdog").unwrap_infallible();
+        }
+    }
+}
@@ -0,0 +1,39 @@
+/*!
+ * Example of using phase-correct PWM on `TC1` to drive pins d9 and d10 with centered pulses.
+ *
+ * In phase-correct mode the counter counts up and down again, so both pulses are centered around
+ * the same point in time instead of starting together.  This halves the PWM frequency, which is
+ * printed over the serial console.
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::prelude::*;
+use arduino_hal::simple_pwm::*;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let timer1 =
+        Timer1Pwm::with_mode(dp.TC1, Prescaler::Prescale64, PwmMode::PhaseCorrect).unwrap();
+
+    // 490 Hz at 16 MHz, like `analogWrite()` on these pins in the Arduino core
+    let frequency = timer1.frequency::<arduino_hal::DefaultClock>();
+    ufmt::uwriteln!(&mut serial, "PWM frequency: {} Hz\r", frequency.raw()).unwrap_infallible();
+
+    let mut d9 = pins.d9.into_output().into_pwm(&timer1);
+    let mut d10 = pins.d10.into_output().into_pwm(&timer1);
+    d9.set_duty(64);
+    d10.set_duty(192);
+    d9.enable();
+    d10.enable();
+
+    // The PWM outputs run in hardware, nothing is left to do.
+    loop {
+        avr_device::asm::sleep()
+    }
+}
@@ -0,0 +1,36 @@
+/*!
+ * Example of using 16-bit PWM on `TC1` to fade a LED on pin d9 with a perceptually smooth
+ * (quadratic) curve.
+ *
+ * The 8-bit PWM timers only have 256 brightness steps, which makes the lowest brightness levels
+ * visibly jumpy.  Here the PWM runs at 1 kHz with a TOP value of 15999 instead.
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::prelude::*;
+use arduino_hal::simple_pwm::*;
+use embedded_hal::pwm::SetDutyCycle;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let pwm = Pwm16::new(dp.TC1, 1.kHz()).unwrap();
+
+    // Digital pin 9 is connected to a LED and a resistor in series
+    let mut led = pins.d9.into_output().into_pwm(&pwm);
+    led.enable();
+
+    let max = led.max_duty_cycle() as u32;
+
+    loop {
+        for step in (0..=1000u32).chain((0..1000).rev()) {
+            let duty = max * step / 1000 * step / 1000;
+            led.set_duty_cycle(duty as u16).unwrap();
+            arduino_hal::delay_ms(1);
+        }
+    }
+}
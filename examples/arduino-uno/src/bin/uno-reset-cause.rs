@@ -0,0 +1,43 @@
+/*!
+ * Report why the board was reset.
+ *
+ * The HAL takes a snapshot of the reset flags at startup, which needs the `reset-cause` feature of
+ * `arduino-hal`:
+ *
+ *     cargo run --bin uno-reset-cause --features reset-cause
+ *
+ * Try power-cycling the board, pressing the reset button, or waiting for the watchdog, which
+ * resets the board after a few seconds.
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::hal::wdt;
+use arduino_hal::prelude::*;
+use arduino_hal::reset::ResetCause;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let cause = ResetCause::read(&dp.CPU);
+    match cause.source() {
+        Some(source) => ufmt::uwriteln!(&mut serial, "Reset by {:?}", source).unwrap_infallible(),
+        None => ufmt::uwriteln!(&mut serial, "Reset cause unknown").unwrap_infallible(),
+    }
+    for source in cause.iter() {
+        ufmt::uwriteln!(&mut serial, "  flag: {:?}", source).unwrap_infallible();
+    }
+
+    let mut watchdog = wdt::Wdt::new(dp.WDT, &dp.CPU.mcusr());
+    watchdog
+        .start(wdt::Timeout::Ms4000, wdt::Mode::Reset)
+        .unwrap();
+
+    loop {
+        arduino_hal::delay_ms(1000);
+    }
+}
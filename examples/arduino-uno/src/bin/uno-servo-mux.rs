@@ -0,0 +1,68 @@
+/*!
+ * Drive four servos on arbitrary pins from the compare interrupt of `TC1`.
+ *
+ * Unlike `uno-servo.rs`, the pulses are generated in software, one servo after the other, so any
+ * output pin can be used.
+ *
+ * Connections
+ * -----------
+ *  - `D2`, `D3`, `D4`, `D5`: PWM signals of the servos
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::servo::ServoMux;
+use core::cell;
+use panic_halt as _;
+
+type Servos = ServoMux<arduino_hal::pac::TC1, 4>;
+
+static SERVOS: avr_device::interrupt::Mutex<cell::RefCell<Option<Servos>>> =
+    avr_device::interrupt::Mutex::new(cell::RefCell::new(None));
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let mut servos = Servos::new(dp.TC1).unwrap();
+    servos.attach(0, pins.d2.into_output().downgrade());
+    servos.attach(1, pins.d3.into_output().downgrade());
+    servos.attach(2, pins.d4.into_output().downgrade());
+    servos.attach(3, pins.d5.into_output().downgrade());
+    avr_device::interrupt::free(|cs| SERVOS.borrow(cs).replace(Some(servos)));
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    loop {
+        for angle in (0..=180).chain((0..180).rev()) {
+            avr_device::interrupt::free(|cs| {
+                if let Some(servos) = SERVOS.borrow(cs).borrow_mut().as_mut() {
+                    // Each servo lags 45° behind the previous one
+                    for channel in 0..4 {
+                        let offset = 45 * channel as u16;
+                        let position = (angle as u16 + offset) % 360;
+                        let position = if position > 180 {
+                            360 - position
+                        } else {
+                            position
+                        };
+                        servos.set_angle(channel, position as u8);
+                    }
+                }
+            });
+            arduino_hal::delay_ms(15);
+        }
+    }
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn TIMER1_COMPA() {
+    avr_device::interrupt::free(|cs| {
+        if let Some(servos) = SERVOS.borrow(cs).borrow_mut().as_mut() {
+            servos.on_compare();
+        }
+    })
+}
@@ -0,0 +1,41 @@
+/*!
+ * Sweep two servos in opposite directions using the hardware PWM outputs of `TC1`.
+ *
+ * The servo driver takes care of the timer setup which `uno-manual-servo.rs` does by hand.  The
+ * pulse widths for the end positions differ between servo models, adjust the calibration to
+ * match yours.
+ *
+ * Connections
+ * -----------
+ *  - `D9`: First servo's PWM signal
+ *  - `D10`: Second servo's PWM signal
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::prelude::*;
+use arduino_hal::servo::{Servo, REFRESH_RATE};
+use arduino_hal::simple_pwm::Pwm16;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let pwm = Pwm16::new(dp.TC1, REFRESH_RATE).unwrap();
+
+    let mut left = Servo::new(pins.d9.into_output(), &pwm);
+    let mut right = Servo::new(pins.d10.into_output(), &pwm);
+    left.calibrate(500.micros(), 2400.micros());
+    // Mounted the other way around
+    right.calibrate(2400.micros(), 500.micros());
+
+    loop {
+        for angle in (0..=180).chain((0..180).rev()) {
+            left.set_angle(angle);
+            right.set_angle(angle);
+            arduino_hal::delay_ms(15);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+/*!
+ * Keep track of time with `millis()`/`micros()` while using PWM on the same timer.
+ *
+ * Like the Arduino core, `TC0` drives both the PWM output on d5 and the system time, which ticks
+ * on every timer overflow.  The LED on d5 is faded without any blocking delays and the uptime is
+ * printed once per second.
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::prelude::*;
+use arduino_hal::simple_pwm::*;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let timer0 = Timer0Pwm::new(dp.TC0, Prescaler::Prescale64);
+    let mut pwm_led = pins.d5.into_output().into_pwm(&timer0);
+    pwm_led.enable();
+
+    let systime = arduino_hal::SysTime::with_pwm_timer(timer0).unwrap();
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    let mut next_print = systime.now();
+    loop {
+        let now = systime.now();
+
+        // Triangle wave with a period of 2 seconds
+        let phase = now.ticks() % 2000;
+        let duty = if phase < 1000 { phase } else { 1999 - phase };
+        pwm_led.set_duty((duty * 255 / 999) as u8);
+
+        if now >= next_print {
+            ufmt::uwriteln!(&mut serial, "Uptime: {} us\r", systime.micros()).unwrap_infallible();
+            next_print += 1000.millis();
+        }
+    }
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn TIMER0_OVF() {
+    arduino_hal::time::tick();
+}
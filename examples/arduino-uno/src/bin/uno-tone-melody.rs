@@ -0,0 +1,78 @@
+/*!
+ * Play a melody on a piezo buzzer in the background while blinking the built-in LED.
+ *
+ * `TC2` toggles pin d11 (`OC2A`) in hardware.  The interrupt handler only advances the melody, so
+ * the main loop is free to do other work.  Any other pin works as well with `Tone::with_pin()`,
+ * then the interrupt handler also toggles the pin.
+ *
+ * Connections
+ * -----------
+ *  - `D11`: Piezo buzzer (other leg to GND)
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::tone::{Note, Tone};
+use core::cell;
+use panic_halt as _;
+
+type Buzzer = Tone<arduino_hal::pac::TC2, arduino_hal::hal::port::PB3>;
+
+static BUZZER: avr_device::interrupt::Mutex<cell::RefCell<Option<Buzzer>>> =
+    avr_device::interrupt::Mutex::new(cell::RefCell::new(None));
+
+// "Twinkle, Twinkle, Little Star"
+const MELODY: &[Note] = &[
+    Note::new(262, 400),
+    Note::rest(50),
+    Note::new(262, 400),
+    Note::rest(50),
+    Note::new(392, 400),
+    Note::rest(50),
+    Note::new(392, 400),
+    Note::rest(50),
+    Note::new(440, 400),
+    Note::rest(50),
+    Note::new(440, 400),
+    Note::rest(50),
+    Note::new(392, 800),
+    Note::rest(500),
+];
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let mut led = pins.d13.into_output();
+
+    let mut buzzer = Buzzer::new(dp.TC2, pins.d11.into_output());
+    buzzer.play_melody(MELODY).unwrap();
+    avr_device::interrupt::free(|cs| BUZZER.borrow(cs).replace(Some(buzzer)));
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    loop {
+        led.toggle();
+        arduino_hal::delay_ms(100);
+
+        avr_device::interrupt::free(|cs| {
+            if let Some(buzzer) = BUZZER.borrow(cs).borrow_mut().as_mut() {
+                if !buzzer.is_playing() {
+                    buzzer.play_melody(MELODY).unwrap();
+                }
+            }
+        });
+    }
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn TIMER2_COMPA() {
+    avr_device::interrupt::free(|cs| {
+        if let Some(buzzer) = BUZZER.borrow(cs).borrow_mut().as_mut() {
+            buzzer.on_compare();
+        }
+    })
+}
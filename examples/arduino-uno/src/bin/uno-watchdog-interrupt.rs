@@ -0,0 +1,76 @@
+/*!
+ * Log the reason for a watchdog reset before it happens.
+ *
+ * The watchdog first runs its interrupt handler when it times out and only resets the device on
+ * the next timeout.  The handler uses this time to store the step the firmware was stuck in in the
+ * EEPROM, which is printed after the reset.
+ *
+ * The firmware pretends to lock up in step 5.
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::hal::wdt;
+use arduino_hal::prelude::*;
+use core::cell::{Cell, RefCell};
+use panic_halt as _;
+
+/// EEPROM offset of the logged step.
+const CRASH_LOG_OFFSET: u16 = 0;
+
+static STEP: avr_device::interrupt::Mutex<Cell<u8>> =
+    avr_device::interrupt::Mutex::new(Cell::new(0));
+static EEPROM: avr_device::interrupt::Mutex<RefCell<Option<arduino_hal::Eeprom>>> =
+    avr_device::interrupt::Mutex::new(RefCell::new(None));
+
+#[avr_device::interrupt(atmega328p)]
+fn WDT() {
+    avr_device::interrupt::free(|cs| {
+        let step = STEP.borrow(cs).get();
+        if let Some(eeprom) = EEPROM.borrow(cs).borrow_mut().as_mut() {
+            eeprom.write_byte(CRASH_LOG_OFFSET, step);
+        }
+    })
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut eeprom = arduino_hal::Eeprom::new(dp.EEPROM);
+    let logged = eeprom.read_byte(CRASH_LOG_OFFSET);
+    if logged != 0xff {
+        ufmt::uwriteln!(&mut serial, "Watchdog reset in step {}", logged).unwrap_infallible();
+        eeprom.erase_byte(CRASH_LOG_OFFSET);
+    }
+    avr_device::interrupt::free(|cs| EEPROM.borrow(cs).replace(Some(eeprom)));
+
+    let mut watchdog = wdt::Wdt::new(dp.WDT, &dp.CPU.mcusr());
+    watchdog
+        .start(wdt::Timeout::Ms1000, wdt::Mode::InterruptThenReset)
+        .unwrap();
+
+    // SAFETY: Not inside a critical section and any non-atomic operations have been completed by
+    // this point.
+    unsafe { avr_device::interrupt::enable() };
+
+    for step in 0.. {
+        avr_device::interrupt::free(|cs| STEP.borrow(cs).set(step));
+        ufmt::uwriteln!(&mut serial, "Step {}", step).unwrap_infallible();
+
+        if step == 5 {
+            // Stuck waiting for something which never happens.
+            loop {
+                arduino_hal::delay_ms(100);
+            }
+        }
+
+        arduino_hal::delay_ms(500);
+        watchdog.feed();
+    }
+
+    unreachable!()
+}
@@ -25,7 +25,9 @@ fn main() -> ! {
     }
 
     let mut watchdog = wdt::Wdt::new(dp.WDT, &dp.CPU.mcusr());
-    watchdog.start(wdt::Timeout::Ms2000).unwrap();
+    watchdog
+        .start(wdt::Timeout::Ms2000, wdt::Mode::Reset)
+        .unwrap();
 
     loop {
         led.toggle();
@@ -0,0 +1,52 @@
+/*!
+ * Calibrate the internal oscillator against sync bytes from the host and keep the result.
+ *
+ * Connect the TX pin of a USB-serial adapter to d2 and let it send a continuous stream of `U` at
+ * 9600 baud, for example with
+ *
+ *     stty -F /dev/ttyUSB0 9600 raw && yes U | tr -d '\n' > /dev/ttyUSB0
+ *
+ * The calibration value is stored in the EEPROM, so later boots only restore it and do not need
+ * the host anymore.  The LED blinks slowly if the oscillator is calibrated and fast if the
+ * calibration failed.
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::osccal;
+use panic_halt as _;
+
+/// EEPROM offset of the calibration value (two bytes).
+const CALIBRATION_OFFSET: u16 = 0;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    // Digital pin 1 is also connected to an onboard LED marked "L"
+    let mut led = pins.d1.into_output();
+
+    let mut cpu = dp.CPU;
+    let mut eeprom = arduino_hal::Eeprom::new(dp.EEPROM);
+
+    let calibrated = osccal::restore_calibration(&mut cpu, &eeprom, CALIBRATION_OFFSET) || {
+        let mut timer = arduino_hal::Timer::new(dp.TC0);
+        let rx = pins.d2.into_floating_input();
+        let mut reference = osccal::UartReference::new(&mut timer, &rx, 9600);
+
+        match osccal::calibrate(&mut cpu, &mut reference) {
+            Ok(value) => {
+                osccal::store_calibration(&mut eeprom, CALIBRATION_OFFSET, value).unwrap();
+                true
+            }
+            Err(_) => false,
+        }
+    };
+
+    let period = if calibrated { 500 } else { 100 };
+    loop {
+        led.toggle();
+        arduino_hal::delay_ms(period);
+    }
+}
@@ -0,0 +1,35 @@
+/*!
+ * Example of using the 64 MHz PLL clock of `TC1` for high-speed PWM.
+ *
+ * A 250 kHz carrier on d4 is modulated with a slow triangle wave.  Behind an RC low-pass filter
+ * (or a speaker, like a class-D amplifier) only the modulation remains.
+ *
+ * Connections
+ * -----------
+ *  - `D4`: PWM output
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::hal::prelude::*;
+use arduino_hal::simple_pwm::*;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let timer1 = Timer1PllPwm::new(dp.TC1, &dp.CPU, 250.kHz()).unwrap();
+
+    let mut d4 = pins.d4.into_output().into_pwm(&timer1);
+    d4.enable();
+
+    let max = d4.get_max_duty();
+    loop {
+        for x in (0..=max).chain((0..max).rev()) {
+            d4.set_duty(x);
+            arduino_hal::delay_us(100);
+        }
+    }
+}
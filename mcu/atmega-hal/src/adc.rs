@@ -25,7 +25,10 @@
 //! ```
 
 use crate::port;
-pub use avr_hal_generic::adc::{AdcChannel, AdcOps, ClockDivider};
+pub use avr_hal_generic::adc::{
+    AdcChannel, AdcOps, AdcPinOps, AdcSleepOps, AdcSupplyOps, AdcTemperatureOps, AdcTimerOps,
+    Calibration, ClockDivider, Differential, TriggerSource, VoltageInput,
+};
 
 /// Select the voltage reference for the ADC peripheral
 ///
@@ -39,7 +42,18 @@ pub enum ReferenceVoltage {
     /// Default reference voltage (default).
     AVcc,
     /// Internal reference voltage.
+    ///
+    /// This is 2.56V on the ATmega8, ATmega16, ATmega32A, ATmega128A, ATmega164PA, ATmega1284P,
+    /// ATmega32U4, ATmega1280 and ATmega2560, and 1.1V on all others.
     Internal,
+    /// Internal 1.1V reference voltage.
+    #[cfg(any(
+        feature = "atmega164pa",
+        feature = "atmega1284p",
+        feature = "atmega1280",
+        feature = "atmega2560"
+    ))]
+    Internal1_1,
 }
 
 impl Default for ReferenceVoltage {
@@ -68,19 +82,226 @@ fn apply_settings(peripheral: &crate::pac::ADC, settings: AdcSettings) {
             ClockDivider::Factor128 => w.adps().prescaler_128(),
         }
     });
-    peripheral.admux().write(|w| match settings.ref_voltage {
+    peripheral.admux().reset();
+    set_reference(peripheral, settings.ref_voltage);
+}
+
+fn reference(peripheral: &crate::pac::ADC) -> ReferenceVoltage {
+    match peripheral.admux().read().refs().bits() {
+        0 => ReferenceVoltage::Aref,
+        1 => ReferenceVoltage::AVcc,
+        #[cfg(any(
+            feature = "atmega164pa",
+            feature = "atmega1284p",
+            feature = "atmega1280",
+            feature = "atmega2560"
+        ))]
+        2 => ReferenceVoltage::Internal1_1,
+        _ => ReferenceVoltage::Internal,
+    }
+}
+
+fn set_reference(peripheral: &crate::pac::ADC, reference: ReferenceVoltage) {
+    peripheral.admux().modify(|_, w| match reference {
         ReferenceVoltage::Aref => w.refs().aref(),
         ReferenceVoltage::AVcc => w.refs().avcc(),
         ReferenceVoltage::Internal => w.refs().internal(),
+        #[cfg(any(
+            feature = "atmega164pa",
+            feature = "atmega1284p",
+            feature = "atmega1280",
+            feature = "atmega2560"
+        ))]
+        // SAFETY: REFS = 0b10 selects the internal 1.1V reference on these chips.
+        ReferenceVoltage::Internal1_1 => unsafe { w.refs().bits(0b10) },
+    });
+}
+
+fn set_left_adjust(peripheral: &crate::pac::ADC, enable: bool) {
+    peripheral.admux().modify(|_, w| w.adlar().bit(enable));
+}
+
+/// Event which starts a conversion in auto-trigger mode.
+///
+/// Check the [`avr_hal_generic::adc::Sampler`] documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AutoTrigger {
+    /// Start the next conversion as soon as the previous one completes.
+    FreeRunning = 0,
+    #[cfg(not(any(feature = "atmega8", feature = "atmega128a")))]
+    AnalogComparator = 1,
+    #[cfg(not(any(feature = "atmega8", feature = "atmega128a")))]
+    ExternalInterrupt0 = 2,
+    #[cfg(not(any(feature = "atmega8", feature = "atmega128a")))]
+    Timer0CompareA = 3,
+    #[cfg(not(any(feature = "atmega8", feature = "atmega128a")))]
+    Timer0Overflow = 4,
+    #[cfg(not(any(feature = "atmega8", feature = "atmega128a")))]
+    Timer1CompareB = 5,
+    #[cfg(not(any(feature = "atmega8", feature = "atmega128a")))]
+    Timer1Overflow = 6,
+    #[cfg(not(any(feature = "atmega8", feature = "atmega128a")))]
+    Timer1Capture = 7,
+    #[cfg(feature = "atmega32u4")]
+    Timer4Overflow = 8,
+    #[cfg(feature = "atmega32u4")]
+    Timer4CompareA = 9,
+    #[cfg(feature = "atmega32u4")]
+    Timer4CompareB = 10,
+    #[cfg(feature = "atmega32u4")]
+    Timer4CompareD = 11,
+}
+
+/// Set ADATE after the trigger source was selected.
+#[cfg(not(any(feature = "atmega8", feature = "atmega128a")))]
+fn set_auto_trigger(peripheral: &crate::pac::ADC, trigger: Option<AutoTrigger>) {
+    peripheral.adcsra().modify(|_, w| {
+        w.adate().bit(trigger.is_some());
+        // Free-running mode needs a first conversion to get going.
+        w.adsc().bit(trigger == Some(AutoTrigger::FreeRunning))
     });
 }
 
+/// Clear the interrupt flag of `trigger`, so its next event starts a conversion again.
+///
+/// This is not needed when the interrupt of the trigger source is enabled, because the hardware
+/// clears the flag when the interrupt handler runs.
+#[cfg(not(any(feature = "atmega8", feature = "atmega128a")))]
+fn clear_trigger_flag(trigger: AutoTrigger) {
+    // SAFETY: The flags are cleared by writing a one, writing zeros to the other flags has no
+    // effect.  `ACSR` is modified inside a critical section.
+    match trigger {
+        AutoTrigger::FreeRunning => (),
+        AutoTrigger::AnalogComparator => avr_hal_generic::avr_device::interrupt::free(|_| {
+            let ac = unsafe { &*crate::pac::AC::ptr() };
+            ac.acsr().modify(|_, w| w.aci().set_bit());
+        }),
+        #[cfg(not(any(feature = "atmega16", feature = "atmega32a", feature = "atmega1284p")))]
+        AutoTrigger::ExternalInterrupt0 => {
+            unsafe { &*crate::pac::EXINT::ptr() }
+                .eifr()
+                .write(|w| w.intf().set(0b1));
+        }
+        #[cfg(feature = "atmega1284p")]
+        AutoTrigger::ExternalInterrupt0 => {
+            unsafe { &*crate::pac::EXINT::ptr() }
+                .eifr()
+                .write(|w| w.intf0().set_bit());
+        }
+        #[cfg(any(feature = "atmega16", feature = "atmega32a"))]
+        AutoTrigger::ExternalInterrupt0 => {
+            unsafe { &*crate::pac::EXINT::ptr() }
+                .gifr()
+                .write(|w| w.intf().set(0b01));
+        }
+        #[cfg(not(any(feature = "atmega16", feature = "atmega32a")))]
+        AutoTrigger::Timer0CompareA => {
+            unsafe { &*crate::pac::TC0::ptr() }
+                .tifr0()
+                .write(|w| w.ocf0a().set_bit());
+        }
+        #[cfg(not(any(feature = "atmega16", feature = "atmega32a")))]
+        AutoTrigger::Timer0Overflow => {
+            unsafe { &*crate::pac::TC0::ptr() }
+                .tifr0()
+                .write(|w| w.tov0().set_bit());
+        }
+        #[cfg(not(any(feature = "atmega16", feature = "atmega32a")))]
+        AutoTrigger::Timer1CompareB => {
+            unsafe { &*crate::pac::TC1::ptr() }
+                .tifr1()
+                .write(|w| w.ocf1b().set_bit());
+        }
+        #[cfg(not(any(feature = "atmega16", feature = "atmega32a")))]
+        AutoTrigger::Timer1Overflow => {
+            unsafe { &*crate::pac::TC1::ptr() }
+                .tifr1()
+                .write(|w| w.tov1().set_bit());
+        }
+        #[cfg(not(any(feature = "atmega16", feature = "atmega32a")))]
+        AutoTrigger::Timer1Capture => {
+            unsafe { &*crate::pac::TC1::ptr() }
+                .tifr1()
+                .write(|w| w.icf1().set_bit());
+        }
+        #[cfg(any(feature = "atmega16", feature = "atmega32a"))]
+        AutoTrigger::Timer0CompareA => {
+            unsafe { &*crate::pac::TC0::ptr() }
+                .tifr()
+                .write(|w| w.ocf0().set_bit());
+        }
+        #[cfg(any(feature = "atmega16", feature = "atmega32a"))]
+        AutoTrigger::Timer0Overflow => {
+            unsafe { &*crate::pac::TC0::ptr() }
+                .tifr()
+                .write(|w| w.tov0().set_bit());
+        }
+        #[cfg(any(feature = "atmega16", feature = "atmega32a"))]
+        AutoTrigger::Timer1CompareB => {
+            unsafe { &*crate::pac::TC1::ptr() }
+                .tifr()
+                .write(|w| w.ocf1b().set_bit());
+        }
+        #[cfg(any(feature = "atmega16", feature = "atmega32a"))]
+        AutoTrigger::Timer1Overflow => {
+            unsafe { &*crate::pac::TC1::ptr() }
+                .tifr()
+                .write(|w| w.tov1().set_bit());
+        }
+        #[cfg(any(feature = "atmega16", feature = "atmega32a"))]
+        AutoTrigger::Timer1Capture => {
+            unsafe { &*crate::pac::TC1::ptr() }
+                .tifr()
+                .write(|w| w.icf1().set_bit());
+        }
+        #[cfg(feature = "atmega32u4")]
+        AutoTrigger::Timer4Overflow => {
+            unsafe { &*crate::pac::TC4::ptr() }
+                .tifr4()
+                .write(|w| w.tov4().set_bit());
+        }
+        #[cfg(feature = "atmega32u4")]
+        AutoTrigger::Timer4CompareA => {
+            unsafe { &*crate::pac::TC4::ptr() }
+                .tifr4()
+                .write(|w| w.ocf4a().set_bit());
+        }
+        #[cfg(feature = "atmega32u4")]
+        AutoTrigger::Timer4CompareB => {
+            unsafe { &*crate::pac::TC4::ptr() }
+                .tifr4()
+                .write(|w| w.ocf4b().set_bit());
+        }
+        #[cfg(feature = "atmega32u4")]
+        AutoTrigger::Timer4CompareD => {
+            unsafe { &*crate::pac::TC4::ptr() }
+                .tifr4()
+                .write(|w| w.ocf4d().set_bit());
+        }
+    }
+}
+
 /// Check the [`avr_hal_generic::adc::Adc`] documentation.
 pub type Adc<CLOCK> = avr_hal_generic::adc::Adc<crate::Atmega, crate::pac::ADC, CLOCK>;
 
 /// Check the [`avr_hal_generic::adc::Channel`] documentation.
 pub type Channel = avr_hal_generic::adc::Channel<crate::Atmega, crate::pac::ADC>;
 
+/// Check the [`avr_hal_generic::adc::Sampler`] documentation.
+pub type Sampler<'a, CLOCK, TRIG> =
+    avr_hal_generic::adc::Sampler<'a, crate::Atmega, crate::pac::ADC, CLOCK, TRIG>;
+
+/// Check the [`avr_hal_generic::adc::TimerTrigger`] documentation.
+pub type TimerTrigger<TC, CLOCK> = avr_hal_generic::adc::TimerTrigger<crate::Atmega, TC, CLOCK>;
+
+/// Check the [`avr_hal_generic::adc::SharedAdc`] documentation.
+pub type SharedAdc<CLOCK> = avr_hal_generic::adc::SharedAdc<crate::Atmega, crate::pac::ADC, CLOCK>;
+
+/// Check the [`avr_hal_generic::adc::AnalogInput`] documentation.
+pub type AnalogInput<'a, CLOCK, PIN> =
+    avr_hal_generic::adc::AnalogInput<'a, crate::Atmega, crate::pac::ADC, CLOCK, PIN>;
+
 /// Additional channels
 ///
 /// Some channels are not directly connected to pins.  This module provides types which can be used
@@ -181,10 +402,22 @@ avr_hal_generic::impl_adc! {
     peripheral: crate::pac::ADC,
     settings: AdcSettings,
     apply_settings: |peripheral, settings| { apply_settings(peripheral, settings) },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| { reference(peripheral) },
+    set_reference: |peripheral, reference| { set_reference(peripheral, reference) },
+    set_left_adjust: |peripheral, enable| { set_left_adjust(peripheral, enable) },
     channel_id: crate::pac::adc::admux::MUX_A,
     set_channel: |peripheral, id| {
         peripheral.admux().modify(|_, w| w.mux().variant(id));
     },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        if let Some(trigger) = trigger {
+            peripheral.adcsrb().modify(|_, w| w.adts().set(trigger as u8));
+        }
+        set_auto_trigger(peripheral, trigger);
+    },
+    clear_trigger: |trigger| { clear_trigger_flag(trigger) },
     pins: {
         port::PC0: (crate::pac::adc::admux::MUX_A::ADC0, didr0::adc0d),
         port::PC1: (crate::pac::adc::admux::MUX_A::ADC1, didr0::adc1d),
@@ -211,10 +444,22 @@ avr_hal_generic::impl_adc! {
     peripheral: crate::pac::ADC,
     settings: AdcSettings,
     apply_settings: |peripheral, settings| { apply_settings(peripheral, settings) },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| { reference(peripheral) },
+    set_reference: |peripheral, reference| { set_reference(peripheral, reference) },
+    set_left_adjust: |peripheral, enable| { set_left_adjust(peripheral, enable) },
     channel_id: crate::pac::adc::admux::MUX_A,
     set_channel: |peripheral, id| {
         peripheral.admux().modify(|_, w| w.mux().variant(id));
     },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        if let Some(trigger) = trigger {
+            peripheral.sfior().modify(|_, w| w.adts().set(trigger as u8));
+        }
+        set_auto_trigger(peripheral, trigger);
+    },
+    clear_trigger: |trigger| { clear_trigger_flag(trigger) },
     pins: {
         port::PA0: (crate::pac::adc::admux::MUX_A::ADC0),
         port::PA1: (crate::pac::adc::admux::MUX_A::ADC1),
@@ -237,11 +482,25 @@ avr_hal_generic::impl_adc! {
     peripheral: crate::pac::ADC,
     settings: AdcSettings,
     apply_settings: |peripheral, settings| { apply_settings(peripheral, settings) },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| { reference(peripheral) },
+    set_reference: |peripheral, reference| { set_reference(peripheral, reference) },
+    set_left_adjust: |peripheral, enable| { set_left_adjust(peripheral, enable) },
     channel_id: u8,
     set_channel: |peripheral, id| {
         peripheral.admux().modify(|_, w| w.mux().set(id & 0x1f));
         peripheral.adcsrb().modify(|_, w| w.mux5().bit(id & 0x20 != 0));
     },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        if let Some(trigger) = trigger {
+            // SAFETY: The PAC does not know the Timer4 trigger sources, which are valid ADTS
+            // values on this chip.
+            peripheral.adcsrb().modify(|_, w| unsafe { w.adts().bits(trigger as u8) });
+        }
+        set_auto_trigger(peripheral, trigger);
+    },
+    clear_trigger: |trigger| { clear_trigger_flag(trigger) },
     pins: {
         port::PF0: (0b000000, didr0::adc0d),
         port::PF1: (0b000001, didr0::adc1d),
@@ -261,6 +520,40 @@ avr_hal_generic::impl_adc! {
         channel::Gnd: 0b011111,
         channel::Temperature: 0b100111,
     },
+    differential: {
+        port::PF1, port::PF0, 10: 0b001001,
+        port::PF1, port::PF0, 40: 0b100110,
+        port::PF1, port::PF0, 200: 0b001011,
+        port::PF0, port::PF1, 1: 0b010000,
+        port::PF4, port::PF1, 1: 0b010100,
+        port::PF5, port::PF1, 1: 0b010101,
+        port::PF6, port::PF1, 1: 0b010110,
+        port::PF7, port::PF1, 1: 0b010111,
+        port::PF4, port::PF0, 10: 0b101000,
+        port::PF5, port::PF0, 10: 0b101001,
+        port::PF6, port::PF0, 10: 0b101010,
+        port::PF7, port::PF0, 10: 0b101011,
+        port::PF4, port::PF1, 10: 0b101100,
+        port::PF5, port::PF1, 10: 0b101101,
+        port::PF6, port::PF1, 10: 0b101110,
+        port::PF7, port::PF1, 10: 0b101111,
+        port::PF4, port::PF0, 40: 0b110000,
+        port::PF5, port::PF0, 40: 0b110001,
+        port::PF6, port::PF0, 40: 0b110010,
+        port::PF7, port::PF0, 40: 0b110011,
+        port::PF4, port::PF1, 40: 0b110100,
+        port::PF5, port::PF1, 40: 0b110101,
+        port::PF6, port::PF1, 40: 0b110110,
+        port::PF7, port::PF1, 40: 0b110111,
+        port::PF4, port::PF0, 200: 0b111000,
+        port::PF5, port::PF0, 200: 0b111001,
+        port::PF6, port::PF0, 200: 0b111010,
+        port::PF7, port::PF0, 200: 0b111011,
+        port::PF4, port::PF1, 200: 0b111100,
+        port::PF5, port::PF1, 200: 0b111101,
+        port::PF6, port::PF1, 200: 0b111110,
+        port::PF7, port::PF1, 200: 0b111111,
+    },
 }
 
 #[cfg(feature = "atmega128a")]
@@ -269,10 +562,23 @@ avr_hal_generic::impl_adc! {
     peripheral: crate::pac::ADC,
     settings: AdcSettings,
     apply_settings: |peripheral, settings| { apply_settings(peripheral, settings) },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| { reference(peripheral) },
+    set_reference: |peripheral, reference| { set_reference(peripheral, reference) },
+    set_left_adjust: |peripheral, enable| { set_left_adjust(peripheral, enable) },
     channel_id: crate::pac::adc::admux::MUX_A,
     set_channel: |peripheral, id| {
         peripheral.admux().modify(|_, w| w.mux().variant(id));
     },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        // Only free-running mode is available.
+        peripheral.adcsra().modify(|_, w| {
+            w.adfr().bit(trigger.is_some());
+            w.adsc().bit(trigger.is_some())
+        });
+    },
+    clear_trigger: |_trigger| {},
     pins: {
         port::PF0: (crate::pac::adc::admux::MUX_A::ADC0),
         port::PF1: (crate::pac::adc::admux::MUX_A::ADC1),
@@ -295,11 +601,23 @@ avr_hal_generic::impl_adc! {
     peripheral: crate::pac::ADC,
     settings: AdcSettings,
     apply_settings: |peripheral, settings| { apply_settings(peripheral, settings) },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| { reference(peripheral) },
+    set_reference: |peripheral, reference| { set_reference(peripheral, reference) },
+    set_left_adjust: |peripheral, enable| { set_left_adjust(peripheral, enable) },
     channel_id: u8,
     set_channel: |peripheral, id| {
         peripheral.admux().modify(|_, w| w.mux().set(id & 0x1f));
         peripheral.adcsrb().modify(|_, w| w.mux5().bit(id & 0x20 != 0));
     },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        if let Some(trigger) = trigger {
+            peripheral.adcsrb().modify(|_, w| w.adts().set(trigger as u8));
+        }
+        set_auto_trigger(peripheral, trigger);
+    },
+    clear_trigger: |trigger| { clear_trigger_flag(trigger) },
     pins: {
         port::PF0: (0b000000, didr0::adc0d),
         port::PF1: (0b000001, didr0::adc1d),
@@ -322,6 +640,40 @@ avr_hal_generic::impl_adc! {
         channel::Vbg: 0b011110,
         channel::Gnd: 0b011111,
     },
+    differential: {
+        port::PF1, port::PF0, 10: 0b001001,
+        port::PF1, port::PF0, 200: 0b001011,
+        port::PF3, port::PF2, 10: 0b001101,
+        port::PF3, port::PF2, 200: 0b001111,
+        port::PF0, port::PF1, 1: 0b010000,
+        port::PF2, port::PF1, 1: 0b010010,
+        port::PF3, port::PF1, 1: 0b010011,
+        port::PF4, port::PF1, 1: 0b010100,
+        port::PF5, port::PF1, 1: 0b010101,
+        port::PF6, port::PF1, 1: 0b010110,
+        port::PF7, port::PF1, 1: 0b010111,
+        port::PF0, port::PF2, 1: 0b011000,
+        port::PF1, port::PF2, 1: 0b011001,
+        port::PF3, port::PF2, 1: 0b011011,
+        port::PF4, port::PF2, 1: 0b011100,
+        port::PF5, port::PF2, 1: 0b011101,
+        port::PK1, port::PK0, 10: 0b101001,
+        port::PK1, port::PK0, 200: 0b101011,
+        port::PK3, port::PK2, 10: 0b101101,
+        port::PK3, port::PK2, 200: 0b101111,
+        port::PK0, port::PK1, 1: 0b110000,
+        port::PK2, port::PK1, 1: 0b110010,
+        port::PK3, port::PK1, 1: 0b110011,
+        port::PK4, port::PK1, 1: 0b110100,
+        port::PK5, port::PK1, 1: 0b110101,
+        port::PK6, port::PK1, 1: 0b110110,
+        port::PK7, port::PK1, 1: 0b110111,
+        port::PK0, port::PK2, 1: 0b111000,
+        port::PK1, port::PK2, 1: 0b111001,
+        port::PK3, port::PK2, 1: 0b111011,
+        port::PK4, port::PK2, 1: 0b111100,
+        port::PK5, port::PK2, 1: 0b111101,
+    },
 }
 
 #[cfg(any(feature = "atmega1284p"))]
@@ -330,10 +682,22 @@ avr_hal_generic::impl_adc! {
     peripheral: crate::pac::ADC,
     settings: AdcSettings,
     apply_settings: |peripheral, settings| { apply_settings(peripheral, settings) },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| { reference(peripheral) },
+    set_reference: |peripheral, reference| { set_reference(peripheral, reference) },
+    set_left_adjust: |peripheral, enable| { set_left_adjust(peripheral, enable) },
     channel_id: crate::pac::adc::admux::MUX_A,
     set_channel: |peripheral, id| {
         peripheral.admux().modify(|_, w| w.mux().variant(id));
     },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        if let Some(trigger) = trigger {
+            peripheral.adcsrb().modify(|_, w| w.adts().set(trigger as u8));
+        }
+        set_auto_trigger(peripheral, trigger);
+    },
+    clear_trigger: |trigger| { clear_trigger_flag(trigger) },
     pins: {
         port::PA0: (crate::pac::adc::admux::MUX_A::ADC0, didr0::adc0d),
         port::PA1: (crate::pac::adc::admux::MUX_A::ADC1, didr0::adc1d),
@@ -358,10 +722,23 @@ avr_hal_generic::impl_adc! {
     peripheral: crate::pac::ADC,
     settings: AdcSettings,
     apply_settings: |peripheral, settings| { apply_settings(peripheral, settings) },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| { reference(peripheral) },
+    set_reference: |peripheral, reference| { set_reference(peripheral, reference) },
+    set_left_adjust: |peripheral, enable| { set_left_adjust(peripheral, enable) },
     channel_id: crate::pac::adc::admux::MUX_A,
     set_channel: |peripheral, id| {
         peripheral.admux().modify(|_, w| w.mux().variant(id));
     },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        // Only free-running mode is available.
+        peripheral.adcsra().modify(|_, w| {
+            w.adfr().bit(trigger.is_some());
+            w.adsc().bit(trigger.is_some())
+        });
+    },
+    clear_trigger: |_trigger| {},
     pins: {
         port::PC0: (crate::pac::adc::admux::MUX_A::ADC0),
         port::PC1: (crate::pac::adc::admux::MUX_A::ADC1),
@@ -386,10 +763,22 @@ avr_hal_generic::impl_adc! {
     peripheral: crate::pac::ADC,
     settings: AdcSettings,
     apply_settings: |peripheral, settings| { apply_settings(peripheral, settings) },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| { reference(peripheral) },
+    set_reference: |peripheral, reference| { set_reference(peripheral, reference) },
+    set_left_adjust: |peripheral, enable| { set_left_adjust(peripheral, enable) },
     channel_id: crate::pac::adc::admux::MUX_A,
     set_channel: |peripheral, id| {
         peripheral.admux().modify(|_, w| w.mux().variant(id));
     },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        if let Some(trigger) = trigger {
+            peripheral.adcsrb().modify(|_, w| w.adts().set(trigger as u8));
+        }
+        set_auto_trigger(peripheral, trigger);
+    },
+    clear_trigger: |trigger| { clear_trigger_flag(trigger) },
     pins: {
         port::PA0: (crate::pac::adc::admux::MUX_A::ADC0, didr0::adc0d),
         port::PA1: (crate::pac::adc::admux::MUX_A::ADC1, didr0::adc1d),
@@ -405,3 +794,69 @@ avr_hal_generic::impl_adc! {
         channel::Gnd: crate::pac::adc::admux::MUX_A::ADC_GND,
     },
 }
+
+#[cfg(not(any(feature = "atmega8", feature = "atmega128a")))]
+impl AdcTimerOps<crate::Atmega, crate::pac::ADC> for crate::pac::TC0 {
+    const TRIGGER: AutoTrigger = AutoTrigger::Timer0CompareA;
+    const COMPARE: avr_hal_generic::timer::OutputCompare = avr_hal_generic::timer::OutputCompare::A;
+}
+
+#[cfg(not(any(feature = "atmega8", feature = "atmega128a")))]
+impl AdcTimerOps<crate::Atmega, crate::pac::ADC> for crate::pac::TC1 {
+    const TRIGGER: AutoTrigger = AutoTrigger::Timer1CompareB;
+    const COMPARE: avr_hal_generic::timer::OutputCompare = avr_hal_generic::timer::OutputCompare::B;
+}
+
+impl AdcSupplyOps<crate::Atmega> for crate::pac::ADC {
+    const SUPPLY_REFERENCE: ReferenceVoltage = ReferenceVoltage::AVcc;
+    #[cfg(not(any(feature = "atmega32u4", feature = "atmega2560", feature = "atmega1280")))]
+    const BANDGAP: Self::Channel = crate::pac::adc::admux::MUX_A::ADC_VBG;
+    #[cfg(any(feature = "atmega32u4", feature = "atmega2560", feature = "atmega1280"))]
+    const BANDGAP: Self::Channel = 0b011110;
+
+    #[cfg(not(any(
+        feature = "atmega8",
+        feature = "atmega16",
+        feature = "atmega32a",
+        feature = "atmega128a"
+    )))]
+    const CALIBRATION: Calibration = Calibration {
+        bandgap_millivolts: 1100,
+        #[cfg(not(feature = "atmega32u4"))]
+        temperature_offset: 292,
+        #[cfg(not(feature = "atmega32u4"))]
+        temperature_slope: 931,
+        #[cfg(feature = "atmega32u4")]
+        temperature_offset: 352,
+        #[cfg(feature = "atmega32u4")]
+        temperature_slope: 1300,
+    };
+    #[cfg(feature = "atmega8")]
+    const CALIBRATION: Calibration = Calibration {
+        bandgap_millivolts: 1300,
+        temperature_offset: 0,
+        temperature_slope: 0,
+    };
+    #[cfg(any(feature = "atmega16", feature = "atmega32a", feature = "atmega128a"))]
+    const CALIBRATION: Calibration = Calibration {
+        bandgap_millivolts: 1230,
+        temperature_offset: 0,
+        temperature_slope: 0,
+    };
+}
+
+/// The temperature sensor is measured against the internal 1.1V reference, or the 2.56V reference
+/// on the ATmega32U4.
+#[cfg(any(
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega32u4",
+    feature = "atmega48p"
+))]
+impl AdcTemperatureOps<crate::Atmega> for crate::pac::ADC {
+    const TEMPERATURE_REFERENCE: ReferenceVoltage = ReferenceVoltage::Internal;
+    #[cfg(not(feature = "atmega32u4"))]
+    const TEMPERATURE: Self::Channel = crate::pac::adc::admux::MUX_A::TEMPSENS;
+    #[cfg(feature = "atmega32u4")]
+    const TEMPERATURE: Self::Channel = 0b100111;
+}
@@ -0,0 +1,23 @@
+//! Core clock speed management
+//!
+//! Most items in this module are re-exported from [`avr_hal_generic::clock`].
+pub use avr_hal_generic::clock::*;
+
+/// Check the [`avr_hal_generic::clock::SystemClock`] documentation.
+pub type SystemClock<SOURCE, CLOCK> =
+    avr_hal_generic::clock::SystemClock<crate::Atmega, SOURCE, CLOCK>;
+
+#[cfg(all(
+    feature = "device-selected",
+    not(any(
+        feature = "atmega8",
+        feature = "atmega16",
+        feature = "atmega32a",
+        feature = "atmega128a",
+    ))
+))]
+avr_hal_generic::impl_clock_prescaler! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    register: clkpr,
+}
@@ -0,0 +1,92 @@
+//! Analog Comparator
+//!
+//! # Example
+//!
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//!
+//! let _ain0 = pins.pd6.into_floating_input();
+//! let _ain1 = pins.pd7.into_floating_input();
+//!
+//! let mut comparator = AnalogComparator::new(dp.AC);
+//!
+//! loop {
+//!     nb::block!(comparator.wait_event()).unwrap_infallible();
+//!     // AIN0 crossed AIN1
+//! }
+//! ```
+pub use avr_hal_generic::comparator::{
+    AnalogComparatorBandgapOps, AnalogComparatorCaptureOps, AnalogComparatorMuxOps,
+    AnalogComparatorOps, Event, PositiveInput,
+};
+
+/// Check the [`avr_hal_generic::comparator::AnalogComparator`] documentation.
+pub type AnalogComparator =
+    avr_hal_generic::comparator::AnalogComparator<crate::Atmega, crate::pac::AC>;
+
+/// Check the [`avr_hal_generic::comparator::MuxedAdc`] documentation.
+pub type MuxedAdc<CLOCK> =
+    avr_hal_generic::comparator::MuxedAdc<crate::Atmega, crate::pac::ADC, CLOCK>;
+
+#[cfg(any(
+    feature = "atmega164pa",
+    feature = "atmega1280",
+    feature = "atmega1284p",
+    feature = "atmega2560",
+    feature = "atmega32u4"
+))]
+avr_hal_generic::impl_analog_comparator! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::AC,
+    bandgap: acbg,
+    input_capture: acic,
+    adc_multiplexer: {
+        peripheral: crate::pac::ADC,
+        set_enabled: |ac, adc, enable| {
+            adc.adcsra().modify(|_, w| w.aden().bit(!enable));
+            ac.adcsrb().modify(|_, w| w.acme().bit(enable));
+        },
+    },
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_analog_comparator! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::AC,
+    bandgap: acbg,
+    input_capture: acic,
+    adc_multiplexer: {
+        peripheral: crate::pac::ADC,
+        set_enabled: |_ac, adc, enable| {
+            adc.adcsra().modify(|_, w| w.aden().bit(!enable));
+            adc.adcsrb().modify(|_, w| w.acme().bit(enable));
+        },
+    },
+}
+
+#[cfg(any(
+    feature = "atmega8",
+    feature = "atmega16",
+    feature = "atmega32a",
+    feature = "atmega128a"
+))]
+avr_hal_generic::impl_analog_comparator! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::AC,
+    bandgap: acbg,
+    input_capture: acic,
+    adc_multiplexer: {
+        peripheral: crate::pac::ADC,
+        set_enabled: |ac, adc, enable| {
+            adc.adcsra().modify(|_, w| w.aden().bit(!enable));
+            ac.sfior().modify(|_, w| w.acme().bit(enable));
+        },
+    },
+}
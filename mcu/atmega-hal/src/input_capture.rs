@@ -0,0 +1,306 @@
+//! Input capture unit of 16-bit timers
+//!
+//! Check the [`avr_hal_generic::input_capture`] documentation for details.
+//!
+//! | Timer | ICP pin |
+//! | --- | --- |
+//! | `TC1` | `PB0` (ATmega48P, ATmega88P, ATmega168, ATmega328P, ATmega328PB, ATmega8), `PD6` (ATmega16, ATmega32A, ATmega164PA, ATmega1284P), `PD4` (ATmega32U4, ATmega128A, ATmega1280, ATmega2560) |
+//! | `TC3` | `PE2` (ATmega328PB), `PC7` (ATmega32U4), `PB5` (ATmega1284P), `PE7` (ATmega128A, ATmega1280, ATmega2560) |
+//! | `TC4` | `PE0` (ATmega328PB), `PL0` (ATmega1280, ATmega2560) |
+//! | `TC5` | `PL1` (ATmega1280, ATmega2560) |
+//!
+//! # Example
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//!
+//! let mut icp = InputCapture::<_, _, CoreClock>::new(
+//!     dp.TC1,
+//!     pins.pb0.into_floating_input(),
+//!     Prescaler::Prescale8,
+//! )
+//! .unwrap();
+//!
+//! // Width of the next high pulse, in timer ticks
+//! let ticks = icp.measure_pulse(Edge::Rising, 100.millis()).unwrap();
+//! ```
+
+pub use avr_hal_generic::input_capture::{
+    CaptureError, CaptureEvent, Edge, InputCaptureOps, PwmMeasurement,
+};
+
+/// Check the [`avr_hal_generic::input_capture::InputCapture`] documentation.
+pub type InputCapture<TC, IMODE, CLOCK> =
+    avr_hal_generic::input_capture::InputCapture<crate::Atmega, TC, IMODE, CLOCK>;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PB0,
+    registers: {
+        control: tccr1b,
+        capture: icr1,
+        mask: timsk1,
+        flags: tifr1,
+    },
+    bits: {
+        edge: ices1,
+        noise_canceler: icnc1,
+        interrupt: icie1,
+        flag: icf1,
+    },
+}
+
+#[cfg(feature = "atmega8")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PB0,
+    registers: {
+        control: tccr1b,
+        capture: icr1,
+        mask: timsk,
+        flags: tifr,
+    },
+    bits: {
+        edge: ices1,
+        noise_canceler: icnc1,
+        interrupt: ticie1,
+        flag: icf1,
+    },
+}
+
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p",))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PD6,
+    registers: {
+        control: tccr1b,
+        capture: icr1,
+        mask: timsk1,
+        flags: tifr1,
+    },
+    bits: {
+        edge: ices1,
+        noise_canceler: icnc1,
+        interrupt: icie1,
+        flag: icf1,
+    },
+}
+
+#[cfg(any(feature = "atmega16", feature = "atmega32a",))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PD6,
+    registers: {
+        control: tccr1b,
+        capture: icr1,
+        mask: timsk,
+        flags: tifr,
+    },
+    bits: {
+        edge: ices1,
+        noise_canceler: icnc1,
+        interrupt: ticie1,
+        flag: icf1,
+    },
+}
+
+#[cfg(any(feature = "atmega32u4", feature = "atmega1280", feature = "atmega2560",))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PD4,
+    registers: {
+        control: tccr1b,
+        capture: icr1,
+        mask: timsk1,
+        flags: tifr1,
+    },
+    bits: {
+        edge: ices1,
+        noise_canceler: icnc1,
+        interrupt: icie1,
+        flag: icf1,
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PD4,
+    registers: {
+        control: tccr1b,
+        capture: icr1,
+        mask: timsk,
+        flags: tifr,
+    },
+    bits: {
+        edge: ices1,
+        noise_canceler: icnc1,
+        interrupt: ticie1,
+        flag: icf1,
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: crate::port::PE2,
+    registers: {
+        control: tccr3b,
+        capture: icr3,
+        mask: timsk3,
+        flags: tifr3,
+    },
+    bits: {
+        edge: ices3,
+        noise_canceler: icnc3,
+        interrupt: icie3,
+        flag: icf3,
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: crate::port::PC7,
+    registers: {
+        control: tccr3b,
+        capture: icr3,
+        mask: timsk3,
+        flags: tifr3,
+    },
+    bits: {
+        edge: ices3,
+        noise_canceler: icnc3,
+        interrupt: icie3,
+        flag: icf3,
+    },
+}
+
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: crate::port::PB5,
+    registers: {
+        control: tccr3b,
+        capture: icr3,
+        mask: timsk3,
+        flags: tifr3,
+    },
+    bits: {
+        edge: ices3,
+        noise_canceler: icnc3,
+        interrupt: icie3,
+        flag: icf3,
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560",))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: crate::port::PE7,
+    registers: {
+        control: tccr3b,
+        capture: icr3,
+        mask: timsk3,
+        flags: tifr3,
+    },
+    bits: {
+        edge: ices3,
+        noise_canceler: icnc3,
+        interrupt: icie3,
+        flag: icf3,
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: crate::port::PE7,
+    registers: {
+        control: tccr3b,
+        capture: icr3,
+        mask: etimsk,
+        flags: etifr,
+    },
+    bits: {
+        edge: ices3,
+        noise_canceler: icnc3,
+        interrupt: ticie3,
+        flag: icf3,
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    pin: crate::port::PE0,
+    registers: {
+        control: tccr4b,
+        capture: icr4,
+        mask: timsk4,
+        flags: tifr4,
+    },
+    bits: {
+        edge: ices4,
+        noise_canceler: icnc4,
+        interrupt: icie4,
+        flag: icf4,
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560",))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    pin: crate::port::PL0,
+    registers: {
+        control: tccr4b,
+        capture: icr4,
+        mask: timsk4,
+        flags: tifr4,
+    },
+    bits: {
+        edge: ices4,
+        noise_canceler: icnc4,
+        interrupt: icie4,
+        flag: icf4,
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560",))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC5,
+    pin: crate::port::PL1,
+    registers: {
+        control: tccr5b,
+        capture: icr5,
+        mask: timsk5,
+        flags: tifr5,
+    },
+    bits: {
+        edge: ices5,
+        noise_canceler: icnc5,
+        interrupt: icie5,
+        flag: icf5,
+    },
+}
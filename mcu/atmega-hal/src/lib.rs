@@ -1,4 +1,13 @@
 #![no_std]
+#![cfg_attr(
+    any(
+        feature = "time-driver-tc1",
+        feature = "rtic-monotonic-tc1",
+        feature = "rtic-monotonic-tc3"
+    ),
+    feature(abi_avr_interrupt)
+)]
+#![cfg_attr(feature = "reset-cause", feature(asm_experimental_arch))]
 
 //! `atmega-hal`
 //! =============
@@ -115,7 +124,7 @@ pub use avr_device::entry;
 #[cfg(feature = "device-selected")]
 pub use pac::Peripherals;
 
-pub use avr_hal_generic::clock;
+pub mod clock;
 pub use avr_hal_generic::delay;
 pub use avr_hal_generic::prelude;
 
@@ -124,11 +133,21 @@ pub mod adc;
 #[cfg(feature = "device-selected")]
 pub use adc::Adc;
 
+#[cfg(feature = "device-selected")]
+pub mod comparator;
+#[cfg(feature = "device-selected")]
+pub use comparator::AnalogComparator;
+
 #[cfg(feature = "device-selected")]
 pub mod i2c;
 #[cfg(feature = "device-selected")]
 pub use i2c::I2c;
 
+#[cfg(feature = "device-selected")]
+pub mod input_capture;
+#[cfg(feature = "device-selected")]
+pub use input_capture::InputCapture;
+
 #[cfg(feature = "device-selected")]
 pub mod spi;
 #[cfg(feature = "device-selected")]
@@ -139,9 +158,45 @@ pub mod port;
 #[cfg(feature = "device-selected")]
 pub use port::Pins;
 
+#[cfg(feature = "device-selected")]
+pub mod osccal;
+
+#[cfg(feature = "device-selected")]
+pub mod power;
+
+#[cfg(feature = "device-selected")]
+pub mod reset;
+
+#[cfg(all(
+    feature = "device-selected",
+    any(feature = "rtic-monotonic-tc1", feature = "rtic-monotonic-tc3")
+))]
+pub mod rtic_monotonic;
+
+#[cfg(feature = "device-selected")]
+pub mod servo;
+
 #[cfg(feature = "device-selected")]
 pub mod simple_pwm;
 
+#[cfg(feature = "device-selected")]
+pub mod time;
+#[cfg(feature = "device-selected")]
+pub use time::SysTime;
+
+#[cfg(all(feature = "device-selected", feature = "time-driver-tc1"))]
+pub mod time_driver;
+
+#[cfg(feature = "device-selected")]
+pub mod timer;
+#[cfg(feature = "device-selected")]
+pub use timer::Timer;
+
+#[cfg(feature = "device-selected")]
+pub mod tone;
+#[cfg(feature = "device-selected")]
+pub use tone::Tone;
+
 #[cfg(feature = "device-selected")]
 pub mod usart;
 #[cfg(feature = "device-selected")]
@@ -0,0 +1,64 @@
+//! Internal RC oscillator calibration
+//!
+//! See [`avr_hal_generic::osccal`] for details.
+//!
+//! # Example
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//! let mut cpu = dp.CPU;
+//!
+//! // Calibrate against 'U' bytes sent by the host at 9600 baud
+//! let mut timer = atmega_hal::Timer::<_, MHz8>::new(dp.TC1);
+//! let rx = pins.pd0.into_floating_input();
+//! let mut reference = osccal::UartReference::new(&mut timer, &rx, 9600);
+//! osccal::calibrate(&mut cpu, &mut reference).unwrap();
+//! ```
+#[allow(unused_imports)]
+pub use avr_hal_generic::osccal::{
+    calibrate, calibration, load_calibration, restore_calibration, set_calibration,
+    store_calibration, CalibrationError, CalibrationReference, CrystalReference, OscCalOps,
+    UartReference, UsbSofOps, UsbSofReference,
+};
+
+// The older MCUs have a single frequency range, the newer ones select one of two overlapping
+// ranges with the highest bit.
+#[cfg(any(
+    feature = "atmega8",
+    feature = "atmega16",
+    feature = "atmega32a",
+    feature = "atmega128a",
+))]
+avr_hal_generic::impl_osccal! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    tuning_mask: 0xff,
+}
+
+#[cfg(not(any(
+    feature = "atmega8",
+    feature = "atmega16",
+    feature = "atmega32a",
+    feature = "atmega128a",
+)))]
+avr_hal_generic::impl_osccal! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    tuning_mask: 0x7f,
+}
+
+#[cfg(feature = "atmega32u4")]
+impl UsbSofOps<crate::Atmega> for crate::pac::USB_DEVICE {
+    #[inline]
+    fn raw_is_sof(&self) -> bool {
+        self.udint().read().sofi().bit_is_set()
+    }
+
+    #[inline]
+    fn raw_clear_sof(&mut self) {
+        // The interrupt flags are cleared by writing zero, writing one has no effect.
+        // SAFETY: Only the start-of-frame flag is cleared.
+        self.udint()
+            .write(|w| unsafe { w.bits(0xff) }.sofi().clear_bit());
+    }
+}
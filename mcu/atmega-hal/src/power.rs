@@ -0,0 +1,519 @@
+//! Sleep modes and power management
+//!
+//! # Example
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let mut cpu = dp.CPU;
+//! let mut exint = dp.EXINT;
+//!
+//! // Wake up when INT0 is pulled low
+//! power::wake_on_interrupt(&mut exint, ExternalInterrupt::Int0, Sense::LowLevel);
+//!
+//! loop {
+//!     // SAFETY: Not inside a critical section.
+//!     unsafe { power::sleep(&mut cpu, SleepMode::PowerDown) };
+//! }
+//!
+//! #[avr_device::interrupt(atmega328p)]
+//! fn INT0() {}
+//! ```
+#[allow(unused_imports)]
+pub use avr_hal_generic::power::{
+    disable_wake_on_interrupt, disable_wake_on_pin_change, enable_interrupts_and_sleep,
+    set_async_timer_clock, set_powered, sleep, sleep_with_bod_disabled, wait_for_async_timer,
+    wake_on_interrupt, wake_on_pin_change, wake_on_watchdog, AsyncTimerOps, ExternalInterruptOps,
+    PinChangeOps, PowerReductionOps, Sense, SleepBodOps, SleepOps, WakeError,
+};
+
+/// Sleep mode entered by the `sleep` instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SleepMode {
+    /// Stop the CPU, all peripherals keep running.
+    Idle,
+    /// Stop the CPU and the I/O clock, leaving the ADC, asynchronous timers and external
+    /// interrupts running.
+    AdcNoiseReduction,
+    /// Stop all clocks.  Only external and pin change interrupts, the TWI address match and the
+    /// watchdog can wake the CPU up.
+    PowerDown,
+    /// Like power-down, but the asynchronous timer keeps running.
+    PowerSave,
+    /// Like power-down, but the main oscillator keeps running, so the CPU wakes up within six
+    /// cycles.  Only available with an external crystal or resonator.
+    Standby,
+    /// Like power-save, but the main oscillator keeps running.  Only available with an external
+    /// crystal or resonator.
+    #[cfg(not(any(feature = "atmega8", feature = "atmega168")))]
+    ExtendedStandby,
+}
+
+/// External interrupt (INTn)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalInterrupt {
+    Int0 = 0,
+    Int1 = 1,
+    /// On ATmega16 and ATmega32A, INT2 only detects edges and [`Sense::LowLevel`] and
+    /// [`Sense::AnyEdge`] select the falling edge.
+    #[cfg(any(
+        feature = "atmega16",
+        feature = "atmega32a",
+        feature = "atmega164pa",
+        feature = "atmega1284p",
+        feature = "atmega128a",
+        feature = "atmega1280",
+        feature = "atmega2560",
+        feature = "atmega32u4"
+    ))]
+    Int2 = 2,
+    #[cfg(any(
+        feature = "atmega128a",
+        feature = "atmega1280",
+        feature = "atmega2560",
+        feature = "atmega32u4"
+    ))]
+    Int3 = 3,
+    #[cfg(any(feature = "atmega128a", feature = "atmega1280", feature = "atmega2560"))]
+    Int4 = 4,
+    #[cfg(any(feature = "atmega128a", feature = "atmega1280", feature = "atmega2560"))]
+    Int5 = 5,
+    #[cfg(any(
+        feature = "atmega128a",
+        feature = "atmega1280",
+        feature = "atmega2560",
+        feature = "atmega32u4"
+    ))]
+    Int6 = 6,
+    #[cfg(any(feature = "atmega128a", feature = "atmega1280", feature = "atmega2560"))]
+    Int7 = 7,
+}
+
+#[cfg(not(any(
+    feature = "atmega8",
+    feature = "atmega16",
+    feature = "atmega32a",
+    feature = "atmega128a"
+)))]
+avr_hal_generic::impl_sleep! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    mode: SleepMode,
+    register: smcr,
+    set_mode: |mode, w| match mode {
+        SleepMode::Idle => w.sm().idle(),
+        SleepMode::AdcNoiseReduction => w.sm().adc(),
+        SleepMode::PowerDown => w.sm().pdown(),
+        SleepMode::PowerSave => w.sm().psave(),
+        SleepMode::Standby => w.sm().stdby(),
+        #[cfg(not(feature = "atmega168"))]
+        SleepMode::ExtendedStandby => w.sm().estdby(),
+    },
+}
+
+#[cfg(any(feature = "atmega8", feature = "atmega16", feature = "atmega32a"))]
+avr_hal_generic::impl_sleep! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    mode: SleepMode,
+    register: mcucr,
+    set_mode: |mode, w| match mode {
+        SleepMode::Idle => w.sm().idle(),
+        SleepMode::AdcNoiseReduction => w.sm().adc(),
+        SleepMode::PowerDown => w.sm().pdown(),
+        SleepMode::PowerSave => w.sm().psave(),
+        SleepMode::Standby => w.sm().stdby(),
+        #[cfg(not(feature = "atmega8"))]
+        SleepMode::ExtendedStandby => w.sm().estdby(),
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_sleep! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    mode: SleepMode,
+    register: mcucr,
+    set_mode: |mode, w| match mode {
+        SleepMode::Idle => w.sm2().idle().sm().set(0b00),
+        SleepMode::AdcNoiseReduction => w.sm2().clear_bit().sm().set(0b01),
+        SleepMode::PowerDown => w.sm2().clear_bit().sm().set(0b10),
+        SleepMode::PowerSave => w.sm2().clear_bit().sm().set(0b11),
+        SleepMode::Standby => w.sm2().set_bit().sm().set(0b10),
+        SleepMode::ExtendedStandby => w.sm2().set_bit().sm().set(0b11),
+    },
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega164pa",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1284p"
+))]
+avr_hal_generic::impl_sleep_bod_disable! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    register: mcucr,
+    bods: 6,
+    bodse: 5,
+}
+
+impl avr_hal_generic::adc::AdcSleepOps<crate::Atmega, crate::pac::ADC> for crate::pac::CPU {
+    const NOISE_REDUCTION: SleepMode = SleepMode::AdcNoiseReduction;
+}
+
+/// Set the two sense control bits of INTn in an EICRx-like register.
+fn with_sense(bits: u8, n: u8, isc: u8) -> u8 {
+    let shift = 2 * (n % 4);
+    bits & !(0b11 << shift) | isc << shift
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_external_interrupts! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupt: ExternalInterrupt,
+    mask: eimsk,
+    flags: eifr,
+    bit: |int| int as u8,
+    set_sense: |exint, int, isc| {
+        // SAFETY: Only the sense bits of this interrupt are changed.
+        exint
+            .eicra()
+            .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), int as u8, isc)) });
+    },
+    pin_change: {
+        control: pcicr,
+        first_bit: 0,
+        masks: [0: pcmsk0, 1: pcmsk1, 2: pcmsk2],
+    },
+}
+
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p"))]
+avr_hal_generic::impl_external_interrupts! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupt: ExternalInterrupt,
+    mask: eimsk,
+    flags: eifr,
+    bit: |int| int as u8,
+    set_sense: |exint, int, isc| {
+        // SAFETY: Only the sense bits of this interrupt are changed.
+        exint
+            .eicra()
+            .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), int as u8, isc)) });
+    },
+    pin_change: {
+        control: pcicr,
+        first_bit: 0,
+        masks: [0: pcmsk0, 1: pcmsk1, 2: pcmsk2, 3: pcmsk3],
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_external_interrupts! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupt: ExternalInterrupt,
+    mask: eimsk,
+    flags: eifr,
+    bit: |int| int as u8,
+    set_sense: |exint, int, isc| {
+        let n = int as u8;
+        // SAFETY: Only the sense bits of this interrupt are changed.
+        if n < 4 {
+            exint
+                .eicra()
+                .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), n, isc)) });
+        } else {
+            exint
+                .eicrb()
+                .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), n, isc)) });
+        }
+    },
+    pin_change: {
+        control: pcicr,
+        first_bit: 0,
+        masks: [0: pcmsk0, 1: pcmsk1, 2: pcmsk2],
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_external_interrupts! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupt: ExternalInterrupt,
+    mask: eimsk,
+    flags: eifr,
+    bit: |int| int as u8,
+    set_sense: |exint, int, isc| {
+        let n = int as u8;
+        // SAFETY: Only the sense bits of this interrupt are changed.
+        if n < 4 {
+            exint
+                .eicra()
+                .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), n, isc)) });
+        } else {
+            exint
+                .eicrb()
+                .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), n, isc)) });
+        }
+    },
+    pin_change: {
+        control: pcicr,
+        first_bit: 0,
+        masks: [0: pcmsk0],
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_external_interrupts! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupt: ExternalInterrupt,
+    mask: eimsk,
+    flags: eifr,
+    bit: |int| int as u8,
+    set_sense: |exint, int, isc| {
+        let n = int as u8;
+        // SAFETY: Only the sense bits of this interrupt are changed.
+        if n < 4 {
+            exint
+                .eicra()
+                .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), n, isc)) });
+        } else {
+            exint
+                .eicrb()
+                .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), n, isc)) });
+        }
+    },
+}
+
+#[cfg(feature = "atmega8")]
+avr_hal_generic::impl_external_interrupts! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupt: ExternalInterrupt,
+    mask: gicr,
+    flags: gifr,
+    bit: |int| match int {
+        ExternalInterrupt::Int0 => 6,
+        ExternalInterrupt::Int1 => 7,
+    },
+    set_sense: |exint, int, isc| {
+        // SAFETY: Only the sense bits of this interrupt are changed.
+        exint
+            .mcucr()
+            .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), int as u8, isc)) });
+    },
+}
+
+#[cfg(any(feature = "atmega16", feature = "atmega32a"))]
+avr_hal_generic::impl_external_interrupts! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupt: ExternalInterrupt,
+    mask: gicr,
+    flags: gifr,
+    bit: |int| match int {
+        ExternalInterrupt::Int0 => 6,
+        ExternalInterrupt::Int1 => 7,
+        ExternalInterrupt::Int2 => 5,
+    },
+    set_sense: |exint, int, isc| match int {
+        ExternalInterrupt::Int2 => {
+            exint
+                .mcucsr()
+                .modify(|_, w| w.isc2().bit(isc == 0b11));
+        }
+        _ => {
+            // SAFETY: Only the sense bits of this interrupt are changed.
+            exint
+                .mcucr()
+                .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), int as u8, isc)) });
+        }
+    },
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega164pa",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1280",
+    feature = "atmega1284p",
+    feature = "atmega2560"
+))]
+avr_hal_generic::impl_async_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    async_clock: as2,
+    busy_mask: 0x1f,
+}
+
+#[cfg(any(feature = "atmega8", feature = "atmega16", feature = "atmega32a"))]
+avr_hal_generic::impl_async_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    async_clock: as2,
+    busy_mask: 0x07,
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_async_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    async_clock: as0,
+    busy_mask: 0x07,
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+))]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    gates: [
+        crate::pac::ADC: prr.pradc,
+        crate::pac::USART0: prr.prusart0,
+        crate::pac::SPI: prr.prspi,
+        crate::pac::TC0: prr.prtim0,
+        crate::pac::TC1: prr.prtim1,
+        crate::pac::TC2: prr.prtim2,
+        crate::pac::TWI: prr.prtwi,
+    ],
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    gates: [
+        crate::pac::ADC: prr0.pradc,
+        crate::pac::USART0: prr0.prusart0,
+        crate::pac::USART1: prr0.prusart1,
+        crate::pac::SPI0: prr0.prspi0,
+        crate::pac::SPI1: prr1.prspi1,
+        crate::pac::TC0: prr0.prtim0,
+        crate::pac::TC1: prr0.prtim1,
+        crate::pac::TC2: prr0.prtim2,
+        crate::pac::TC3: prr1.prtim3,
+        crate::pac::TC4: prr1.prtim4,
+        crate::pac::TWI0: prr0.prtwi0,
+        crate::pac::TWI1: prr1.prtwi1,
+    ],
+}
+
+#[cfg(feature = "atmega164pa")]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    gates: [
+        crate::pac::ADC: prr0.pradc,
+        crate::pac::USART0: prr0.prusart0,
+        crate::pac::USART1: prr0.prusart1,
+        crate::pac::SPI: prr0.prspi,
+        crate::pac::TC0: prr0.prtim0,
+        crate::pac::TC1: prr0.prtim1,
+        crate::pac::TC2: prr0.prtim2,
+        crate::pac::TWI: prr0.prtwi,
+    ],
+}
+
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    gates: [
+        crate::pac::ADC: prr0.pradc,
+        crate::pac::USART0: prr0.prusart0,
+        crate::pac::USART1: prr0.prusart1,
+        crate::pac::SPI: prr0.prspi,
+        crate::pac::TC0: prr0.prtim0,
+        crate::pac::TC1: prr0.prtim1,
+        crate::pac::TC2: prr0.prtim2,
+        crate::pac::TC3: prr1.prtim3,
+        crate::pac::TWI: prr0.prtwi,
+    ],
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    gates: [
+        crate::pac::ADC: prr0.pradc,
+        crate::pac::USART0: prr0.prusart0,
+        crate::pac::USART1: prr1.prusart1,
+        crate::pac::USART2: prr1.prusart2,
+        crate::pac::USART3: prr1.prusart3,
+        crate::pac::SPI: prr0.prspi,
+        crate::pac::TC0: prr0.prtim0,
+        crate::pac::TC1: prr0.prtim1,
+        crate::pac::TC2: prr0.prtim2,
+        crate::pac::TC3: prr1.prtim3,
+        crate::pac::TC4: prr1.prtim4,
+        crate::pac::TC5: prr1.prtim5,
+        crate::pac::TWI: prr0.prtwi,
+    ],
+}
+
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    gates: [
+        crate::pac::ADC: prr0.pradc,
+        crate::pac::USART1: prr1.prusart1,
+        crate::pac::SPI: prr0.prspi,
+        crate::pac::TC0: prr0.prtim0,
+        crate::pac::TC1: prr0.prtim1,
+        crate::pac::TC3: prr1.prtim3,
+        crate::pac::TC4: prr1.prtim4,
+        crate::pac::TWI: prr0.prtwi,
+        crate::pac::USB_DEVICE: prr1.prusb,
+    ],
+}
+
+#[cfg(any(feature = "atmega8", feature = "atmega16", feature = "atmega32a"))]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    always_on: [
+        crate::pac::ADC,
+        crate::pac::USART,
+        crate::pac::SPI,
+        crate::pac::TC0,
+        crate::pac::TC1,
+        crate::pac::TC2,
+        crate::pac::TWI,
+    ],
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::CPU,
+    always_on: [
+        crate::pac::ADC,
+        crate::pac::USART0,
+        crate::pac::USART1,
+        crate::pac::SPI,
+        crate::pac::TC0,
+        crate::pac::TC1,
+        crate::pac::TC2,
+        crate::pac::TC3,
+        crate::pac::TWI,
+    ],
+}
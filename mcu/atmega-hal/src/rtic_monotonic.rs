@@ -0,0 +1,126 @@
+//! [RTIC](https://rtic.rs) monotonics on 16-bit timers
+//!
+//! Each monotonic is enabled with its own feature, which also defines the overflow and compare A
+//! interrupt handlers of the timer:
+//!
+//! | Feature | Monotonic | Timer |
+//! | --- | --- | --- |
+//! | `rtic-monotonic-tc1` | [`Tc1Monotonic`] | `TC1` |
+//! | `rtic-monotonic-tc3` | `Tc3Monotonic` | `TC3` (ATmega32U4, ATmega328PB, ATmega1284P, ATmega128A, ATmega1280, ATmega2560) |
+//!
+//! Check the [`avr_hal_generic::rtic_monotonic`] documentation for details.
+//!
+//! # Example
+//! ```
+//! // 250 kHz ticks from a 16 MHz clock
+//! type Mono = atmega_hal::rtic_monotonic::Tc1Monotonic<CoreClock, 250_000>;
+//!
+//! Mono::start(dp.TC1);
+//!
+//! // ... in an RTIC task
+//! Mono::delay(100.millis()).await;
+//! ```
+
+pub use avr_hal_generic::rtic_monotonic::rtic_time::Monotonic;
+
+#[cfg(feature = "rtic-monotonic-tc1")]
+avr_hal_generic::impl_rtic_monotonic! {
+    /// RTIC monotonic on `TC1`, ticking at `TICK_HZ`.
+    pub struct Tc1Monotonic {
+        backend: Tc1Backend,
+        hal: crate::Atmega,
+        timer: crate::pac::TC1,
+    }
+}
+
+#[cfg(all(
+    feature = "rtic-monotonic-tc3",
+    any(
+        feature = "atmega32u4",
+        feature = "atmega328pb",
+        feature = "atmega1284p",
+        feature = "atmega128a",
+        feature = "atmega1280",
+        feature = "atmega2560"
+    )
+))]
+avr_hal_generic::impl_rtic_monotonic! {
+    /// RTIC monotonic on `TC3`, ticking at `TICK_HZ`.
+    pub struct Tc3Monotonic {
+        backend: Tc3Backend,
+        hal: crate::Atmega,
+        timer: crate::pac::TC3,
+    }
+}
+
+#[cfg(all(
+    feature = "rtic-monotonic-tc3",
+    not(any(
+        feature = "atmega32u4",
+        feature = "atmega328pb",
+        feature = "atmega1284p",
+        feature = "atmega128a",
+        feature = "atmega1280",
+        feature = "atmega2560"
+    ))
+))]
+compile_error!("The `rtic-monotonic-tc3` feature needs an MCU with a `TC3` timer.");
+
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "time-driver-tc1"))]
+compile_error!("The `rtic-monotonic-tc1` and `time-driver-tc1` features both use `TC1`.");
+
+macro_rules! impl_interrupts {
+    ($chip:ident, $Backend:ident, $OVF:ident, $COMPA:ident) => {
+        #[avr_device::interrupt($chip)]
+        fn $OVF() {
+            $Backend::on_overflow();
+        }
+
+        #[avr_device::interrupt($chip)]
+        fn $COMPA() {
+            $Backend::on_compare();
+        }
+    };
+}
+
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega48p"))]
+impl_interrupts!(atmega48p, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega16"))]
+impl_interrupts!(atmega16, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega164pa"))]
+impl_interrupts!(atmega164pa, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega168"))]
+impl_interrupts!(atmega168, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega328p"))]
+impl_interrupts!(atmega328p, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega328pb"))]
+impl_interrupts!(atmega328pb, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega32a"))]
+impl_interrupts!(atmega32a, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega32u4"))]
+impl_interrupts!(atmega32u4, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega2560"))]
+impl_interrupts!(atmega2560, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega128a"))]
+impl_interrupts!(atmega128a, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega1280"))]
+impl_interrupts!(atmega1280, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega1284p"))]
+impl_interrupts!(atmega1284p, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega8"))]
+impl_interrupts!(atmega8, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc1", feature = "atmega88p"))]
+impl_interrupts!(atmega88p, Tc1Backend, TIMER1_OVF, TIMER1_COMPA);
+
+#[cfg(all(feature = "rtic-monotonic-tc3", feature = "atmega32u4"))]
+impl_interrupts!(atmega32u4, Tc3Backend, TIMER3_OVF, TIMER3_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc3", feature = "atmega328pb"))]
+impl_interrupts!(atmega328pb, Tc3Backend, TIMER3_OVF, TIMER3_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc3", feature = "atmega1284p"))]
+impl_interrupts!(atmega1284p, Tc3Backend, TIMER3_OVF, TIMER3_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc3", feature = "atmega128a"))]
+impl_interrupts!(atmega128a, Tc3Backend, TIMER3_OVF, TIMER3_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc3", feature = "atmega1280"))]
+impl_interrupts!(atmega1280, Tc3Backend, TIMER3_OVF, TIMER3_COMPA);
+#[cfg(all(feature = "rtic-monotonic-tc3", feature = "atmega2560"))]
+impl_interrupts!(atmega2560, Tc3Backend, TIMER3_OVF, TIMER3_COMPA);
@@ -0,0 +1,21 @@
+//! Hobby servo control
+//!
+//! Check the [`avr_hal_generic::servo`] documentation for details.
+//!
+//! # Example
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//!
+//! let pwm = Pwm16::<_, CoreClock>::new(dp.TC1, REFRESH_RATE).unwrap();
+//! let mut servo = Servo::new(pins.pb1.into_output(), &pwm);
+//! servo.set_angle(90);
+//! ```
+
+pub use avr_hal_generic::servo::{
+    Servo, DEFAULT_MAX_PULSE_WIDTH, DEFAULT_MIN_PULSE_WIDTH, MAX_PULSE_WIDTH, REFRESH_RATE,
+};
+
+/// Check the [`avr_hal_generic::servo::ServoMux`] documentation.
+pub type ServoMux<TC, CLOCK, PIN, const N: usize> =
+    avr_hal_generic::servo::ServoMux<crate::Atmega, TC, CLOCK, PIN, N>;
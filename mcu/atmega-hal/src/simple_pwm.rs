@@ -1,7 +1,15 @@
-pub use avr_hal_generic::simple_pwm::{IntoPwmPin, Prescaler, PwmPinOps};
+pub use avr_hal_generic::simple_pwm::{
+    ComplementaryPwm, ComplementaryPwmOps, DeadTimePrescaler, IntoPwmPin, Prescaler, Pwm16Ops,
+    PwmError, PwmMode, PwmPinOps, PwmTimer,
+};
+
+/// Check the [`avr_hal_generic::simple_pwm::Pwm16`] documentation.
+pub type Pwm16<TC, CLOCK> = avr_hal_generic::simple_pwm::Pwm16<crate::Atmega, TC, CLOCK>;
 
 #[allow(unused_imports)]
 use crate::port::*;
+#[cfg(feature = "atmega32u4")]
+use crate::timer::{read_tc4_10bit, write_tc4_10bit};
 
 #[cfg(any(
     feature = "atmega48p",
@@ -24,9 +32,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d5.enable();
     /// ```
     pub struct Timer0Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC0,
-        init: |tim, prescaler| {
-            tim.tccr0a().modify(|_r, w| w.wgm0().pwm_fast());
+        modes: [Fast, PhaseCorrect],
+        init: |tim, prescaler, mode| {
+            tim.tccr0a().modify(|_r, w| match mode {
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+                _ => w.wgm0().pwm_fast(),
+            });
             tim.tccr0b().modify(|_r, w| match prescaler {
                 Prescaler::Direct => w.cs0().direct(),
                 Prescaler::Prescale8 => w.cs0().prescale_8(),
@@ -38,8 +51,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PD6: {
                 ocr: ocr0a,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0a().match_set()
+                    } else {
+                        w.com0a().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0a().disconnected());
                 },
@@ -47,8 +64,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PD5: {
                 ocr: ocr0b,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0b().match_set()
+                    } else {
+                        w.com0b().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0b().disconnected());
                 },
@@ -78,11 +99,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d9.enable();
     /// ```
     pub struct Timer1Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC1,
-        init: |tim, prescaler| {
-            tim.tccr1a().modify(|_r, w| w.wgm1().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr1,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
             tim.tccr1b().modify(|_r, w| {
-                w.wgm1().set(0b01);
+                w.wgm1().set(wgm_b);
 
                 match prescaler {
                     Prescaler::Direct => w.cs1().direct(),
@@ -96,8 +120,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB1: {
                 ocr: ocr1a,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1a().match_set()
+                    } else {
+                        w.com1a().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1a().disconnected());
                 },
@@ -105,8 +133,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PB2: {
                 ocr: ocr1b,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1b().match_set()
+                    } else {
+                        w.com1b().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1b().disconnected());
                 },
@@ -136,9 +168,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d11.enable();
     /// ```
     pub struct Timer2Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC2,
-        init: |tim, prescaler| {
-            tim.tccr2a().modify(|_r, w| w.wgm2().pwm_fast());
+        modes: [Fast, PhaseCorrect],
+        init: |tim, prescaler, mode| {
+            tim.tccr2a().modify(|_r, w| match mode {
+                PwmMode::PhaseCorrect => w.wgm2().pwm_phase(),
+                _ => w.wgm2().pwm_fast(),
+            });
             tim.tccr2b().modify(|_r, w| match prescaler {
                     Prescaler::Direct => w.cs2().direct(),
                     Prescaler::Prescale8 => w.cs2().prescale_8(),
@@ -150,8 +187,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB3: {
                 ocr: ocr2a,
-                into_pwm: |tim| if enable {
-                    tim.tccr2a().modify(|_r, w| w.com2a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr2a().modify(|_r, w| if inverted {
+                        w.com2a().match_set()
+                    } else {
+                        w.com2a().match_clear()
+                    });
                 } else {
                     tim.tccr2a().modify(|_r, w| w.com2a().disconnected());
                 },
@@ -159,8 +200,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PD3: {
                 ocr: ocr2b,
-                into_pwm: |tim| if enable {
-                    tim.tccr2a().modify(|_r, w| w.com2b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr2a().modify(|_r, w| if inverted {
+                        w.com2b().match_set()
+                    } else {
+                        w.com2b().match_clear()
+                    });
                 } else {
                     tim.tccr2a().modify(|_r, w| w.com2b().disconnected());
                 },
@@ -173,11 +218,14 @@ avr_hal_generic::impl_simple_pwm! {
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC3` for PWM (pins `PD0`, `PD2`)
     pub struct Timer3Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC3,
-        init: |tim, prescaler| {
-            tim.tccr3a().modify(|_r, w| w.wgm3().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr3,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr3a().modify(|_r, w| w.wgm3().set(wgm_a));
             tim.tccr3b().modify(|_r, w| {
-                unsafe { w.wgm3().bits(0b01) };
+                unsafe { w.wgm3().bits(wgm_b) };
 
                 match prescaler {
                     Prescaler::Direct => w.cs3().direct(),
@@ -191,8 +239,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PD0: {
                 ocr: ocr3a,
-                into_pwm: |tim| if enable {
-                    tim.tccr3a().modify(|_r, w| w.com3a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr3a().modify(|_r, w| if inverted {
+                        w.com3a().match_set()
+                    } else {
+                        w.com3a().match_clear()
+                    });
                 } else {
                     tim.tccr3a().modify(|_r, w| w.com3a().disconnected());
                 },
@@ -200,8 +252,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PD2: {
                 ocr: ocr3b,
-                into_pwm: |tim| if enable {
-                    tim.tccr3a().modify(|_r, w| w.com3b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr3a().modify(|_r, w| if inverted {
+                        w.com3b().match_set()
+                    } else {
+                        w.com3b().match_clear()
+                    });
                 } else {
                     tim.tccr3a().modify(|_r, w| w.com3b().disconnected());
                 },
@@ -214,11 +270,14 @@ avr_hal_generic::impl_simple_pwm! {
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC4` for PWM (pins `PD1`, `PD2`)
     pub struct Timer4Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC4,
-        init: |tim, prescaler| {
-            tim.tccr4a().modify(|_r, w| w.wgm4().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr4,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr4a().modify(|_r, w| w.wgm4().set(wgm_a));
             tim.tccr4b().modify(|_r, w| {
-                unsafe { w.wgm4().bits(0b01) };
+                unsafe { w.wgm4().bits(wgm_b) };
 
                 match prescaler {
                     Prescaler::Direct => w.cs4().direct(),
@@ -232,8 +291,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PD1: {
                 ocr: ocr4a,
-                into_pwm: |tim| if enable {
-                    tim.tccr4a().modify(|_r, w| w.com4a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr4a().modify(|_r, w| if inverted {
+                        w.com4a().match_set()
+                    } else {
+                        w.com4a().match_clear()
+                    });
                 } else {
                     tim.tccr4a().modify(|_r, w| w.com4a().disconnected());
                 },
@@ -241,8 +304,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PD2: {
                 ocr: ocr4b,
-                into_pwm: |tim| if enable {
-                    tim.tccr4a().modify(|_r, w| w.com4b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr4a().modify(|_r, w| if inverted {
+                        w.com4b().match_set()
+                    } else {
+                        w.com4b().match_clear()
+                    });
                 } else {
                     tim.tccr4a().modify(|_r, w| w.com4b().disconnected());
                 },
@@ -266,9 +333,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d13.enable();
     /// ```
     pub struct Timer0Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC0,
-        init: |tim, prescaler| {
-            tim.tccr0a().modify(|_r, w| w.wgm0().pwm_fast());
+        modes: [Fast, PhaseCorrect],
+        init: |tim, prescaler, mode| {
+            tim.tccr0a().modify(|_r, w| match mode {
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+                _ => w.wgm0().pwm_fast(),
+            });
             tim.tccr0b().modify(|_r, w| match prescaler {
                 Prescaler::Direct => w.cs0().direct(),
                 Prescaler::Prescale8 => w.cs0().prescale_8(),
@@ -280,8 +352,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB7: {
                 ocr: ocr0a,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0a().match_set()
+                    } else {
+                        w.com0a().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0a().disconnected());
                 },
@@ -289,8 +365,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PG5: {
                 ocr: ocr0b,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0b().match_set()
+                    } else {
+                        w.com0b().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0b().disconnected());
                 },
@@ -315,11 +395,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d11.enable();
     /// ```
     pub struct Timer1Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC1,
-        init: |tim, prescaler| {
-            tim.tccr1a().modify(|_r, w| w.wgm1().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr1,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
             tim.tccr1b().modify(|_r, w| {
-                w.wgm1().set(0b01);
+                w.wgm1().set(wgm_b);
 
                 match prescaler {
                     Prescaler::Direct => w.cs1().direct(),
@@ -333,8 +416,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB5: {
                 ocr: ocr1a,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1a().match_set()
+                    } else {
+                        w.com1a().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1a().disconnected());
                 },
@@ -342,8 +429,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PB6: {
                 ocr: ocr1b,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1b().match_set()
+                    } else {
+                        w.com1b().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1b().disconnected());
                 },
@@ -351,8 +442,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PB7: {
                 ocr: ocr1c,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1c().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1c().match_set()
+                    } else {
+                        w.com1c().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1c().disconnected());
                 },
@@ -365,6 +460,9 @@ avr_hal_generic::impl_simple_pwm! {
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC2` for PWM (pins `PB4`, `PH6`)
     ///
+    /// [`new()`][Self::new] starts the timer in phase-correct mode, use
+    /// [`with_mode()`][Self::with_mode] for fast PWM.
+    ///
     /// # Example
     /// ```
     /// let mut timer2 = Timer2Pwm::new(dp.TC2, Prescaler::Prescale64);
@@ -377,9 +475,15 @@ avr_hal_generic::impl_simple_pwm! {
     /// ```
 
     pub struct Timer2Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC2,
-        init: |tim, prescaler| {
-            tim.tccr2a().modify(|_r, w| w.wgm2().set(0b01));
+        modes: [Fast, PhaseCorrect],
+        default_mode: PhaseCorrect,
+        init: |tim, prescaler, mode| {
+            tim.tccr2a().modify(|_r, w| w.wgm2().set(match mode {
+                PwmMode::PhaseCorrect => 0b01,
+                _ => 0b11,
+            }));
             tim.tccr2b().modify(|_r, w| {
                 w.wgm22().clear_bit();
 
@@ -395,8 +499,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB4: {
                 ocr: ocr2a,
-                into_pwm: |tim| if enable {
-                    tim.tccr2a().modify(|_r, w| w.com2a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr2a().modify(|_r, w| if inverted {
+                        w.com2a().match_set()
+                    } else {
+                        w.com2a().match_clear()
+                    });
                 } else {
                     tim.tccr2a().modify(|_r, w| w.com2a().disconnected());
                 },
@@ -404,8 +512,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PH6: {
                 ocr: ocr2b,
-                into_pwm: |tim| if enable {
-                    tim.tccr2a().modify(|_r, w| w.com2b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr2a().modify(|_r, w| if inverted {
+                        w.com2b().match_set()
+                    } else {
+                        w.com2b().match_clear()
+                    });
                 } else {
                     tim.tccr2a().modify(|_r, w| w.com2b().disconnected());
                 },
@@ -430,11 +542,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d5.enable();
     /// ```
     pub struct Timer3Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC3,
-        init: |tim, prescaler| {
-            tim.tccr3a().modify(|_r, w| w.wgm3().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr3,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr3a().modify(|_r, w| w.wgm3().set(wgm_a));
             tim.tccr3b().modify(|_r, w| {
-                w.wgm3().set(0b01);
+                w.wgm3().set(wgm_b);
 
                 match prescaler {
                     Prescaler::Direct => w.cs3().direct(),
@@ -448,8 +563,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PE3: {
                 ocr: ocr3a,
-                into_pwm: |tim| if enable {
-                    tim.tccr3a().modify(|_r, w| w.com3a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr3a().modify(|_r, w| if inverted {
+                        w.com3a().match_set()
+                    } else {
+                        w.com3a().match_clear()
+                    });
                 } else {
                     tim.tccr3a().modify(|_r, w| w.com3a().disconnected());
                 },
@@ -457,8 +576,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PE4: {
                 ocr: ocr3b,
-                into_pwm: |tim| if enable {
-                    tim.tccr3a().modify(|_r, w| w.com3b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr3a().modify(|_r, w| if inverted {
+                        w.com3b().match_set()
+                    } else {
+                        w.com3b().match_clear()
+                    });
                 } else {
                     tim.tccr3a().modify(|_r, w| w.com3b().disconnected());
                 },
@@ -466,8 +589,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PE5: {
                 ocr: ocr3c,
-                into_pwm: |tim| if enable {
-                    tim.tccr3a().modify(|_r, w| w.com3c().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr3a().modify(|_r, w| if inverted {
+                        w.com3c().match_set()
+                    } else {
+                        w.com3c().match_clear()
+                    });
                 } else {
                     tim.tccr3a().modify(|_r, w| w.com3c().disconnected());
                 },
@@ -493,11 +620,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d6.enable();
     /// ```
     pub struct Timer4Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC4,
-        init: |tim, prescaler| {
-            tim.tccr4a().modify(|_r, w| w.wgm4().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr4,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr4a().modify(|_r, w| w.wgm4().set(wgm_a));
             tim.tccr4b().modify(|_r, w| {
-                w.wgm4().set(0b01);
+                w.wgm4().set(wgm_b);
 
                 match prescaler {
                     Prescaler::Direct => w.cs4().direct(),
@@ -511,8 +641,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PH3: {
                 ocr: ocr4a,
-                into_pwm: |tim| if enable {
-                    tim.tccr4a().modify(|_r, w| w.com4a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr4a().modify(|_r, w| if inverted {
+                        w.com4a().match_set()
+                    } else {
+                        w.com4a().match_clear()
+                    });
                 } else {
                     tim.tccr4a().modify(|_r, w| w.com4a().disconnected());
                 },
@@ -520,8 +654,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PH4: {
                 ocr: ocr4b,
-                into_pwm: |tim| if enable {
-                    tim.tccr4a().modify(|_r, w| w.com4b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr4a().modify(|_r, w| if inverted {
+                        w.com4b().match_set()
+                    } else {
+                        w.com4b().match_clear()
+                    });
                 } else {
                     tim.tccr4a().modify(|_r, w| w.com4b().disconnected());
                 },
@@ -529,8 +667,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PH5: {
                 ocr: ocr4c,
-                into_pwm: |tim| if enable {
-                    tim.tccr4a().modify(|_r, w| w.com4c().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr4a().modify(|_r, w| if inverted {
+                        w.com4c().match_set()
+                    } else {
+                        w.com4c().match_clear()
+                    });
                 } else {
                     tim.tccr4a().modify(|_r, w| w.com4c().disconnected());
                 },
@@ -556,11 +698,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d46.enable();
     /// ```
     pub struct Timer5Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC5,
-        init: |tim, prescaler| {
-            tim.tccr5a().modify(|_r, w| w.wgm5().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr5,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr5a().modify(|_r, w| w.wgm5().set(wgm_a));
             tim.tccr5b().modify(|_r, w| {
-                w.wgm5().set(0b01);
+                w.wgm5().set(wgm_b);
 
                 match prescaler {
                     Prescaler::Direct => w.cs5().direct(),
@@ -574,8 +719,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PL3: {
                 ocr: ocr5a,
-                into_pwm: |tim| if enable {
-                    tim.tccr5a().modify(|_r, w| w.com5a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr5a().modify(|_r, w| if inverted {
+                        w.com5a().match_set()
+                    } else {
+                        w.com5a().match_clear()
+                    });
                 } else {
                     tim.tccr5a().modify(|_r, w| w.com5a().disconnected());
                 },
@@ -583,8 +732,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PL4: {
                 ocr: ocr5b,
-                into_pwm: |tim| if enable {
-                    tim.tccr5a().modify(|_r, w| w.com5b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr5a().modify(|_r, w| if inverted {
+                        w.com5b().match_set()
+                    } else {
+                        w.com5b().match_clear()
+                    });
                 } else {
                     tim.tccr5a().modify(|_r, w| w.com5b().disconnected());
                 },
@@ -592,8 +745,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PL5: {
                 ocr: ocr5c,
-                into_pwm: |tim| if enable {
-                    tim.tccr5a().modify(|_r, w| w.com5c().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr5a().modify(|_r, w| if inverted {
+                        w.com5c().match_set()
+                    } else {
+                        w.com5c().match_clear()
+                    });
                 } else {
                     tim.tccr5a().modify(|_r, w| w.com5c().disconnected());
                 },
@@ -618,9 +775,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d11.enable();
     /// ```
     pub struct Timer0Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC0,
-        init: |tim, prescaler| {
-            tim.tccr0a().modify(|_r, w| w.wgm0().pwm_fast());
+        modes: [Fast, PhaseCorrect],
+        init: |tim, prescaler, mode| {
+            tim.tccr0a().modify(|_r, w| match mode {
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+                _ => w.wgm0().pwm_fast(),
+            });
             tim.tccr0b().modify(|_r, w| match prescaler {
                 Prescaler::Direct => w.cs0().direct(),
                 Prescaler::Prescale8 => w.cs0().prescale_8(),
@@ -632,8 +794,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB7: {
                 ocr: ocr0a,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0a().match_set()
+                    } else {
+                        w.com0a().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0a().disconnected());
                 },
@@ -641,8 +807,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PD0: {
                 ocr: ocr0b,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0b().match_set()
+                    } else {
+                        w.com0b().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0b().disconnected());
                 },
@@ -667,10 +837,13 @@ avr_hal_generic::impl_simple_pwm! {
     /// d9.enable();
     /// ```
     pub struct Timer1Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC1,
-        init: |tim, prescaler| {
-            tim.tccr1a().modify(|_r, w| w.wgm1().set(0b01));
-            tim.tccr1b().modify(|_r, w| w.wgm1().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr1,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
+            tim.tccr1b().modify(|_r, w| w.wgm1().set(wgm_b));
 
             tim.tccr1b().modify(|_r, w| match prescaler {
                 Prescaler::Direct => w.cs1().direct(),
@@ -683,8 +856,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB5: {
                 ocr: ocr1a,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1a().match_set()
+                    } else {
+                        w.com1a().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1a().disconnected());
                 },
@@ -692,8 +869,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PB6: {
                 ocr: ocr1b,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1b().match_set()
+                    } else {
+                        w.com1b().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1b().disconnected());
                 },
@@ -701,8 +882,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PB7: {
                 ocr: ocr1c,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1c().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1c().match_set()
+                    } else {
+                        w.com1c().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1c().disconnected());
                 },
@@ -725,10 +910,13 @@ avr_hal_generic::impl_simple_pwm! {
     /// d5.enable();
     /// ```
     pub struct Timer3Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC3,
-        init: |tim, prescaler| {
-            tim.tccr3a().modify(|_r, w| w.wgm3().set(0b01));
-            tim.tccr3b().modify(|_r, w| w.wgm3().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr3,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr3a().modify(|_r, w| w.wgm3().set(wgm_a));
+            tim.tccr3b().modify(|_r, w| w.wgm3().set(wgm_b));
 
             tim.tccr3b().modify(|_r, w| match prescaler {
                 Prescaler::Direct => w.cs3().direct(),
@@ -741,8 +929,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PC6: {
                 ocr: ocr3a,
-                into_pwm: |tim| if enable {
-                    tim.tccr3a().modify(|_r, w| w.com3a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr3a().modify(|_r, w| if inverted {
+                        w.com3a().match_set()
+                    } else {
+                        w.com3a().match_clear()
+                    });
                 } else {
                     tim.tccr3a().modify(|_r, w| w.com3a().disconnected());
                 },
@@ -767,8 +959,10 @@ avr_hal_generic::impl_simple_pwm! {
     /// d6.enable();
     /// ```
     pub struct Timer4Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC4,
-        init: |tim, prescaler| {
+        modes: [Fast],
+        init: |tim, prescaler, _mode| {
             tim.tccr4a().modify(|_r, w| w.pwm4a().set_bit());
             tim.tccr4a().modify(|_r, w| w.pwm4b().set_bit());
             tim.tccr4c().modify(|_r, w| w.pwm4d().set_bit());
@@ -784,8 +978,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB6: {
                 ocr: ocr4b,
-                into_pwm: |tim| if enable {
-                    tim.tccr4a().modify(|_r, w| w.com4b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr4a().modify(|_r, w| if inverted {
+                        w.com4b().match_set()
+                    } else {
+                        w.com4b().match_clear()
+                    });
                 } else {
                     tim.tccr4a().modify(|_r, w| w.com4b().disconnected());
                 },
@@ -793,8 +991,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PC7: {
                 ocr: ocr4a,
-                into_pwm: |tim| if enable {
-                    tim.tccr4a().modify(|_r, w| w.com4a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr4a().modify(|_r, w| if inverted {
+                        w.com4a().match_set()
+                    } else {
+                        w.com4a().match_clear()
+                    });
                 } else {
                     tim.tccr4a().modify(|_r, w| w.com4a().disconnected());
                 },
@@ -802,8 +1004,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PD7: {
                 ocr: ocr4d,
-                into_pwm: |tim| if enable {
-                    tim.tccr4c().modify(|_r, w| w.com4d().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr4c().modify(|_r, w| if inverted {
+                        w.com4d().match_set()
+                    } else {
+                        w.com4d().match_clear()
+                    });
                 } else {
                     tim.tccr4c().modify(|_r, w| w.com4d().disconnected());
                 },
@@ -812,6 +1018,508 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_complementary_pwm! {
+    timer: Timer4Pwm,
+    peripheral: crate::pac::TC4,
+    pins: {
+        PC7: {
+            complement: PC6,
+            enable: |tim| {
+                // COM4A = 0b01: OC4A and !OC4A connected
+                tim.tccr4a().modify(|_r, w| w.com4a().set(0b01));
+            },
+            dead_time: |tim, prescaler, output, complement| {
+                set_dead_time_tc4(tim, prescaler, output, complement);
+            },
+        },
+
+        PB6: {
+            complement: PB5,
+            enable: |tim| {
+                // COM4B = 0b01: OC4B and !OC4B connected
+                tim.tccr4a().modify(|_r, w| w.com4b().set(0b01));
+            },
+            dead_time: |tim, prescaler, output, complement| {
+                set_dead_time_tc4(tim, prescaler, output, complement);
+            },
+        },
+
+        PD7: {
+            complement: PD6,
+            enable: |tim| {
+                // COM4D = 0b01: OC4D and !OC4D connected
+                tim.tccr4c().modify(|_r, w| w.com4d().set(0b01));
+            },
+            dead_time: |tim, prescaler, output, complement| {
+                set_dead_time_tc4(tim, prescaler, output, complement);
+            },
+        },
+    },
+}
+
+/// The dead-time generator of `TC4` is shared by all of its outputs.
+#[cfg(feature = "atmega32u4")]
+fn set_dead_time_tc4(
+    tim: &crate::pac::tc4::RegisterBlock,
+    prescaler: DeadTimePrescaler,
+    output: u8,
+    complement: u8,
+) {
+    tim.tccr4b().modify(|_r, w| match prescaler {
+        DeadTimePrescaler::Direct => w.dtps4().x1(),
+        DeadTimePrescaler::Prescale2 => w.dtps4().x2(),
+        DeadTimePrescaler::Prescale4 => w.dtps4().x4(),
+        DeadTimePrescaler::Prescale8 => w.dtps4().x8(),
+    });
+    // DT4H delays OC4x, DT4L delays !OC4x.
+    tim.dt4().write(|w| w.dt4l().set(output << 4 | complement));
+}
+
+/// Clock source of [`Timer4HsPwm`].
+#[cfg(feature = "atmega32u4")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timer4Clock {
+    /// The system clock.
+    System,
+    /// The 96 MHz PLL output divided by 2.
+    Pll48MHz,
+    /// The 96 MHz PLL output divided by 1.5.
+    Pll64MHz,
+}
+
+#[cfg(feature = "atmega32u4")]
+impl Timer4Clock {
+    /// Frequency of this clock source in Hz.
+    pub const fn frequency<CLOCK: avr_hal_generic::clock::Clock>(self) -> u32 {
+        match self {
+            Timer4Clock::System => CLOCK::FREQ,
+            Timer4Clock::Pll48MHz => 48_000_000,
+            Timer4Clock::Pll64MHz => 64_000_000,
+        }
+    }
+}
+
+/// Input which trips the fault protection of [`Timer4HsPwm`].
+#[cfg(feature = "atmega32u4")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultSource {
+    /// The `INT0` pin (`PD0`).
+    Int0,
+    /// The output of the analog comparator.
+    AnalogComparator,
+}
+
+/// Edge of the fault input which trips the fault protection of [`Timer4HsPwm`].
+#[cfg(feature = "atmega32u4")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultEdge {
+    Falling,
+    Rising,
+}
+
+/// Use `TC4` as a 10-bit high-speed PWM timer (pins `PB6`, `PC7`, `PD7`)
+///
+/// Unlike [`Timer4Pwm`], this driver uses the full 10-bit resolution of the timer with the TOP
+/// value in `OCR4C`, so the PWM frequency is `clock / (prescaler * (top + 1))`.  `TC4` can run from
+/// the 64 MHz or 48 MHz PLL clock ([`Timer4Clock`]), for example for 62.5 kHz at full 10-bit
+/// resolution.  The maximum duty of the pins is TOP.
+///
+/// The PLL is started if it is not running yet.  A PLL which is already running, e.g. for USB,
+/// must output 96 MHz.
+///
+/// The complementary outputs `PB5`, `PC6` and `PD6` are available through [`ComplementaryPwm`].
+///
+/// # Fault protection
+/// An edge on the fault input ([`FaultSource`]) immediately disconnects all PWM outputs of the
+/// timer.  They stay disconnected until they are enabled again, after
+/// [`clear_fault()`][Timer4HsPwm::clear_fault].
+///
+/// # Example
+/// ```
+/// let mut timer4 = Timer4HsPwm::<CoreClock>::new(
+///     dp.TC4,
+///     &dp.PLL,
+///     Timer4Clock::Pll64MHz,
+///     62500.Hz(),
+/// )
+/// .unwrap();
+/// timer4.enable_fault_protection(FaultSource::Int0, FaultEdge::Falling, true);
+///
+/// let mut d10 = pins.d10.into_output().into_pwm(&timer4);
+/// d10.set_duty(512);
+/// d10.enable();
+/// ```
+#[cfg(feature = "atmega32u4")]
+pub struct Timer4HsPwm<CLOCK> {
+    timer: crate::pac::TC4,
+    clock: Timer4Clock,
+    prescaler: crate::timer::Prescaler,
+    top: u16,
+    _clock: core::marker::PhantomData<CLOCK>,
+}
+
+#[cfg(feature = "atmega32u4")]
+impl<CLOCK: avr_hal_generic::clock::Clock> Timer4HsPwm<CLOCK> {
+    /// Largest TOP value.
+    pub const MAX_TOP: u16 = 0x3ff;
+
+    /// Prescaler settings supported by `TC4`, in ascending order.
+    pub const PRESCALERS: &'static [crate::timer::Prescaler] = &[
+        crate::timer::Prescaler::Direct,
+        crate::timer::Prescaler::Prescale8,
+        crate::timer::Prescaler::Prescale32,
+        crate::timer::Prescaler::Prescale64,
+        crate::timer::Prescaler::Prescale128,
+        crate::timer::Prescaler::Prescale256,
+        crate::timer::Prescaler::Prescale1024,
+    ];
+
+    /// Start PWM with the highest resolution available for `frequency`.
+    pub fn new(
+        timer: crate::pac::TC4,
+        pll: &crate::pac::PLL,
+        clock: Timer4Clock,
+        frequency: crate::timer::Hertz,
+    ) -> Result<Self, crate::timer::TimerError> {
+        let (prescaler, top) = Self::settings(clock, frequency)?;
+        Self::with_top(timer, pll, clock, prescaler, top)
+    }
+
+    /// Start PWM with the given prescaler and TOP value.
+    ///
+    /// Fails with [`TimerError::PeriodTooLong`][crate::timer::TimerError::PeriodTooLong] if `top`
+    /// is larger than [`MAX_TOP`][Self::MAX_TOP].
+    pub fn with_top(
+        mut timer: crate::pac::TC4,
+        pll: &crate::pac::PLL,
+        clock: Timer4Clock,
+        prescaler: crate::timer::Prescaler,
+        top: u16,
+    ) -> Result<Self, crate::timer::TimerError> {
+        if top > Self::MAX_TOP {
+            return Err(crate::timer::TimerError::PeriodTooLong);
+        }
+
+        crate::power::PowerReductionOps::<crate::Atmega>::raw_set_powered(&mut timer, true);
+        timer.tccr4b().modify(|_r, w| w.cs4().no_clock());
+        match clock {
+            Timer4Clock::System => {
+                pll.pllfrq().modify(|_r, w| w.plltm().disconnected());
+            }
+            Timer4Clock::Pll48MHz => {
+                enable_pll::<CLOCK>(pll);
+                pll.pllfrq().modify(|_r, w| w.plltm().factor_2());
+            }
+            Timer4Clock::Pll64MHz => {
+                enable_pll::<CLOCK>(pll);
+                pll.pllfrq().modify(|_r, w| w.plltm().factor_15());
+            }
+        }
+
+        timer
+            .tccr4a()
+            .modify(|_r, w| w.pwm4a().set_bit().pwm4b().set_bit());
+        timer.tccr4c().modify(|_r, w| w.pwm4d().set_bit());
+        timer
+            .tccr4d()
+            .modify(|_r, w| w.wgm4().pwm_fast().fpf4().clear_bit());
+
+        let mut t = Self {
+            timer,
+            clock,
+            prescaler,
+            top,
+            _clock: core::marker::PhantomData,
+        };
+        t.set_top(prescaler, top)?;
+        Ok(t)
+    }
+
+    /// Change the PWM frequency, choosing the highest resolution available.
+    ///
+    /// The duty cycles of the pins are not rescaled, so set them again afterwards.
+    pub fn set_frequency(
+        &mut self,
+        frequency: crate::timer::Hertz,
+    ) -> Result<(), crate::timer::TimerError> {
+        let (prescaler, top) = Self::settings(self.clock, frequency)?;
+        self.set_top(prescaler, top)
+    }
+
+    /// Change the prescaler and the TOP value.
+    ///
+    /// Fails with [`TimerError::PeriodTooLong`][crate::timer::TimerError::PeriodTooLong] if `top`
+    /// is larger than [`MAX_TOP`][Self::MAX_TOP].
+    pub fn set_top(
+        &mut self,
+        prescaler: crate::timer::Prescaler,
+        top: u16,
+    ) -> Result<(), crate::timer::TimerError> {
+        use crate::timer::Prescaler;
+
+        if top > Self::MAX_TOP {
+            return Err(crate::timer::TimerError::PeriodTooLong);
+        }
+
+        self.timer.tccr4b().modify(|_r, w| w.cs4().no_clock());
+        write_tc4_10bit(top, |low| {
+            self.timer.ocr4c().write(|w| w.set(low));
+        });
+        write_tc4_10bit(0, |low| {
+            self.timer.tcnt4().write(|w| w.set(low));
+        });
+        self.timer.tccr4b().modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs4().direct(),
+            Prescaler::Prescale8 => w.cs4().prescale_8(),
+            Prescaler::Prescale32 => w.cs4().prescale_32(),
+            Prescaler::Prescale64 => w.cs4().prescale_64(),
+            Prescaler::Prescale128 => w.cs4().prescale_128(),
+            Prescaler::Prescale256 => w.cs4().prescale_256(),
+            Prescaler::Prescale1024 => w.cs4().prescale_1024(),
+        });
+
+        self.prescaler = prescaler;
+        self.top = top;
+        Ok(())
+    }
+
+    /// The clock source of the timer.
+    #[inline]
+    pub fn clock(&self) -> Timer4Clock {
+        self.clock
+    }
+
+    /// The prescaler of the timer clock.
+    #[inline]
+    pub fn prescaler(&self) -> crate::timer::Prescaler {
+        self.prescaler
+    }
+
+    /// TOP value of the counter, which is also the maximum duty.
+    #[inline]
+    pub fn top(&self) -> u16 {
+        self.top
+    }
+
+    /// The PWM frequency.
+    pub fn frequency(&self) -> crate::timer::Hertz {
+        crate::timer::Hertz::from_raw(
+            self.clock.frequency::<CLOCK>() / (self.prescaler.divider() * (self.top as u32 + 1)),
+        )
+    }
+
+    /// Disconnect all PWM outputs on an edge of the fault input.
+    ///
+    /// The noise canceler only trips after four equal samples of the input, which delays the
+    /// reaction by four clock cycles.
+    pub fn enable_fault_protection(
+        &mut self,
+        source: FaultSource,
+        edge: FaultEdge,
+        noise_canceler: bool,
+    ) {
+        self.timer.tccr4d().modify(|_r, w| {
+            w.fpac4()
+                .bit(source == FaultSource::AnalogComparator)
+                .fpes4()
+                .bit(edge == FaultEdge::Rising)
+                .fpnc4()
+                .bit(noise_canceler)
+                // Writing a one clears a stale fault flag.
+                .fpf4()
+                .set_bit()
+                .fpen4()
+                .set_bit()
+        });
+    }
+
+    /// Disable the fault protection.
+    pub fn disable_fault_protection(&mut self) {
+        self.timer
+            .tccr4d()
+            .modify(|_r, w| w.fpen4().clear_bit().fpie4().clear_bit().fpf4().clear_bit());
+    }
+
+    /// Whether the fault protection has tripped.
+    #[inline]
+    pub fn is_faulted(&self) -> bool {
+        self.timer.tccr4d().read().fpf4().bit_is_set()
+    }
+
+    /// Clear the fault flag.  The outputs must be enabled again afterwards.
+    pub fn clear_fault(&mut self) {
+        self.timer.tccr4d().modify(|_r, w| w.fpf4().set_bit());
+    }
+
+    /// Enable the `TIMER4_FPF` interrupt, which is raised when the fault protection trips.
+    ///
+    /// The flag is cleared by hardware when the interrupt handler runs.
+    pub fn listen_fault(&mut self) {
+        // A one in FPF4 would clear the flag, so write a zero.
+        self.timer
+            .tccr4d()
+            .modify(|_r, w| w.fpie4().set_bit().fpf4().clear_bit());
+    }
+
+    /// Disable the `TIMER4_FPF` interrupt.
+    pub fn unlisten_fault(&mut self) {
+        self.timer
+            .tccr4d()
+            .modify(|_r, w| w.fpie4().clear_bit().fpf4().clear_bit());
+    }
+
+    /// Give up the PWM timer and return the timer peripheral.
+    ///
+    /// The timer keeps running, so PWM outputs which were already set up continue to work.
+    pub fn into_timer(self) -> crate::pac::TC4 {
+        self.timer
+    }
+
+    fn settings(
+        clock: Timer4Clock,
+        frequency: crate::timer::Hertz,
+    ) -> Result<(crate::timer::Prescaler, u16), crate::timer::TimerError> {
+        let hz = frequency.to_Hz();
+        if hz == 0 {
+            return Err(crate::timer::TimerError::PeriodTooLong);
+        }
+        crate::timer::Prescaler::for_cycles(
+            clock.frequency::<CLOCK>() / hz,
+            Self::PRESCALERS,
+            Self::MAX_TOP,
+        )
+    }
+}
+
+/// Start the PLL with 96 MHz output, unless it is already running.
+#[cfg(feature = "atmega32u4")]
+fn enable_pll<CLOCK: avr_hal_generic::clock::Clock>(pll: &crate::pac::PLL) {
+    if pll.pllcsr().read().plle().bit_is_set() {
+        return;
+    }
+    // The PLL input must be 8 MHz, so a 16 MHz clock is divided by 2.
+    pll.pllcsr()
+        .write(|w| w.pindiv().bit(CLOCK::FREQ == 16_000_000));
+    pll.pllfrq().modify(|_r, w| w.pdiv().mhz96());
+    pll.pllcsr().modify(|_r, w| w.plle().set_bit());
+    while pll.pllcsr().read().plock().bit_is_clear() {}
+}
+
+#[cfg(feature = "atmega32u4")]
+macro_rules! impl_timer4_hs_pin {
+    (
+        $PXi:ident: {
+            complement: $NPXi:ident,
+            ocr: $ocr:ident,
+            enable: |$tim:ident, $com:ident| $enable_block:block,
+        },
+    ) => {
+        impl<CLOCK> PwmPinOps<Timer4HsPwm<CLOCK>> for $PXi {
+            type Duty = u16;
+
+            fn enable(&mut self) {
+                // SAFETY: The read-modify-write sequence is wrapped in a critical section.
+                avr_hal_generic::avr_device::interrupt::free(|_| {
+                    let $tim = unsafe { &*crate::pac::TC4::ptr() };
+                    let $com = 0b10;
+                    $enable_block
+                });
+            }
+
+            fn enable_inverted(&mut self) {
+                // SAFETY: See `enable()`.
+                avr_hal_generic::avr_device::interrupt::free(|_| {
+                    let $tim = unsafe { &*crate::pac::TC4::ptr() };
+                    let $com = 0b11;
+                    $enable_block
+                });
+            }
+
+            fn disable(&mut self) {
+                // SAFETY: See `enable()`.
+                avr_hal_generic::avr_device::interrupt::free(|_| {
+                    let $tim = unsafe { &*crate::pac::TC4::ptr() };
+                    let $com = 0b00;
+                    $enable_block
+                });
+            }
+
+            fn get_duty(&self) -> Self::Duty {
+                let tim = unsafe { &*crate::pac::TC4::ptr() };
+                read_tc4_10bit(|| tim.$ocr().read().bits())
+            }
+
+            fn get_max_duty(&self) -> Self::Duty {
+                let tim = unsafe { &*crate::pac::TC4::ptr() };
+                read_tc4_10bit(|| tim.ocr4c().read().bits())
+            }
+
+            fn set_duty(&mut self, duty: Self::Duty) {
+                let tim = unsafe { &*crate::pac::TC4::ptr() };
+                write_tc4_10bit(duty, |low| {
+                    tim.$ocr().write(|w| w.set(low));
+                });
+            }
+        }
+
+        impl<CLOCK> ComplementaryPwmOps<Timer4HsPwm<CLOCK>> for $PXi {
+            type Complement = $NPXi;
+
+            fn enable_complementary(&mut self) {
+                // SAFETY: The read-modify-write sequence is wrapped in a critical section.
+                avr_hal_generic::avr_device::interrupt::free(|_| {
+                    let $tim = unsafe { &*crate::pac::TC4::ptr() };
+                    // COM4x = 0b01: OC4x and !OC4x connected
+                    let $com = 0b01;
+                    $enable_block
+                });
+            }
+
+            fn set_dead_time(&mut self, prescaler: DeadTimePrescaler, output: u8, complement: u8) {
+                // SAFETY: The read-modify-write sequence is wrapped in a critical section.
+                avr_hal_generic::avr_device::interrupt::free(|_| {
+                    let tim = unsafe { &*crate::pac::TC4::ptr() };
+                    set_dead_time_tc4(tim, prescaler, output, complement);
+                });
+            }
+        }
+    };
+}
+
+#[cfg(feature = "atmega32u4")]
+impl_timer4_hs_pin! {
+    PC7: {
+        complement: PC6,
+        ocr: ocr4a,
+        enable: |tim, com| {
+            tim.tccr4a().modify(|_r, w| w.com4a().set(com));
+        },
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+impl_timer4_hs_pin! {
+    PB6: {
+        complement: PB5,
+        ocr: ocr4b,
+        enable: |tim, com| {
+            tim.tccr4a().modify(|_r, w| w.com4b().set(com));
+        },
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+impl_timer4_hs_pin! {
+    PD7: {
+        complement: PD6,
+        ocr: ocr4d,
+        enable: |tim, com| {
+            tim.tccr4c().modify(|_r, w| w.com4d().set(com));
+        },
+    },
+}
+
 #[cfg(any(feature = "atmega1284p"))]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC0` for PWM (pins `PB3`, `PB4`)
@@ -827,9 +1535,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// b4.enable();
     /// ```
     pub struct Timer0Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC0,
-        init: |tim, prescaler| {
-            tim.tccr0a().modify(|_r, w| w.wgm0().pwm_fast());
+        modes: [Fast, PhaseCorrect],
+        init: |tim, prescaler, mode| {
+            tim.tccr0a().modify(|_r, w| match mode {
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+                _ => w.wgm0().pwm_fast(),
+            });
             tim.tccr0b().modify(|_r, w| match prescaler {
                 Prescaler::Direct => w.cs0().direct(),
                 Prescaler::Prescale8 => w.cs0().prescale_8(),
@@ -841,8 +1554,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB3: {
                 ocr: ocr0a,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0a().match_set()
+                    } else {
+                        w.com0a().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0a().disconnected());
                 },
@@ -850,8 +1567,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PB4: {
                 ocr: ocr0b,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0b().match_set()
+                    } else {
+                        w.com0b().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0b().disconnected());
                 },
@@ -875,11 +1596,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d5.enable();
     /// ```
     pub struct Timer1Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC1,
-        init: |tim, prescaler| {
-            tim.tccr1a().modify(|_r, w| w.wgm1().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr1,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
             tim.tccr1b().modify(|_r, w| {
-                w.wgm1().set(0b01);
+                w.wgm1().set(wgm_b);
 
                 match prescaler {
                     Prescaler::Direct => w.cs1().direct(),
@@ -893,8 +1617,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PD5: {
                 ocr: ocr1a,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1a().match_set()
+                    } else {
+                        w.com1a().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1a().disconnected());
                 },
@@ -902,8 +1630,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PD4: {
                 ocr: ocr1b,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1b().match_set()
+                    } else {
+                        w.com1b().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1b().disconnected());
                 },
@@ -927,9 +1659,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d7.enable();
     /// ```
     pub struct Timer2Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC2,
-        init: |tim, prescaler| {
-            tim.tccr2a().modify(|_r, w| w.wgm2().pwm_fast());
+        modes: [Fast, PhaseCorrect],
+        init: |tim, prescaler, mode| {
+            tim.tccr2a().modify(|_r, w| match mode {
+                PwmMode::PhaseCorrect => w.wgm2().pwm_phase(),
+                _ => w.wgm2().pwm_fast(),
+            });
             tim.tccr2b().modify(|_r, w| match prescaler {
                     Prescaler::Direct => w.cs2().direct(),
                     Prescaler::Prescale8 => w.cs2().prescale_8(),
@@ -941,8 +1678,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PD7: {
                 ocr: ocr2a,
-                into_pwm: |tim| if enable {
-                    tim.tccr2a().modify(|_r, w| w.com2a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr2a().modify(|_r, w| if inverted {
+                        w.com2a().match_set()
+                    } else {
+                        w.com2a().match_clear()
+                    });
                 } else {
                     tim.tccr2a().modify(|_r, w| w.com2a().disconnected());
                 },
@@ -950,8 +1691,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PD6: {
                 ocr: ocr2b,
-                into_pwm: |tim| if enable {
-                    tim.tccr2a().modify(|_r, w| w.com2b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr2a().modify(|_r, w| if inverted {
+                        w.com2b().match_set()
+                    } else {
+                        w.com2b().match_clear()
+                    });
                 } else {
                     tim.tccr2a().modify(|_r, w| w.com2b().disconnected());
                 },
@@ -964,11 +1709,14 @@ avr_hal_generic::impl_simple_pwm! {
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC3` for PWM (pins `PB6`, `PB7`)
     pub struct Timer3Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC3,
-        init: |tim, prescaler| {
-            tim.tccr3a().modify(|_r, w| w.wgm3().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr3,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr3a().modify(|_r, w| w.wgm3().set(wgm_a));
             tim.tccr3b().modify(|_r, w| {
-                w.wgm3().set(0b01);
+                w.wgm3().set(wgm_b);
 
                 match prescaler {
                     Prescaler::Direct => w.cs3().direct(),
@@ -982,8 +1730,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB6: {
                 ocr: ocr3a,
-                into_pwm: |tim| if enable {
-                    tim.tccr3a().modify(|_r, w| w.com3a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr3a().modify(|_r, w| if inverted {
+                        w.com3a().match_set()
+                    } else {
+                        w.com3a().match_clear()
+                    });
                 } else {
                     tim.tccr3a().modify(|_r, w| w.com3a().disconnected());
                 },
@@ -991,8 +1743,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PB7: {
                 ocr: ocr3b,
-                into_pwm: |tim| if enable {
-                    tim.tccr3a().modify(|_r, w| w.com3b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr3a().modify(|_r, w| if inverted {
+                        w.com3b().match_set()
+                    } else {
+                        w.com3b().match_clear()
+                    });
                 } else {
                     tim.tccr3a().modify(|_r, w| w.com3b().disconnected());
                 },
@@ -1016,11 +1772,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d9.enable();
     /// ```
     pub struct Timer1Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC1,
-        init: |tim, prescaler| {
-            tim.tccr1a().modify(|_r, w| w.wgm1().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr1,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
             tim.tccr1b().modify(|_r, w| {
-                w.wgm1().set(0b01);
+                w.wgm1().set(wgm_b);
 
                 match prescaler {
                     Prescaler::Direct => w.cs1().direct(),
@@ -1034,8 +1793,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB1: {
                 ocr: ocr1a,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1a().match_set()
+                    } else {
+                        w.com1a().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1a().disconnected());
                 },
@@ -1043,8 +1806,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PB2: {
                 ocr: ocr1b,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| if inverted {
+                        w.com1b().match_set()
+                    } else {
+                        w.com1b().match_clear()
+                    });
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1b().disconnected());
                 },
@@ -1068,9 +1835,11 @@ avr_hal_generic::impl_simple_pwm! {
     /// d11.enable();
     /// ```
     pub struct Timer2Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC2,
-        init: |tim, prescaler| {
-            tim.tccr2().modify(|_r, w| w.wgm20().set_bit().wgm21().set_bit());
+        modes: [Fast, PhaseCorrect],
+        init: |tim, prescaler, mode| {
+            tim.tccr2().modify(|_r, w| w.wgm20().set_bit().wgm21().bit(mode == PwmMode::Fast));
             tim.tccr2().modify(|_r, w| match prescaler {
                     Prescaler::Direct => w.cs2().direct(),
                     Prescaler::Prescale8 => w.cs2().prescale_8(),
@@ -1082,8 +1851,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB3: {
                 ocr: ocr2,
-                into_pwm: |tim| if enable {
-                    tim.tccr2().modify(|_r, w| w.com2().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr2().modify(|_r, w| if inverted {
+                        w.com2().match_set()
+                    } else {
+                        w.com2().match_clear()
+                    });
                 } else {
                     tim.tccr2().modify(|_r, w| w.com2().disconnected());
                 },
@@ -1106,9 +1879,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// b3.enable();
     /// ```
     pub struct Timer0Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC0,
-        init: |tim, prescaler| {
-            tim.tccr0a().modify(|_r, w| w.wgm0().set(0b11));
+        modes: [Fast, PhaseCorrect],
+        init: |tim, prescaler, mode| {
+            tim.tccr0a().modify(|_r, w| w.wgm0().set(match mode {
+                PwmMode::PhaseCorrect => 0b01,
+                _ => 0b11,
+            }));
             tim.tccr0a().modify(|_r, w| w.com0a().set(0b00));
 
             tim.tccr0b().modify(|_r, w| match prescaler {
@@ -1122,8 +1900,8 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB3: {
                 ocr: ocr0a,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0a().set(0b11));
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| w.com0a().set(if inverted { 0b11 } else { 0b10 }));
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0a().set(0b00));
                 },
@@ -1136,6 +1914,9 @@ avr_hal_generic::impl_simple_pwm! {
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC1` for PWM (pins `PD4`, `PD5`)
     ///
+    /// [`new()`][Self::new] starts the timer in phase-correct mode, use
+    /// [`with_mode()`][Self::with_mode] for fast PWM.
+    ///
     /// # Example
     /// ```
     /// let mut timer1 = Timer1Pwm::new(dp.TC1, Prescaler::Prescale64);
@@ -1149,9 +1930,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d5.enable();
     /// ```
     pub struct Timer1Pwm {
+        hal: crate::Atmega,
         timer: crate::pac::TC1,
-        init: |tim, prescaler| {
-            tim.tccr1a().modify(|_r, w| w.wgm1().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        default_mode: PhaseCorrect,
+        top: icr1,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
+            tim.tccr1b().modify(|_r, w| w.wgm1().set(wgm_b));
             tim.tccr1a().modify(|_r, w| w.com1a().set(0b00));
             tim.tccr1a().modify(|_r, w| w.com1b().set(0b00));
             #[cfg(any(feature = "atmega164pa"))]
@@ -1174,16 +1960,16 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PD4: {
                 ocr: ocr1a,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1a().set(0b11));
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| w.com1a().set(if inverted { 0b11 } else { 0b10 }));
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1a().set(0b00));
                 },
             },
             PD5: {
                 ocr: ocr1b,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_r, w| w.com1b().set(0b11));
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_r, w| w.com1b().set(if inverted { 0b11 } else { 0b10 }));
                 } else {
                     tim.tccr1a().modify(|_r, w| w.com1b().set(0b00));
                 },
@@ -1191,3 +1977,549 @@ avr_hal_generic::impl_simple_pwm! {
         },
     }
 }
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC1,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
+        tim.tccr1b().modify(|_r, w| w.wgm1().set(wgm_b));
+    },
+    top: icr1,
+    pins: {
+        PB1: {
+            ocr: ocr1a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1a().match_set()
+                } else {
+                    w.com1a().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1a().disconnected());
+            },
+        },
+
+        PB2: {
+            ocr: ocr1b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1b().match_set()
+                } else {
+                    w.com1b().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1b().disconnected());
+            },
+        },
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC3,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr3a().modify(|_r, w| w.wgm3().set(wgm_a));
+        tim.tccr3b().modify(|_r, w| unsafe { w.wgm3().bits(wgm_b) });
+    },
+    top: icr3,
+    pins: {
+        PD0: {
+            ocr: ocr3a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr3a().modify(|_r, w| if inverted {
+                    w.com3a().match_set()
+                } else {
+                    w.com3a().match_clear()
+                });
+            } else {
+                tim.tccr3a().modify(|_r, w| w.com3a().disconnected());
+            },
+        },
+
+        PD2: {
+            ocr: ocr3b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr3a().modify(|_r, w| if inverted {
+                    w.com3b().match_set()
+                } else {
+                    w.com3b().match_clear()
+                });
+            } else {
+                tim.tccr3a().modify(|_r, w| w.com3b().disconnected());
+            },
+        },
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC4,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr4a().modify(|_r, w| w.wgm4().set(wgm_a));
+        tim.tccr4b().modify(|_r, w| unsafe { w.wgm4().bits(wgm_b) });
+    },
+    top: icr4,
+    pins: {
+        PD1: {
+            ocr: ocr4a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr4a().modify(|_r, w| if inverted {
+                    w.com4a().match_set()
+                } else {
+                    w.com4a().match_clear()
+                });
+            } else {
+                tim.tccr4a().modify(|_r, w| w.com4a().disconnected());
+            },
+        },
+
+        PD2: {
+            ocr: ocr4b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr4a().modify(|_r, w| if inverted {
+                    w.com4b().match_set()
+                } else {
+                    w.com4b().match_clear()
+                });
+            } else {
+                tim.tccr4a().modify(|_r, w| w.com4b().disconnected());
+            },
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC1,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
+        tim.tccr1b().modify(|_r, w| w.wgm1().set(wgm_b));
+    },
+    top: icr1,
+    pins: {
+        PB5: {
+            ocr: ocr1a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1a().match_set()
+                } else {
+                    w.com1a().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1a().disconnected());
+            },
+        },
+
+        PB6: {
+            ocr: ocr1b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1b().match_set()
+                } else {
+                    w.com1b().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1b().disconnected());
+            },
+        },
+
+        PB7: {
+            ocr: ocr1c,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1c().match_set()
+                } else {
+                    w.com1c().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1c().disconnected());
+            },
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC3,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr3a().modify(|_r, w| w.wgm3().set(wgm_a));
+        tim.tccr3b().modify(|_r, w| w.wgm3().set(wgm_b));
+    },
+    top: icr3,
+    pins: {
+        PE3: {
+            ocr: ocr3a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr3a().modify(|_r, w| if inverted {
+                    w.com3a().match_set()
+                } else {
+                    w.com3a().match_clear()
+                });
+            } else {
+                tim.tccr3a().modify(|_r, w| w.com3a().disconnected());
+            },
+        },
+
+        PE4: {
+            ocr: ocr3b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr3a().modify(|_r, w| if inverted {
+                    w.com3b().match_set()
+                } else {
+                    w.com3b().match_clear()
+                });
+            } else {
+                tim.tccr3a().modify(|_r, w| w.com3b().disconnected());
+            },
+        },
+
+        PE5: {
+            ocr: ocr3c,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr3a().modify(|_r, w| if inverted {
+                    w.com3c().match_set()
+                } else {
+                    w.com3c().match_clear()
+                });
+            } else {
+                tim.tccr3a().modify(|_r, w| w.com3c().disconnected());
+            },
+        },
+
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC4,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr4a().modify(|_r, w| w.wgm4().set(wgm_a));
+        tim.tccr4b().modify(|_r, w| w.wgm4().set(wgm_b));
+    },
+    top: icr4,
+    pins: {
+        PH3: {
+            ocr: ocr4a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr4a().modify(|_r, w| if inverted {
+                    w.com4a().match_set()
+                } else {
+                    w.com4a().match_clear()
+                });
+            } else {
+                tim.tccr4a().modify(|_r, w| w.com4a().disconnected());
+            },
+        },
+
+        PH4: {
+            ocr: ocr4b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr4a().modify(|_r, w| if inverted {
+                    w.com4b().match_set()
+                } else {
+                    w.com4b().match_clear()
+                });
+            } else {
+                tim.tccr4a().modify(|_r, w| w.com4b().disconnected());
+            },
+        },
+
+        PH5: {
+            ocr: ocr4c,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr4a().modify(|_r, w| if inverted {
+                    w.com4c().match_set()
+                } else {
+                    w.com4c().match_clear()
+                });
+            } else {
+                tim.tccr4a().modify(|_r, w| w.com4c().disconnected());
+            },
+        },
+
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC5,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr5a().modify(|_r, w| w.wgm5().set(wgm_a));
+        tim.tccr5b().modify(|_r, w| w.wgm5().set(wgm_b));
+    },
+    top: icr5,
+    pins: {
+        PL3: {
+            ocr: ocr5a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr5a().modify(|_r, w| if inverted {
+                    w.com5a().match_set()
+                } else {
+                    w.com5a().match_clear()
+                });
+            } else {
+                tim.tccr5a().modify(|_r, w| w.com5a().disconnected());
+            },
+        },
+
+        PL4: {
+            ocr: ocr5b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr5a().modify(|_r, w| if inverted {
+                    w.com5b().match_set()
+                } else {
+                    w.com5b().match_clear()
+                });
+            } else {
+                tim.tccr5a().modify(|_r, w| w.com5b().disconnected());
+            },
+        },
+
+        PL5: {
+            ocr: ocr5c,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr5a().modify(|_r, w| if inverted {
+                    w.com5c().match_set()
+                } else {
+                    w.com5c().match_clear()
+                });
+            } else {
+                tim.tccr5a().modify(|_r, w| w.com5c().disconnected());
+            },
+        },
+
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC1,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
+        tim.tccr1b().modify(|_r, w| w.wgm1().set(wgm_b));
+    },
+    top: icr1,
+    pins: {
+        PB5: {
+            ocr: ocr1a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1a().match_set()
+                } else {
+                    w.com1a().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1a().disconnected());
+            },
+        },
+
+        PB6: {
+            ocr: ocr1b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1b().match_set()
+                } else {
+                    w.com1b().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1b().disconnected());
+            },
+        },
+
+        PB7: {
+            ocr: ocr1c,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1c().match_set()
+                } else {
+                    w.com1c().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1c().disconnected());
+            },
+        },
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC3,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr3a().modify(|_r, w| w.wgm3().set(wgm_a));
+        tim.tccr3b().modify(|_r, w| w.wgm3().set(wgm_b));
+    },
+    top: icr3,
+    pins: {
+        PC6: {
+            ocr: ocr3a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr3a().modify(|_r, w| if inverted {
+                    w.com3a().match_set()
+                } else {
+                    w.com3a().match_clear()
+                });
+            } else {
+                tim.tccr3a().modify(|_r, w| w.com3a().disconnected());
+            },
+        },
+    },
+}
+
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC1,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
+        tim.tccr1b().modify(|_r, w| w.wgm1().set(wgm_b));
+    },
+    top: icr1,
+    pins: {
+        PD5: {
+            ocr: ocr1a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1a().match_set()
+                } else {
+                    w.com1a().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1a().disconnected());
+            },
+        },
+
+        PD4: {
+            ocr: ocr1b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1b().match_set()
+                } else {
+                    w.com1b().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1b().disconnected());
+            },
+        },
+    },
+}
+
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC3,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr3a().modify(|_r, w| w.wgm3().set(wgm_a));
+        tim.tccr3b().modify(|_r, w| w.wgm3().set(wgm_b));
+    },
+    top: icr3,
+    pins: {
+        PB6: {
+            ocr: ocr3a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr3a().modify(|_r, w| if inverted {
+                    w.com3a().match_set()
+                } else {
+                    w.com3a().match_clear()
+                });
+            } else {
+                tim.tccr3a().modify(|_r, w| w.com3a().disconnected());
+            },
+        },
+
+        PB7: {
+            ocr: ocr3b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr3a().modify(|_r, w| if inverted {
+                    w.com3b().match_set()
+                } else {
+                    w.com3b().match_clear()
+                });
+            } else {
+                tim.tccr3a().modify(|_r, w| w.com3b().disconnected());
+            },
+        },
+    },
+}
+
+#[cfg(feature = "atmega8")]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC1,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
+        tim.tccr1b().modify(|_r, w| w.wgm1().set(wgm_b));
+    },
+    top: icr1,
+    pins: {
+        PB1: {
+            ocr: ocr1a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1a().match_set()
+                } else {
+                    w.com1a().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1a().disconnected());
+            },
+        },
+
+        PB2: {
+            ocr: ocr1b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| if inverted {
+                    w.com1b().match_set()
+                } else {
+                    w.com1b().match_clear()
+                });
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1b().disconnected());
+            },
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega16", feature = "atmega164pa"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Atmega,
+    timer: crate::pac::TC1,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
+        tim.tccr1b().modify(|_r, w| w.wgm1().set(wgm_b));
+    },
+    top: icr1,
+    pins: {
+        PD4: {
+            ocr: ocr1a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| w.com1a().set(if inverted { 0b11 } else { 0b10 }));
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1a().set(0b00));
+            },
+        },
+        PD5: {
+            ocr: ocr1b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_r, w| w.com1b().set(if inverted { 0b11 } else { 0b10 }));
+            } else {
+                tim.tccr1a().modify(|_r, w| w.com1b().set(0b00));
+            },
+        },
+    },
+}
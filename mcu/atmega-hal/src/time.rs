@@ -0,0 +1,24 @@
+//! Monotonic system time
+//!
+//! Check the [`avr_hal_generic::time`] documentation for details.
+//!
+//! # Example
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//!
+//! let systime = SysTime::<_, CoreClock>::new(dp.TC1, 1.kHz()).unwrap();
+//! unsafe { avr_device::interrupt::enable() };
+//!
+//! let start = systime.now();
+//! while systime.now() - start < 500.millis() {}
+//!
+//! #[avr_device::interrupt(atmega328p)]
+//! fn TIMER1_COMPA() {
+//!     atmega_hal::time::tick();
+//! }
+//! ```
+
+pub use avr_hal_generic::time::{tick, MicrosInstant, MillisInstant};
+
+/// Check the [`avr_hal_generic::time::SysTime`] documentation.
+pub type SysTime<TC, CLOCK> = avr_hal_generic::time::SysTime<crate::Atmega, TC, CLOCK>;
@@ -0,0 +1,115 @@
+//! [`embassy-time`](https://docs.rs/embassy-time) driver on `TC1`
+//!
+//! Enabled with the `time-driver-tc1` feature, which also defines the `TIMER1_OVF` and
+//! `TIMER1_COMPA` interrupt handlers.  Check the [`avr_hal_generic::time_driver`] documentation
+//! for details on choosing the tick rate.
+//!
+//! # Example
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//!
+//! atmega_hal::time_driver::init::<CoreClock>(dp.TC1);
+//! atmega_hal::time_driver::enable_idle_sleep();
+//!
+//! // ... start the embassy executor
+//! ```
+
+pub use avr_hal_generic::time_driver::TICK_HZ;
+
+type TimeDriver = avr_hal_generic::time_driver::TimeDriver<crate::Atmega, crate::pac::TC1>;
+
+avr_hal_generic::time_driver::embassy_time_driver::time_driver_impl!(
+    static DRIVER: TimeDriver = TimeDriver::new()
+);
+
+/// Start the time driver on `TC1`.
+///
+/// The clock of `TC1` is started in the Power Reduction Register, if necessary.  Global interrupts
+/// must be enabled for the time to advance.
+///
+/// # Panics
+/// Panics if `TC1` cannot be clocked at [`TICK_HZ`] from the given core clock.
+pub fn init<CLOCK: avr_hal_generic::clock::Clock>(tc1: crate::pac::TC1) {
+    DRIVER.init::<CLOCK>(tc1);
+}
+
+/// Select the *Idle* sleep mode and enable sleeping.
+///
+/// In *Idle* mode, the timers keep running so the CPU is woken up by the next alarm.  After
+/// this, every `sleep` instruction puts the CPU to sleep until the next interrupt, which is what
+/// the executor of `embassy-executor` does while there is no work.
+pub fn enable_idle_sleep() {
+    // SAFETY: Only the sleep control bits are touched and the read-modify-write sequence is
+    // wrapped in a critical section.
+    let cpu = unsafe { &*crate::pac::CPU::ptr() };
+    avr_device::interrupt::free(|_| {
+        #[cfg(not(any(
+            feature = "atmega8",
+            feature = "atmega16",
+            feature = "atmega32a",
+            feature = "atmega128a"
+        )))]
+        cpu.smcr().modify(|_, w| w.sm().idle().se().set_bit());
+        #[cfg(any(feature = "atmega8", feature = "atmega16", feature = "atmega32a"))]
+        cpu.mcucr().modify(|_, w| w.sm().idle().se().set_bit());
+        #[cfg(feature = "atmega128a")]
+        cpu.mcucr()
+            .modify(|_, w| w.sm2().idle().sm().set(0b00).se().set_bit());
+    });
+}
+
+/// Low-power idle hook: Sleep until the next interrupt.
+///
+/// This is meant to be called by a custom executor loop when no task is ready to run.  It must be
+/// called with interrupts disabled, so a task that was woken right before cannot be missed.
+/// Interrupts are enabled again when this function returns.
+///
+/// # Safety
+/// This enables interrupts, so it must not be called inside a critical section.
+pub unsafe fn idle() {
+    enable_idle_sleep();
+    avr_hal_generic::time_driver::enable_interrupts_and_sleep();
+}
+
+macro_rules! impl_interrupts {
+    ($chip:ident) => {
+        #[avr_device::interrupt($chip)]
+        fn TIMER1_OVF() {
+            DRIVER.on_overflow();
+        }
+
+        #[avr_device::interrupt($chip)]
+        fn TIMER1_COMPA() {
+            DRIVER.on_compare();
+        }
+    };
+}
+
+#[cfg(feature = "atmega48p")]
+impl_interrupts!(atmega48p);
+#[cfg(feature = "atmega16")]
+impl_interrupts!(atmega16);
+#[cfg(feature = "atmega164pa")]
+impl_interrupts!(atmega164pa);
+#[cfg(feature = "atmega168")]
+impl_interrupts!(atmega168);
+#[cfg(feature = "atmega328p")]
+impl_interrupts!(atmega328p);
+#[cfg(feature = "atmega328pb")]
+impl_interrupts!(atmega328pb);
+#[cfg(feature = "atmega32a")]
+impl_interrupts!(atmega32a);
+#[cfg(feature = "atmega32u4")]
+impl_interrupts!(atmega32u4);
+#[cfg(feature = "atmega2560")]
+impl_interrupts!(atmega2560);
+#[cfg(feature = "atmega128a")]
+impl_interrupts!(atmega128a);
+#[cfg(feature = "atmega1280")]
+impl_interrupts!(atmega1280);
+#[cfg(feature = "atmega1284p")]
+impl_interrupts!(atmega1284p);
+#[cfg(feature = "atmega8")]
+impl_interrupts!(atmega8);
+#[cfg(feature = "atmega88p")]
+impl_interrupts!(atmega88p);
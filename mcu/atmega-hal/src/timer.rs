@@ -0,0 +1,822 @@
+//! General-purpose Timer/Counter
+//!
+//! Check the [`avr_hal_generic::timer`] documentation for details.
+//!
+//! # Example
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//!
+//! let mut timer = Timer::<_, CoreClock>::new(dp.TC1);
+//! timer.start(10.Hz()).unwrap();
+//!
+//! loop {
+//!     nb::block!(timer.wait()).unwrap_infallible();
+//!     // Executed 10 times per second
+//! }
+//! ```
+
+pub use avr_hal_generic::timer::{
+    Duration, Event, Hertz, OutputCompare, Prescaler, TimerError, TimerMode, TimerOps,
+};
+
+/// Check the [`avr_hal_generic::timer::Timer`] documentation.
+pub type Timer<TC, CLOCK> = avr_hal_generic::timer::Timer<crate::Atmega, TC, CLOCK>;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1284p",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4",
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr0b().modify(|_, w| match prescaler {
+                Some(Prescaler::Direct) => w.cs0().direct(),
+                Some(Prescaler::Prescale8) => w.cs0().prescale_8(),
+                Some(Prescaler::Prescale64) => w.cs0().prescale_64(),
+                Some(Prescaler::Prescale256) => w.cs0().prescale_256(),
+                Some(Prescaler::Prescale1024) => w.cs0().prescale_1024(),
+                _ => w.cs0().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr0a().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm0().normal_top(),
+            TimerMode::Ctc => w.wgm0().ctc(),
+        });
+        tim.tccr0b().modify(|_, w| w.wgm02().clear_bit());
+    },
+    registers: {
+        counter: tcnt0,
+        compare_a: ocr0a,
+        compare_b: ocr0b,
+        mask: timsk0,
+        flags: tifr0,
+    },
+    interrupts: {
+        overflow: (toie0, tov0),
+        compare_a: (ocie0a, ocf0a),
+        compare_b: (ocie0b, ocf0b),
+    },
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1284p",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4",
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr1b().modify(|_, w| match prescaler {
+                Some(Prescaler::Direct) => w.cs1().direct(),
+                Some(Prescaler::Prescale8) => w.cs1().prescale_8(),
+                Some(Prescaler::Prescale64) => w.cs1().prescale_64(),
+                Some(Prescaler::Prescale256) => w.cs1().prescale_256(),
+                Some(Prescaler::Prescale1024) => w.cs1().prescale_1024(),
+                _ => w.cs1().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr1a().modify(|_, w| w.wgm1().set(0b00));
+        tim.tccr1b().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm1().set(0b00),
+            TimerMode::Ctc => w.wgm1().set(0b01),
+        });
+    },
+    registers: {
+        counter: tcnt1,
+        compare_a: ocr1a,
+        compare_b: ocr1b,
+        mask: timsk1,
+        flags: tifr1,
+    },
+    interrupts: {
+        overflow: (toie1, tov1),
+        compare_a: (ocie1a, ocf1a),
+        compare_b: (ocie1b, ocf1b),
+    },
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1284p",
+    feature = "atmega1280",
+    feature = "atmega2560",
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale32, Prescale64, Prescale128, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr2b().modify(|_, w| match prescaler {
+                Some(Prescaler::Direct) => w.cs2().direct(),
+                Some(Prescaler::Prescale8) => w.cs2().prescale_8(),
+                Some(Prescaler::Prescale32) => w.cs2().prescale_32(),
+                Some(Prescaler::Prescale64) => w.cs2().prescale_64(),
+                Some(Prescaler::Prescale128) => w.cs2().prescale_128(),
+                Some(Prescaler::Prescale256) => w.cs2().prescale_256(),
+                Some(Prescaler::Prescale1024) => w.cs2().prescale_1024(),
+                _ => w.cs2().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr2a().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm2().normal_top(),
+            TimerMode::Ctc => w.wgm2().ctc(),
+        });
+        tim.tccr2b().modify(|_, w| w.wgm22().clear_bit());
+    },
+    registers: {
+        counter: tcnt2,
+        compare_a: ocr2a,
+        compare_b: ocr2b,
+        mask: timsk2,
+        flags: tifr2,
+    },
+    interrupts: {
+        overflow: (toie2, tov2),
+        compare_a: (ocie2a, ocf2a),
+        compare_b: (ocie2b, ocf2b),
+    },
+}
+
+#[cfg(any(
+    feature = "atmega1284p",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4",
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    counter: u16,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr3b().modify(|_, w| match prescaler {
+                Some(Prescaler::Direct) => w.cs3().direct(),
+                Some(Prescaler::Prescale8) => w.cs3().prescale_8(),
+                Some(Prescaler::Prescale64) => w.cs3().prescale_64(),
+                Some(Prescaler::Prescale256) => w.cs3().prescale_256(),
+                Some(Prescaler::Prescale1024) => w.cs3().prescale_1024(),
+                _ => w.cs3().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr3a().modify(|_, w| w.wgm3().set(0b00));
+        tim.tccr3b().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm3().set(0b00),
+            TimerMode::Ctc => w.wgm3().set(0b01),
+        });
+    },
+    registers: {
+        counter: tcnt3,
+        compare_a: ocr3a,
+        compare_b: ocr3b,
+        mask: timsk3,
+        flags: tifr3,
+    },
+    interrupts: {
+        overflow: (toie3, tov3),
+        compare_a: (ocie3a, ocf3a),
+        compare_b: (ocie3b, ocf3b),
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    counter: u16,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr3b().modify(|_, w| match prescaler {
+                Some(Prescaler::Direct) => w.cs3().direct(),
+                Some(Prescaler::Prescale8) => w.cs3().prescale_8(),
+                Some(Prescaler::Prescale64) => w.cs3().prescale_64(),
+                Some(Prescaler::Prescale256) => w.cs3().prescale_256(),
+                Some(Prescaler::Prescale1024) => w.cs3().prescale_1024(),
+                _ => w.cs3().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        let wgm = match mode {
+            TimerMode::Normal => 0b00,
+            TimerMode::Ctc => 0b01,
+        };
+        tim.tccr3a().modify(|_, w| w.wgm3().set(0b00));
+        // SAFETY: The PAC of the ATmega328PB has no safe writer for the upper WGM3 bits, but both
+        // values select a valid mode.
+        tim.tccr3b().modify(|_, w| unsafe { w.wgm3().bits(wgm) });
+    },
+    registers: {
+        counter: tcnt3,
+        compare_a: ocr3a,
+        compare_b: ocr3b,
+        mask: timsk3,
+        flags: tifr3,
+    },
+    interrupts: {
+        overflow: (toie3, tov3),
+        compare_a: (ocie3a, ocf3a),
+        compare_b: (ocie3b, ocf3b),
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    counter: u16,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr4b().modify(|_, w| match prescaler {
+                Some(Prescaler::Direct) => w.cs4().direct(),
+                Some(Prescaler::Prescale8) => w.cs4().prescale_8(),
+                Some(Prescaler::Prescale64) => w.cs4().prescale_64(),
+                Some(Prescaler::Prescale256) => w.cs4().prescale_256(),
+                Some(Prescaler::Prescale1024) => w.cs4().prescale_1024(),
+                _ => w.cs4().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr4a().modify(|_, w| w.wgm4().set(0b00));
+        tim.tccr4b().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm4().set(0b00),
+            TimerMode::Ctc => w.wgm4().set(0b01),
+        });
+    },
+    registers: {
+        counter: tcnt4,
+        compare_a: ocr4a,
+        compare_b: ocr4b,
+        mask: timsk4,
+        flags: tifr4,
+    },
+    interrupts: {
+        overflow: (toie4, tov4),
+        compare_a: (ocie4a, ocf4a),
+        compare_b: (ocie4b, ocf4b),
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    counter: u16,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr4b().modify(|_, w| match prescaler {
+                Some(Prescaler::Direct) => w.cs4().direct(),
+                Some(Prescaler::Prescale8) => w.cs4().prescale_8(),
+                Some(Prescaler::Prescale64) => w.cs4().prescale_64(),
+                Some(Prescaler::Prescale256) => w.cs4().prescale_256(),
+                Some(Prescaler::Prescale1024) => w.cs4().prescale_1024(),
+                _ => w.cs4().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        let wgm = match mode {
+            TimerMode::Normal => 0b00,
+            TimerMode::Ctc => 0b01,
+        };
+        tim.tccr4a().modify(|_, w| w.wgm4().set(0b00));
+        // SAFETY: The PAC of the ATmega328PB has no safe writer for the upper WGM4 bits, but both
+        // values select a valid mode.
+        tim.tccr4b().modify(|_, w| unsafe { w.wgm4().bits(wgm) });
+    },
+    registers: {
+        counter: tcnt4,
+        compare_a: ocr4a,
+        compare_b: ocr4b,
+        mask: timsk4,
+        flags: tifr4,
+    },
+    interrupts: {
+        overflow: (toie4, tov4),
+        compare_a: (ocie4a, ocf4a),
+        compare_b: (ocie4b, ocf4b),
+    },
+}
+
+/// Write a 10-bit register of `TC4`: the two high bits go through the shared `TC4H` register,
+/// which must be written right before the low byte.  `TC4H` follows the same rules as the TEMP
+/// register of the 16-bit timers, see [`avr_hal_generic::timer::access_16bit()`].
+#[cfg(feature = "atmega32u4")]
+pub(crate) fn write_tc4_10bit(value: u16, write_low: impl FnOnce(u8)) {
+    avr_hal_generic::timer::access_16bit(|| {
+        let tim = unsafe { &*crate::pac::TC4::ptr() };
+        tim.tc4h().write(|w| w.set((value >> 8) as u8));
+        write_low(value as u8);
+    });
+}
+
+/// Read a 10-bit register of `TC4`: reading the low byte latches the high bits into `TC4H`.
+#[cfg(feature = "atmega32u4")]
+pub(crate) fn read_tc4_10bit(read_low: impl FnOnce() -> u8) -> u16 {
+    avr_hal_generic::timer::access_16bit(|| {
+        let tim = unsafe { &*crate::pac::TC4::ptr() };
+        let low = read_low();
+        (tim.tc4h().read().bits() as u16) << 8 | low as u16
+    })
+}
+
+/// `TC4` of the ATmega32U4 is a 10-bit timer which always counts up to `OCR4C`.  In normal mode,
+/// `OCR4C` is set to the counter maximum; in CTC mode, it is written together with `OCR4A`.
+#[cfg(feature = "atmega32u4")]
+impl TimerOps<crate::Atmega> for crate::pac::TC4 {
+    const MAX: u16 = 0x3ff;
+
+    const PRESCALERS: &'static [Prescaler] = &[
+        Prescaler::Direct,
+        Prescaler::Prescale8,
+        Prescaler::Prescale32,
+        Prescaler::Prescale64,
+        Prescaler::Prescale128,
+        Prescaler::Prescale256,
+        Prescaler::Prescale1024,
+    ];
+
+    const HAS_COMPARE_B: bool = true;
+
+    #[inline]
+    fn raw_set_clock(&mut self, prescaler: Option<Prescaler>) {
+        self.tccr4b().modify(|_, w| match prescaler {
+            Some(Prescaler::Direct) => w.cs4().direct(),
+            Some(Prescaler::Prescale8) => w.cs4().prescale_8(),
+            Some(Prescaler::Prescale32) => w.cs4().prescale_32(),
+            Some(Prescaler::Prescale64) => w.cs4().prescale_64(),
+            Some(Prescaler::Prescale128) => w.cs4().prescale_128(),
+            Some(Prescaler::Prescale256) => w.cs4().prescale_256(),
+            Some(Prescaler::Prescale1024) => w.cs4().prescale_1024(),
+            None => w.cs4().no_clock(),
+        });
+    }
+
+    #[inline]
+    fn raw_set_mode(&mut self, mode: TimerMode) {
+        // Leave any PWM mode the timer was used in before.
+        self.tccr4a()
+            .modify(|_, w| w.pwm4a().clear_bit().pwm4b().clear_bit());
+        self.tccr4c().modify(|_, w| w.pwm4d().clear_bit());
+        self.tccr4d().modify(|_, w| w.wgm4().pwm_fast());
+        if mode == TimerMode::Normal {
+            write_tc4_10bit(Self::MAX, |low| {
+                self.ocr4c().write(|w| w.set(low));
+            });
+        }
+    }
+
+    #[inline]
+    fn raw_read_counter(&self) -> u16 {
+        read_tc4_10bit(|| self.tcnt4().read().bits())
+    }
+
+    #[inline]
+    fn raw_write_counter(&mut self, value: u16) {
+        write_tc4_10bit(value, |low| {
+            self.tcnt4().write(|w| w.set(low));
+        });
+    }
+
+    #[inline]
+    fn raw_set_compare(&mut self, channel: OutputCompare, value: u16) {
+        match channel {
+            OutputCompare::A => {
+                write_tc4_10bit(value, |low| {
+                    self.ocr4a().write(|w| w.set(low));
+                });
+                write_tc4_10bit(value, |low| {
+                    self.ocr4c().write(|w| w.set(low));
+                });
+            }
+            OutputCompare::B => {
+                write_tc4_10bit(value, |low| {
+                    self.ocr4b().write(|w| w.set(low));
+                });
+            }
+        }
+    }
+
+    #[inline]
+    fn raw_set_interrupt(&mut self, event: Event, enable: bool) {
+        self.timsk4().modify(|_, w| match event {
+            Event::Overflow => w.toie4().bit(enable),
+            Event::CompareA => w.ocie4a().bit(enable),
+            Event::CompareB => w.ocie4b().bit(enable),
+        });
+    }
+
+    #[inline]
+    fn raw_is_pending(&self, event: Event) -> bool {
+        let flags = self.tifr4().read();
+        match event {
+            Event::Overflow => flags.tov4().bit_is_set(),
+            Event::CompareA => flags.ocf4a().bit_is_set(),
+            Event::CompareB => flags.ocf4b().bit_is_set(),
+        }
+    }
+
+    #[inline]
+    fn raw_clear_pending(&mut self, event: Event) {
+        // Flags are cleared by writing a logical one.
+        self.tifr4().write(|w| match event {
+            Event::Overflow => w.tov4().set_bit(),
+            Event::CompareA => w.ocf4a().set_bit(),
+            Event::CompareB => w.ocf4b().set_bit(),
+        });
+    }
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC5,
+    counter: u16,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr5b().modify(|_, w| match prescaler {
+                Some(Prescaler::Direct) => w.cs5().direct(),
+                Some(Prescaler::Prescale8) => w.cs5().prescale_8(),
+                Some(Prescaler::Prescale64) => w.cs5().prescale_64(),
+                Some(Prescaler::Prescale256) => w.cs5().prescale_256(),
+                Some(Prescaler::Prescale1024) => w.cs5().prescale_1024(),
+                _ => w.cs5().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr5a().modify(|_, w| w.wgm5().set(0b00));
+        tim.tccr5b().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm5().set(0b00),
+            TimerMode::Ctc => w.wgm5().set(0b01),
+        });
+    },
+    registers: {
+        counter: tcnt5,
+        compare_a: ocr5a,
+        compare_b: ocr5b,
+        mask: timsk5,
+        flags: tifr5,
+    },
+    interrupts: {
+        overflow: (toie5, tov5),
+        compare_a: (ocie5a, ocf5a),
+        compare_b: (ocie5b, ocf5b),
+    },
+}
+
+#[cfg(any(feature = "atmega16", feature = "atmega32a"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr0().modify(|_, w| w.cs0().set(match prescaler {
+            Some(Prescaler::Direct) => 0b001,
+            Some(Prescaler::Prescale8) => 0b010,
+            Some(Prescaler::Prescale64) => 0b011,
+            Some(Prescaler::Prescale256) => 0b100,
+            Some(Prescaler::Prescale1024) => 0b101,
+            _ => 0b000,
+        }));
+    },
+    set_mode: |tim, mode| {
+        tim.tccr0().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm00().clear_bit().wgm01().clear_bit(),
+            TimerMode::Ctc => w.wgm00().clear_bit().wgm01().set_bit(),
+        });
+    },
+    registers: {
+        counter: tcnt0,
+        compare_a: ocr0,
+        mask: timsk,
+        flags: tifr,
+    },
+    interrupts: {
+        overflow: (toie0, tov0),
+        compare_a: (ocie0, ocf0),
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale32, Prescale64, Prescale128, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr0().modify(|_, w| match prescaler {
+            Some(Prescaler::Direct) => w.cs0().direct(),
+            Some(Prescaler::Prescale8) => w.cs0().prescale_8(),
+            Some(Prescaler::Prescale32) => w.cs0().prescale_32(),
+            Some(Prescaler::Prescale64) => w.cs0().prescale_64(),
+            Some(Prescaler::Prescale128) => w.cs0().prescale_128(),
+            Some(Prescaler::Prescale256) => w.cs0().prescale_256(),
+            Some(Prescaler::Prescale1024) => w.cs0().prescale_1024(),
+            _ => w.cs0().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr0().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm00().clear_bit().wgm01().clear_bit(),
+            TimerMode::Ctc => w.wgm00().clear_bit().wgm01().set_bit(),
+        });
+    },
+    registers: {
+        counter: tcnt0,
+        compare_a: ocr0,
+        mask: timsk,
+        flags: tifr,
+    },
+    interrupts: {
+        overflow: (toie0, tov0),
+        compare_a: (ocie0, ocf0),
+    },
+}
+
+#[cfg(any(
+    feature = "atmega8",
+    feature = "atmega16",
+    feature = "atmega32a",
+    feature = "atmega128a",
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr1b().modify(|_, w| w.cs1().set(match prescaler {
+            Some(Prescaler::Direct) => 0b001,
+            Some(Prescaler::Prescale8) => 0b010,
+            Some(Prescaler::Prescale64) => 0b011,
+            Some(Prescaler::Prescale256) => 0b100,
+            Some(Prescaler::Prescale1024) => 0b101,
+            _ => 0b000,
+        }));
+    },
+    set_mode: |tim, mode| {
+        tim.tccr1a().modify(|_, w| w.wgm1().set(0b00));
+        tim.tccr1b().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm1().set(0b00),
+            TimerMode::Ctc => w.wgm1().set(0b01),
+        });
+    },
+    registers: {
+        counter: tcnt1,
+        compare_a: ocr1a,
+        compare_b: ocr1b,
+        mask: timsk,
+        flags: tifr,
+    },
+    interrupts: {
+        overflow: (toie1, tov1),
+        compare_a: (ocie1a, ocf1a),
+        compare_b: (ocie1b, ocf1b),
+    },
+}
+
+#[cfg(any(feature = "atmega8", feature = "atmega16", feature = "atmega32a"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale32, Prescale64, Prescale128, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr2().modify(|_, w| w.cs2().set(match prescaler {
+            Some(Prescaler::Direct) => 0b001,
+            Some(Prescaler::Prescale8) => 0b010,
+            Some(Prescaler::Prescale32) => 0b011,
+            Some(Prescaler::Prescale64) => 0b100,
+            Some(Prescaler::Prescale128) => 0b101,
+            Some(Prescaler::Prescale256) => 0b110,
+            Some(Prescaler::Prescale1024) => 0b111,
+            None => 0b000,
+        }));
+    },
+    set_mode: |tim, mode| {
+        tim.tccr2().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm20().clear_bit().wgm21().clear_bit(),
+            TimerMode::Ctc => w.wgm20().clear_bit().wgm21().set_bit(),
+        });
+    },
+    registers: {
+        counter: tcnt2,
+        compare_a: ocr2,
+        mask: timsk,
+        flags: tifr,
+    },
+    interrupts: {
+        overflow: (toie2, tov2),
+        compare_a: (ocie2, ocf2),
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr2().modify(|_, w| w.cs2().set(match prescaler {
+            Some(Prescaler::Direct) => 0b001,
+            Some(Prescaler::Prescale8) => 0b010,
+            Some(Prescaler::Prescale64) => 0b011,
+            Some(Prescaler::Prescale256) => 0b100,
+            Some(Prescaler::Prescale1024) => 0b101,
+            _ => 0b000,
+        }));
+    },
+    set_mode: |tim, mode| {
+        tim.tccr2().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm20().clear_bit().wgm21().clear_bit(),
+            TimerMode::Ctc => w.wgm20().clear_bit().wgm21().set_bit(),
+        });
+    },
+    registers: {
+        counter: tcnt2,
+        compare_a: ocr2,
+        mask: timsk,
+        flags: tifr,
+    },
+    interrupts: {
+        overflow: (toie2, tov2),
+        compare_a: (ocie2, ocf2),
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    counter: u16,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr3b().modify(|_, w| match prescaler {
+            Some(Prescaler::Direct) => w.cs3().direct(),
+            Some(Prescaler::Prescale8) => w.cs3().prescale_8(),
+            Some(Prescaler::Prescale64) => w.cs3().prescale_64(),
+            Some(Prescaler::Prescale256) => w.cs3().prescale_256(),
+            Some(Prescaler::Prescale1024) => w.cs3().prescale_1024(),
+            _ => w.cs3().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr3a().modify(|_, w| w.wgm3().set(0b00));
+        tim.tccr3b().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm3().set(0b00),
+            TimerMode::Ctc => w.wgm3().set(0b01),
+        });
+    },
+    registers: {
+        counter: tcnt3,
+        compare_a: ocr3a,
+        compare_b: ocr3b,
+        mask: etimsk,
+        flags: etifr,
+    },
+    interrupts: {
+        overflow: (toie3, tov3),
+        compare_a: (ocie3a, ocf3a),
+        compare_b: (ocie3b, ocf3b),
+    },
+}
+
+#[cfg(feature = "atmega164pa")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr0b().modify(|_, w| w.cs0().set(match prescaler {
+            Some(Prescaler::Direct) => 0b001,
+            Some(Prescaler::Prescale8) => 0b010,
+            Some(Prescaler::Prescale64) => 0b011,
+            Some(Prescaler::Prescale256) => 0b100,
+            Some(Prescaler::Prescale1024) => 0b101,
+            _ => 0b000,
+        }));
+    },
+    set_mode: |tim, mode| {
+        tim.tccr0a().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm0().set(0b00),
+            TimerMode::Ctc => w.wgm0().set(0b10),
+        });
+        tim.tccr0b().modify(|_, w| w.wgm02().clear_bit());
+    },
+    registers: {
+        counter: tcnt0,
+        compare_a: ocr0a,
+        compare_b: ocr0b,
+        mask: timsk0,
+        flags: tifr0,
+    },
+    interrupts: {
+        overflow: (toie0, tov0),
+        compare_a: (ocie0a, ocf0a),
+        compare_b: (ocie0b, ocf0b),
+    },
+}
+
+#[cfg(feature = "atmega164pa")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr1b().modify(|_, w| w.cs1().set(match prescaler {
+            Some(Prescaler::Direct) => 0b001,
+            Some(Prescaler::Prescale8) => 0b010,
+            Some(Prescaler::Prescale64) => 0b011,
+            Some(Prescaler::Prescale256) => 0b100,
+            Some(Prescaler::Prescale1024) => 0b101,
+            _ => 0b000,
+        }));
+    },
+    set_mode: |tim, mode| {
+        tim.tccr1a().modify(|_, w| w.wgm1().set(0b00));
+        tim.tccr1b().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm1().set(0b00),
+            TimerMode::Ctc => w.wgm1().set(0b01),
+        });
+    },
+    registers: {
+        counter: tcnt1,
+        compare_a: ocr1a,
+        compare_b: ocr1b,
+        mask: timsk1,
+        flags: tifr1,
+    },
+    interrupts: {
+        overflow: (toie1, tov1),
+        compare_a: (ocie1a, ocf1a),
+        compare_b: (ocie1b, ocf1b),
+    },
+}
+
+#[cfg(feature = "atmega164pa")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale32, Prescale64, Prescale128, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr2b().modify(|_, w| w.cs2().set(match prescaler {
+            Some(Prescaler::Direct) => 0b001,
+            Some(Prescaler::Prescale8) => 0b010,
+            Some(Prescaler::Prescale32) => 0b011,
+            Some(Prescaler::Prescale64) => 0b100,
+            Some(Prescaler::Prescale128) => 0b101,
+            Some(Prescaler::Prescale256) => 0b110,
+            Some(Prescaler::Prescale1024) => 0b111,
+            None => 0b000,
+        }));
+    },
+    set_mode: |tim, mode| {
+        tim.tccr2a().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm2().set(0b00),
+            TimerMode::Ctc => w.wgm2().set(0b10),
+        });
+        tim.tccr2b().modify(|_, w| w.wgm22().clear_bit());
+    },
+    registers: {
+        counter: tcnt2,
+        compare_a: ocr2a,
+        compare_b: ocr2b,
+        mask: timsk2,
+        flags: tifr2,
+    },
+    interrupts: {
+        overflow: (toie2, tov2),
+        compare_a: (ocie2a, ocf2a),
+        compare_b: (ocie2b, ocf2b),
+    },
+}
@@ -0,0 +1,374 @@
+//! Square-wave tone generation
+//!
+//! Check the [`avr_hal_generic::tone`] documentation for details.
+//!
+//! The hardware-toggled output of each timer is its `OCnA` (or `OCn`) pin:
+//!
+//! | Timer | Pin |
+//! | --- | --- |
+//! | `TC0` | `PD6` (ATmega48P, ATmega88P, ATmega168, ATmega328P, ATmega328PB), `PB7` (ATmega32U4, ATmega1280, ATmega2560), `PB3` (ATmega16, ATmega32A, ATmega164PA, ATmega1284P), `PB4` (ATmega128A) |
+//! | `TC1` | `PB1` (ATmega8, ATmega48P, ATmega88P, ATmega168, ATmega328P, ATmega328PB), `PB5` (ATmega32U4, ATmega128A, ATmega1280, ATmega2560), `PD5` (ATmega16, ATmega32A, ATmega164PA, ATmega1284P) |
+//! | `TC2` | `PB3` (ATmega8, ATmega48P, ATmega88P, ATmega168, ATmega328P, ATmega328PB), `PB4` (ATmega1280, ATmega2560), `PD7` (ATmega16, ATmega32A, ATmega164PA, ATmega1284P), `PB7` (ATmega128A) |
+//! | `TC3` | `PD0` (ATmega328PB), `PC6` (ATmega32U4), `PB6` (ATmega1284P), `PE3` (ATmega128A, ATmega1280, ATmega2560) |
+//! | `TC4` | `PD1` (ATmega328PB), `PH3` (ATmega1280, ATmega2560) |
+//! | `TC5` | `PL3` (ATmega1280, ATmega2560) |
+//!
+//! # Example
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//!
+//! let mut tone = Tone::<_, CoreClock, _>::new(dp.TC2, pins.pb3.into_output());
+//! tone.play(440.Hz()).unwrap();
+//! ```
+
+pub use avr_hal_generic::tone::{Note, ToneOps};
+
+/// Check the [`avr_hal_generic::tone::Tone`] documentation.
+pub type Tone<TC, CLOCK, PIN> = avr_hal_generic::tone::Tone<crate::Atmega, TC, CLOCK, PIN>;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    pin: crate::port::PD6,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr0a().modify(|_, w| if enable {
+            w.com0a().set(0b01)
+        } else {
+            w.com0a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560", feature = "atmega32u4",))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    pin: crate::port::PB7,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr0a().modify(|_, w| if enable {
+            w.com0a().set(0b01)
+        } else {
+            w.com0a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p",))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    pin: crate::port::PB3,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr0a().modify(|_, w| if enable {
+            w.com0a().set(0b01)
+        } else {
+            w.com0a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(feature = "atmega16", feature = "atmega32a",))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    pin: crate::port::PB3,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr0().modify(|_, w| if enable {
+            w.com0().set(0b01)
+        } else {
+            w.com0().set(0b00)
+        });
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    pin: crate::port::PB4,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr0().modify(|_, w| if enable {
+            // SAFETY: The PAC has no variant for toggling, but 0b01 is valid.
+            unsafe { w.com0().bits(0b01) }
+        } else {
+            w.com0().disconnected()
+        });
+    },
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega8",
+))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PB1,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr1a().modify(|_, w| if enable {
+            w.com1a().set(0b01)
+        } else {
+            w.com1a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4",
+    feature = "atmega128a",
+))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PB5,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr1a().modify(|_, w| if enable {
+            w.com1a().set(0b01)
+        } else {
+            w.com1a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(
+    feature = "atmega16",
+    feature = "atmega32a",
+    feature = "atmega164pa",
+    feature = "atmega1284p",
+))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PD5,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr1a().modify(|_, w| if enable {
+            w.com1a().set(0b01)
+        } else {
+            w.com1a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    pin: crate::port::PB3,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr2a().modify(|_, w| if enable {
+            w.com2a().set(0b01)
+        } else {
+            w.com2a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560",))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    pin: crate::port::PB4,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr2a().modify(|_, w| if enable {
+            w.com2a().set(0b01)
+        } else {
+            w.com2a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p",))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    pin: crate::port::PD7,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr2a().modify(|_, w| if enable {
+            w.com2a().set(0b01)
+        } else {
+            w.com2a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(feature = "atmega16", feature = "atmega32a",))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    pin: crate::port::PD7,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr2().modify(|_, w| if enable {
+            w.com2().set(0b01)
+        } else {
+            w.com2().set(0b00)
+        });
+    },
+}
+
+#[cfg(feature = "atmega8")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    pin: crate::port::PB3,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr2().modify(|_, w| if enable {
+            // SAFETY: The PAC has no variant for toggling, but 0b01 is valid.
+            unsafe { w.com2().bits(0b01) }
+        } else {
+            w.com2().disconnected()
+        });
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    pin: crate::port::PB7,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr2().modify(|_, w| if enable {
+            w.com2().set(0b01)
+        } else {
+            w.com2().set(0b00)
+        });
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: crate::port::PD0,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr3a().modify(|_, w| if enable {
+            w.com3a().set(0b01)
+        } else {
+            w.com3a().set(0b00)
+        });
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: crate::port::PC6,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr3a().modify(|_, w| if enable {
+            w.com3a().set(0b01)
+        } else {
+            w.com3a().set(0b00)
+        });
+    },
+}
+
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: crate::port::PB6,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr3a().modify(|_, w| if enable {
+            w.com3a().set(0b01)
+        } else {
+            w.com3a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(feature = "atmega128a", feature = "atmega1280", feature = "atmega2560",))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: crate::port::PE3,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr3a().modify(|_, w| if enable {
+            w.com3a().set(0b01)
+        } else {
+            w.com3a().set(0b00)
+        });
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    pin: crate::port::PD1,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr4a().modify(|_, w| if enable {
+            w.com4a().set(0b01)
+        } else {
+            w.com4a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560",))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    pin: crate::port::PH3,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr4a().modify(|_, w| if enable {
+            w.com4a().set(0b01)
+        } else {
+            w.com4a().set(0b00)
+        });
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560",))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC5,
+    pin: crate::port::PL3,
+    set_toggle: |tim, enable| {
+        // COMnA = 0b01: Toggle the pin on compare match
+        tim.tccr5a().modify(|_, w| if enable {
+            w.com5a().set(0b01)
+        } else {
+            w.com5a().set(0b00)
+        });
+    },
+}
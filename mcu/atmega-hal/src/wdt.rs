@@ -1,5 +1,5 @@
 #[allow(unused_imports)]
-pub use avr_hal_generic::wdt::{Timeout, WdtOps};
+pub use avr_hal_generic::wdt::{Mode, Timeout, WdtInterruptOps, WdtOps};
 
 pub type Wdt = avr_hal_generic::wdt::Wdt<crate::Atmega, crate::pac::WDT>;
 
@@ -14,6 +14,7 @@ avr_hal_generic::impl_wdt! {
     peripheral: crate::pac::WDT,
     mcusr: crate::pac::cpu::MCUSR,
     wdtcsr_name: wdtcsr,
+    interrupt: wdie, wdif,
     timeout: |to, w| match to {
         Timeout::Ms16 => w.wdpl().cycles_2k_512k(),
         Timeout::Ms32 => w.wdpl().cycles_4k_1024k(),
@@ -26,7 +26,10 @@
 //! ```
 
 use crate::port;
-pub use avr_hal_generic::adc::{AdcChannel, AdcOps, ClockDivider};
+pub use avr_hal_generic::adc::{
+    AdcChannel, AdcOps, AdcPinOps, AdcSleepOps, AdcSupplyOps, AdcTemperatureOps, AdcTimerOps,
+    Calibration, ClockDivider, Differential, TriggerSource, VoltageInput,
+};
 
 /// Select the voltage reference for the ADC peripheral
 ///
@@ -36,15 +39,20 @@ pub use avr_hal_generic::adc::{AdcChannel, AdcOps, ClockDivider};
 #[repr(u8)]
 pub enum ReferenceVoltage {
     /// Voltage applied to AREF pin.
-    #[cfg(any(feature = "attiny85", feature = "attiny167",))]
+    #[cfg(any(feature = "attiny84", feature = "attiny85", feature = "attiny167",))]
     Aref,
     /// Default reference voltage (default).
     AVcc,
     /// Internal 1.1V reference.
     Internal1_1,
     /// Internal 2.56V reference.
+    ///
+    /// On the ATtiny85, AREF is left unconnected, so it can still be used as a GPIO pin.
     #[cfg(any(feature = "attiny85", feature = "attiny167",))]
     Internal2_56,
+    /// Internal 2.56V reference with an external bypass capacitor at the AREF pin.
+    #[cfg(feature = "attiny85")]
+    Internal2_56Bypassed,
 }
 
 impl Default for ReferenceVoltage {
@@ -60,12 +68,162 @@ pub struct AdcSettings {
     pub ref_voltage: ReferenceVoltage,
 }
 
+/// Event which starts a conversion in auto-trigger mode.
+///
+/// Check the [`avr_hal_generic::adc::Sampler`] documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AutoTrigger {
+    /// Start the next conversion as soon as the previous one completes.
+    FreeRunning = 0,
+    AnalogComparator = 1,
+    ExternalInterrupt0 = 2,
+    Timer0CompareA = 3,
+    Timer0Overflow = 4,
+    #[cfg(any(feature = "attiny84", feature = "attiny85"))]
+    Timer0CompareB = 5,
+    #[cfg(any(feature = "attiny84", feature = "attiny85"))]
+    PinChangeInterrupt = 6,
+    #[cfg(any(feature = "attiny88", feature = "attiny167"))]
+    Timer1CompareB = 5,
+    #[cfg(feature = "attiny88")]
+    Timer1Overflow = 6,
+    #[cfg(feature = "attiny88")]
+    Timer1Capture = 7,
+    #[cfg(feature = "attiny167")]
+    Timer1Capture = 6,
+    #[cfg(feature = "attiny167")]
+    Watchdog = 7,
+}
+
+/// Set ADATE after the trigger source was selected.
+fn set_auto_trigger(peripheral: &crate::pac::ADC, trigger: Option<AutoTrigger>) {
+    peripheral.adcsra().modify(|_, w| {
+        w.adate().bit(trigger.is_some());
+        // Free-running mode needs a first conversion to get going.
+        w.adsc().bit(trigger == Some(AutoTrigger::FreeRunning))
+    });
+}
+
+/// Clear the interrupt flag of `trigger`, so its next event starts a conversion again.
+///
+/// This is not needed when the interrupt of the trigger source is enabled, because the hardware
+/// clears the flag when the interrupt handler runs.
+fn clear_trigger_flag(trigger: AutoTrigger) {
+    // SAFETY: The flags are cleared by writing a one, writing zeros to the other flags has no
+    // effect.  `ACSR` and `WDTCR` are modified inside a critical section.
+    match trigger {
+        AutoTrigger::FreeRunning => (),
+        AutoTrigger::AnalogComparator => avr_hal_generic::avr_device::interrupt::free(|_| {
+            let ac = unsafe { &*crate::pac::AC::ptr() };
+            ac.acsr().modify(|_, w| w.aci().set_bit());
+        }),
+        #[cfg(any(feature = "attiny84", feature = "attiny85"))]
+        AutoTrigger::ExternalInterrupt0 => {
+            unsafe { &*crate::pac::EXINT::ptr() }
+                .gifr()
+                .write(|w| w.intf0().set_bit());
+        }
+        #[cfg(any(feature = "attiny88", feature = "attiny167"))]
+        AutoTrigger::ExternalInterrupt0 => {
+            unsafe { &*crate::pac::EXINT::ptr() }
+                .eifr()
+                .write(|w| w.intf().set(0b01));
+        }
+        #[cfg(feature = "attiny85")]
+        AutoTrigger::Timer0CompareA => {
+            unsafe { &*crate::pac::TC0::ptr() }
+                .tifr()
+                .write(|w| w.ocf0a().set_bit());
+        }
+        #[cfg(feature = "attiny85")]
+        AutoTrigger::Timer0Overflow => {
+            unsafe { &*crate::pac::TC0::ptr() }
+                .tifr()
+                .write(|w| w.tov0().set_bit());
+        }
+        #[cfg(feature = "attiny85")]
+        AutoTrigger::Timer0CompareB => {
+            unsafe { &*crate::pac::TC0::ptr() }
+                .tifr()
+                .write(|w| w.ocf0b().set_bit());
+        }
+        #[cfg(feature = "attiny85")]
+        AutoTrigger::PinChangeInterrupt => {
+            unsafe { &*crate::pac::EXINT::ptr() }
+                .gifr()
+                .write(|w| w.pcif().set_bit());
+        }
+        #[cfg(not(feature = "attiny85"))]
+        AutoTrigger::Timer0CompareA => {
+            unsafe { &*crate::pac::TC0::ptr() }
+                .tifr0()
+                .write(|w| w.ocf0a().set_bit());
+        }
+        #[cfg(not(feature = "attiny85"))]
+        AutoTrigger::Timer0Overflow => {
+            unsafe { &*crate::pac::TC0::ptr() }
+                .tifr0()
+                .write(|w| w.tov0().set_bit());
+        }
+        #[cfg(feature = "attiny84")]
+        AutoTrigger::Timer0CompareB => {
+            unsafe { &*crate::pac::TC0::ptr() }
+                .tifr0()
+                .write(|w| w.ocf0b().set_bit());
+        }
+        #[cfg(feature = "attiny84")]
+        AutoTrigger::PinChangeInterrupt => {
+            unsafe { &*crate::pac::EXINT::ptr() }
+                .gifr()
+                .write(|w| w.pcif().set(0b11));
+        }
+        #[cfg(any(feature = "attiny88", feature = "attiny167"))]
+        AutoTrigger::Timer1CompareB => {
+            unsafe { &*crate::pac::TC1::ptr() }
+                .tifr1()
+                .write(|w| w.ocf1b().set_bit());
+        }
+        #[cfg(feature = "attiny88")]
+        AutoTrigger::Timer1Overflow => {
+            unsafe { &*crate::pac::TC1::ptr() }
+                .tifr1()
+                .write(|w| w.tov1().set_bit());
+        }
+        #[cfg(any(feature = "attiny88", feature = "attiny167"))]
+        AutoTrigger::Timer1Capture => {
+            unsafe { &*crate::pac::TC1::ptr() }
+                .tifr1()
+                .write(|w| w.icf1().set_bit());
+        }
+        #[cfg(feature = "attiny167")]
+        AutoTrigger::Watchdog => avr_hal_generic::avr_device::interrupt::free(|_| {
+            let wdt = unsafe { &*crate::pac::WDT::ptr() };
+            wdt.wdtcr().modify(|_, w| w.wdif().set_bit());
+        }),
+    }
+}
+
 /// Check the [`avr_hal_generic::adc::Adc`] documentation.
 pub type Adc<CLOCK> = avr_hal_generic::adc::Adc<crate::Attiny, crate::pac::ADC, CLOCK>;
 
 /// Check the [`avr_hal_generic::adc::Channel`] documentation.
 pub type Channel = avr_hal_generic::adc::Channel<crate::Attiny, crate::pac::ADC>;
 
+/// Check the [`avr_hal_generic::adc::Sampler`] documentation.
+pub type Sampler<'a, CLOCK, TRIG> =
+    avr_hal_generic::adc::Sampler<'a, crate::Attiny, crate::pac::ADC, CLOCK, TRIG>;
+
+/// Check the [`avr_hal_generic::adc::TimerTrigger`] documentation.
+pub type TimerTrigger<TC, CLOCK> = avr_hal_generic::adc::TimerTrigger<crate::Attiny, TC, CLOCK>;
+
+/// Check the [`avr_hal_generic::adc::SharedAdc`] documentation.
+pub type SharedAdc<CLOCK> = avr_hal_generic::adc::SharedAdc<crate::Attiny, crate::pac::ADC, CLOCK>;
+
+/// Check the [`avr_hal_generic::adc::AnalogInput`] documentation.
+pub type AnalogInput<'a, CLOCK, PIN> =
+    avr_hal_generic::adc::AnalogInput<'a, crate::Attiny, crate::pac::ADC, CLOCK, PIN>;
+
 /// Additional channels
 ///
 /// Some channels are not directly connected to pins.  This module provides types which can be used
@@ -113,12 +271,55 @@ avr_hal_generic::impl_adc! {
             ReferenceVoltage::AVcc => w.refs().vcc(),
             ReferenceVoltage::Internal1_1 => w.refs().internal().refs2().clear_bit(),
             ReferenceVoltage::Internal2_56 => w.refs().internal().refs2().set_bit(),
+            ReferenceVoltage::Internal2_56Bypassed => w.refs().internal_bypass().refs2().set_bit(),
+        });
+    },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| {
+        let admux = peripheral.admux().read();
+        match (admux.refs().bits(), admux.refs2().bit()) {
+            (0, _) => ReferenceVoltage::AVcc,
+            (1, _) => ReferenceVoltage::Aref,
+            (_, false) => ReferenceVoltage::Internal1_1,
+            (2, true) => ReferenceVoltage::Internal2_56,
+            (_, true) => ReferenceVoltage::Internal2_56Bypassed,
+        }
+    },
+    set_reference: |peripheral, reference| {
+        peripheral.admux().modify(|_, w| match reference {
+            ReferenceVoltage::Aref => w.refs().aref(),
+            ReferenceVoltage::AVcc => w.refs().vcc(),
+            ReferenceVoltage::Internal1_1 => w.refs().internal().refs2().clear_bit(),
+            ReferenceVoltage::Internal2_56 => w.refs().internal().refs2().set_bit(),
+            ReferenceVoltage::Internal2_56Bypassed => w.refs().internal_bypass().refs2().set_bit(),
         });
     },
+    set_left_adjust: |peripheral, enable| {
+        peripheral.admux().modify(|_, w| w.adlar().bit(enable));
+    },
     channel_id: crate::pac::adc::admux::MUX_A,
     set_channel: |peripheral, id| {
         peripheral.admux().modify(|_, w| w.mux().variant(id));
+        // Bipolar mode gives signed results for the differential channels.
+        let differential = (4..=11).contains(&u8::from(id));
+        peripheral.adcsrb().modify(|_, w| w.bin().bit(differential));
     },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        if let Some(trigger) = trigger {
+            peripheral.adcsrb().modify(|_, w| w.adts().variant(match trigger {
+                AutoTrigger::FreeRunning => crate::pac::adc::adcsrb::ADTS_A::FREE,
+                AutoTrigger::AnalogComparator => crate::pac::adc::adcsrb::ADTS_A::AC,
+                AutoTrigger::ExternalInterrupt0 => crate::pac::adc::adcsrb::ADTS_A::INT0,
+                AutoTrigger::Timer0CompareA => crate::pac::adc::adcsrb::ADTS_A::TC0_CMA,
+                AutoTrigger::Timer0Overflow => crate::pac::adc::adcsrb::ADTS_A::TC0_OVF,
+                AutoTrigger::Timer0CompareB => crate::pac::adc::adcsrb::ADTS_A::TC0_CMB,
+                AutoTrigger::PinChangeInterrupt => crate::pac::adc::adcsrb::ADTS_A::PCIR,
+            }));
+        }
+        set_auto_trigger(peripheral, trigger);
+    },
+    clear_trigger: |trigger| { clear_trigger_flag(trigger) },
     pins: {
         port::PB5: (crate::pac::adc::admux::MUX_A::ADC0, didr0::adc0d),
         port::PB2: (crate::pac::adc::admux::MUX_A::ADC1, didr0::adc1d),
@@ -130,6 +331,134 @@ avr_hal_generic::impl_adc! {
         channel::Gnd: crate::pac::adc::admux::MUX_A::ADC_GND,
         channel::Temperature: crate::pac::adc::admux::MUX_A::TEMPSENS,
     },
+    differential: {
+        port::PB5, port::PB2, 1: crate::pac::adc::admux::MUX_A::ADC0_ADC1_1X,
+        port::PB5, port::PB2, 20: crate::pac::adc::admux::MUX_A::ADC0_ADC1_20X,
+        port::PB4, port::PB3, 1: crate::pac::adc::admux::MUX_A::ADC2_ADC3_1X,
+        port::PB4, port::PB3, 20: crate::pac::adc::admux::MUX_A::ADC2_ADC3_20X,
+    },
+}
+
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_adc! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::ADC,
+    settings: AdcSettings,
+    apply_settings: |peripheral, settings| {
+        apply_clock(peripheral, settings);
+        peripheral.admux().write(|w| match settings.ref_voltage {
+            ReferenceVoltage::Aref => w.refs().aref(),
+            ReferenceVoltage::AVcc => w.refs().vcc(),
+            ReferenceVoltage::Internal1_1 => w.refs().internal(),
+        });
+    },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| {
+        match peripheral.admux().read().refs().bits() {
+            0 => ReferenceVoltage::AVcc,
+            1 => ReferenceVoltage::Aref,
+            _ => ReferenceVoltage::Internal1_1,
+        }
+    },
+    set_reference: |peripheral, reference| {
+        peripheral.admux().modify(|_, w| match reference {
+            ReferenceVoltage::Aref => w.refs().aref(),
+            ReferenceVoltage::AVcc => w.refs().vcc(),
+            ReferenceVoltage::Internal1_1 => w.refs().internal(),
+        });
+    },
+    set_left_adjust: |peripheral, enable| {
+        peripheral.adcsrb().modify(|_, w| w.adlar().bit(enable));
+    },
+    channel_id: crate::pac::adc::admux::MUX_A,
+    set_channel: |peripheral, id| {
+        peripheral.admux().modify(|_, w| w.mux().variant(id));
+        // Bipolar mode gives signed results for the differential channels.
+        let id = u8::from(id);
+        let differential = (8..=31).contains(&id) || (35..=63).contains(&id);
+        peripheral.adcsrb().modify(|_, w| w.bin().bit(differential));
+    },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        if let Some(trigger) = trigger {
+            peripheral.adcsrb().modify(|_, w| w.adts().variant(match trigger {
+                AutoTrigger::FreeRunning => crate::pac::adc::adcsrb::ADTS_A::FREE,
+                AutoTrigger::AnalogComparator => crate::pac::adc::adcsrb::ADTS_A::AC,
+                AutoTrigger::ExternalInterrupt0 => crate::pac::adc::adcsrb::ADTS_A::INT0,
+                AutoTrigger::Timer0CompareA => crate::pac::adc::adcsrb::ADTS_A::TC0_CMA,
+                AutoTrigger::Timer0Overflow => crate::pac::adc::adcsrb::ADTS_A::TC0_OVF,
+                AutoTrigger::Timer0CompareB => crate::pac::adc::adcsrb::ADTS_A::TC0_CMB,
+                AutoTrigger::PinChangeInterrupt => crate::pac::adc::adcsrb::ADTS_A::PCIR,
+            }));
+        }
+        set_auto_trigger(peripheral, trigger);
+    },
+    clear_trigger: |trigger| { clear_trigger_flag(trigger) },
+    pins: {
+        port::PA0: (crate::pac::adc::admux::MUX_A::ADC0),
+        port::PA1: (crate::pac::adc::admux::MUX_A::ADC1),
+        port::PA2: (crate::pac::adc::admux::MUX_A::ADC2),
+        port::PA3: (crate::pac::adc::admux::MUX_A::ADC3),
+        port::PA4: (crate::pac::adc::admux::MUX_A::ADC4),
+        port::PA5: (crate::pac::adc::admux::MUX_A::ADC5),
+        port::PA6: (crate::pac::adc::admux::MUX_A::ADC6),
+        port::PA7: (crate::pac::adc::admux::MUX_A::ADC7),
+    },
+    channels: {
+        channel::Vbg: crate::pac::adc::admux::MUX_A::ADC_VBG,
+        channel::Gnd: crate::pac::adc::admux::MUX_A::ADC_GND,
+        channel::Temperature: crate::pac::adc::admux::MUX_A::TEMPSENS,
+    },
+    differential: {
+        port::PA0, port::PA1, 1: crate::pac::adc::admux::MUX_A::ADC0_ADC1_1X,
+        port::PA0, port::PA1, 20: crate::pac::adc::admux::MUX_A::ADC0_ADC1_20X,
+        port::PA0, port::PA3, 1: crate::pac::adc::admux::MUX_A::ADC0_ADC3_1X,
+        port::PA0, port::PA3, 20: crate::pac::adc::admux::MUX_A::ADC0_ADC3_20X,
+        port::PA1, port::PA2, 1: crate::pac::adc::admux::MUX_A::ADC1_ADC2_1X,
+        port::PA1, port::PA2, 20: crate::pac::adc::admux::MUX_A::ADC1_ADC2_20X,
+        port::PA1, port::PA3, 1: crate::pac::adc::admux::MUX_A::ADC1_ADC3_1X,
+        port::PA1, port::PA3, 20: crate::pac::adc::admux::MUX_A::ADC1_ADC3_20X,
+        port::PA2, port::PA3, 1: crate::pac::adc::admux::MUX_A::ADC2_ADC3_1X,
+        port::PA2, port::PA3, 20: crate::pac::adc::admux::MUX_A::ADC2_ADC3_20X,
+        port::PA3, port::PA4, 1: crate::pac::adc::admux::MUX_A::ADC3_ADC4_1X,
+        port::PA3, port::PA4, 20: crate::pac::adc::admux::MUX_A::ADC3_ADC4_20X,
+        port::PA3, port::PA5, 1: crate::pac::adc::admux::MUX_A::ADC3_ADC5_1X,
+        port::PA3, port::PA5, 20: crate::pac::adc::admux::MUX_A::ADC3_ADC5_20X,
+        port::PA3, port::PA6, 1: crate::pac::adc::admux::MUX_A::ADC3_ADC6_1X,
+        port::PA3, port::PA6, 20: crate::pac::adc::admux::MUX_A::ADC3_ADC6_20X,
+        port::PA3, port::PA7, 1: crate::pac::adc::admux::MUX_A::ADC3_ADC7_1X,
+        port::PA3, port::PA7, 20: crate::pac::adc::admux::MUX_A::ADC3_ADC7_20X,
+        port::PA4, port::PA5, 1: crate::pac::adc::admux::MUX_A::ADC4_ADC5_1X,
+        port::PA4, port::PA5, 20: crate::pac::adc::admux::MUX_A::ADC4_ADC5_20X,
+        port::PA5, port::PA6, 1: crate::pac::adc::admux::MUX_A::ADC5_ADC6_1X,
+        port::PA5, port::PA6, 20: crate::pac::adc::admux::MUX_A::ADC5_ADC6_20X,
+        port::PA6, port::PA7, 1: crate::pac::adc::admux::MUX_A::ADC6_ADC7_1X,
+        port::PA6, port::PA7, 20: crate::pac::adc::admux::MUX_A::ADC6_ADC7_20X,
+        port::PA1, port::PA0, 1: crate::pac::adc::admux::MUX_A::ADC1_ADC0_1X,
+        port::PA1, port::PA0, 20: crate::pac::adc::admux::MUX_A::ADC1_ADC0_20X,
+        port::PA3, port::PA0, 1: crate::pac::adc::admux::MUX_A::ADC3_ADC0_1X,
+        port::PA3, port::PA0, 20: crate::pac::adc::admux::MUX_A::ADC3_ADC0_20X,
+        port::PA2, port::PA1, 1: crate::pac::adc::admux::MUX_A::ADC2_ADC1_1X,
+        port::PA2, port::PA1, 20: crate::pac::adc::admux::MUX_A::ADC2_ADC1_20X,
+        port::PA3, port::PA1, 1: crate::pac::adc::admux::MUX_A::ADC3_ADC1_1X,
+        port::PA3, port::PA1, 20: crate::pac::adc::admux::MUX_A::ADC3_ADC1_20X,
+        port::PA3, port::PA2, 1: crate::pac::adc::admux::MUX_A::ADC3_ADC2_1X,
+        port::PA3, port::PA2, 20: crate::pac::adc::admux::MUX_A::ADC3_ADC2_20X,
+        port::PA4, port::PA3, 1: crate::pac::adc::admux::MUX_A::ADC4_ADC3_1X,
+        port::PA4, port::PA3, 20: crate::pac::adc::admux::MUX_A::ADC4_ADC3_20X,
+        port::PA5, port::PA3, 1: crate::pac::adc::admux::MUX_A::ADC5_ADC3_1X,
+        port::PA5, port::PA3, 20: crate::pac::adc::admux::MUX_A::ADC5_ADC3_20X,
+        port::PA6, port::PA3, 1: crate::pac::adc::admux::MUX_A::ADC6_ADC3_1X,
+        port::PA6, port::PA3, 20: crate::pac::adc::admux::MUX_A::ADC6_ADC3_20X,
+        port::PA7, port::PA3, 1: crate::pac::adc::admux::MUX_A::ADC7_ADC3_1X,
+        port::PA7, port::PA3, 20: crate::pac::adc::admux::MUX_A::ADC7_ADC3_20X,
+        port::PA5, port::PA4, 1: crate::pac::adc::admux::MUX_A::ADC5_ADC4_1X,
+        port::PA5, port::PA4, 20: crate::pac::adc::admux::MUX_A::ADC5_ADC4_20X,
+        port::PA6, port::PA5, 1: crate::pac::adc::admux::MUX_A::ADC6_ADC5_1X,
+        port::PA6, port::PA5, 20: crate::pac::adc::admux::MUX_A::ADC6_ADC5_20X,
+        port::PA7, port::PA6, 1: crate::pac::adc::admux::MUX_A::ADC7_ADC6_1X,
+        port::PA7, port::PA6, 20: crate::pac::adc::admux::MUX_A::ADC7_ADC6_20X,
+    },
 }
 
 #[cfg(feature = "attiny88")]
@@ -144,10 +473,35 @@ avr_hal_generic::impl_adc! {
             ReferenceVoltage::Internal1_1 => w.refs0().internal(),
         });
     },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| {
+        if peripheral.admux().read().refs0().is_avcc() {
+            ReferenceVoltage::AVcc
+        } else {
+            ReferenceVoltage::Internal1_1
+        }
+    },
+    set_reference: |peripheral, reference| {
+        peripheral.admux().modify(|_, w| match reference {
+            ReferenceVoltage::AVcc => w.refs0().avcc(),
+            ReferenceVoltage::Internal1_1 => w.refs0().internal(),
+        });
+    },
+    set_left_adjust: |peripheral, enable| {
+        peripheral.admux().modify(|_, w| w.adlar().bit(enable));
+    },
     channel_id: crate::pac::adc::admux::MUX_A,
     set_channel: |peripheral, id| {
         peripheral.admux().modify(|_, w| w.mux().variant(id));
     },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        if let Some(trigger) = trigger {
+            peripheral.adcsrb().modify(|_, w| w.adts().set(trigger as u8));
+        }
+        set_auto_trigger(peripheral, trigger);
+    },
+    clear_trigger: |trigger| { clear_trigger_flag(trigger) },
     pins: {
         port::PC0: (crate::pac::adc::admux::MUX_A::ADC0, didr0::adc0d),
         port::PC1: (crate::pac::adc::admux::MUX_A::ADC1, didr0::adc1d),
@@ -183,10 +537,41 @@ avr_hal_generic::impl_adc! {
             ReferenceVoltage::Internal2_56 => w.refs().internal_256(),
         });
     },
+    reference: ReferenceVoltage,
+    get_reference: |peripheral| {
+        if peripheral.amiscr().read().arefen().bit_is_set() {
+            return ReferenceVoltage::Aref;
+        }
+        match peripheral.admux().read().refs().bits() {
+            0 => ReferenceVoltage::AVcc,
+            1 => ReferenceVoltage::Internal1_1,
+            _ => ReferenceVoltage::Internal2_56,
+        }
+    },
+    set_reference: |peripheral, reference| {
+        peripheral.amiscr().modify(|_, w| w.arefen().bit(reference == ReferenceVoltage::Aref));
+        peripheral.admux().modify(|_, w| match reference {
+            ReferenceVoltage::Aref => w.refs().avcc(),
+            ReferenceVoltage::AVcc => w.refs().avcc(),
+            ReferenceVoltage::Internal1_1 => w.refs().internal_11(),
+            ReferenceVoltage::Internal2_56 => w.refs().internal_256(),
+        });
+    },
+    set_left_adjust: |peripheral, enable| {
+        peripheral.admux().modify(|_, w| w.adlar().bit(enable));
+    },
     channel_id: crate::pac::adc::admux::MUX_A,
     set_channel: |peripheral, id| {
         peripheral.admux().modify(|_, w| w.mux().variant(id));
     },
+    trigger: AutoTrigger,
+    set_trigger: |peripheral, trigger| {
+        if let Some(trigger) = trigger {
+            peripheral.adcsrb().modify(|_, w| w.adts().set(trigger as u8));
+        }
+        set_auto_trigger(peripheral, trigger);
+    },
+    clear_trigger: |trigger| { clear_trigger_flag(trigger) },
     pins: {
         port::PA0: (crate::pac::adc::admux::MUX_A::ADC0, didr0::adc0d),
         port::PA1: (crate::pac::adc::admux::MUX_A::ADC1, didr0::adc1d),
@@ -207,3 +592,45 @@ avr_hal_generic::impl_adc! {
         channel::Temperature: crate::pac::adc::admux::MUX_A::TEMPSENS,
     },
 }
+
+#[cfg(any(
+    feature = "attiny84",
+    feature = "attiny85",
+    feature = "attiny88",
+    feature = "attiny167"
+))]
+impl AdcTimerOps<crate::Attiny, crate::pac::ADC> for crate::pac::TC0 {
+    const TRIGGER: AutoTrigger = AutoTrigger::Timer0CompareA;
+    const COMPARE: avr_hal_generic::timer::OutputCompare = avr_hal_generic::timer::OutputCompare::A;
+}
+
+#[cfg(any(feature = "attiny88", feature = "attiny167"))]
+impl AdcTimerOps<crate::Attiny, crate::pac::ADC> for crate::pac::TC1 {
+    const TRIGGER: AutoTrigger = AutoTrigger::Timer1CompareB;
+    const COMPARE: avr_hal_generic::timer::OutputCompare = avr_hal_generic::timer::OutputCompare::B;
+}
+
+#[cfg(not(feature = "attiny2313"))]
+impl AdcSupplyOps<crate::Attiny> for crate::pac::ADC {
+    const SUPPLY_REFERENCE: ReferenceVoltage = ReferenceVoltage::AVcc;
+    const BANDGAP: Self::Channel = crate::pac::adc::admux::MUX_A::ADC_VBG;
+
+    #[cfg(not(feature = "attiny84"))]
+    const CALIBRATION: Calibration = Calibration {
+        bandgap_millivolts: 1100,
+        temperature_offset: 300,
+        temperature_slope: 1120,
+    };
+    #[cfg(feature = "attiny84")]
+    const CALIBRATION: Calibration = Calibration {
+        bandgap_millivolts: 1100,
+        temperature_offset: 324,
+        temperature_slope: 1552,
+    };
+}
+
+#[cfg(not(feature = "attiny2313"))]
+impl AdcTemperatureOps<crate::Attiny> for crate::pac::ADC {
+    const TEMPERATURE_REFERENCE: ReferenceVoltage = ReferenceVoltage::Internal1_1;
+    const TEMPERATURE: Self::Channel = crate::pac::adc::admux::MUX_A::TEMPSENS;
+}
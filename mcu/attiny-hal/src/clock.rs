@@ -0,0 +1,15 @@
+//! Core clock speed management
+//!
+//! Most items in this module are re-exported from [`avr_hal_generic::clock`].
+pub use avr_hal_generic::clock::*;
+
+/// Check the [`avr_hal_generic::clock::SystemClock`] documentation.
+pub type SystemClock<SOURCE, CLOCK> =
+    avr_hal_generic::clock::SystemClock<crate::Attiny, SOURCE, CLOCK>;
+
+#[cfg(feature = "device-selected")]
+avr_hal_generic::impl_clock_prescaler! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    register: clkpr,
+}
@@ -0,0 +1,97 @@
+//! Analog Comparator
+//!
+//! # Example
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//!
+//! let _ain0 = pins.pb0.into_floating_input();
+//! let _ain1 = pins.pb1.into_floating_input();
+//!
+//! let mut comparator = AnalogComparator::new(dp.AC);
+//!
+//! loop {
+//!     nb::block!(comparator.wait_event()).unwrap_infallible();
+//!     // AIN0 crossed AIN1
+//! }
+//! ```
+pub use avr_hal_generic::comparator::{
+    AnalogComparatorBandgapOps, AnalogComparatorCaptureOps, AnalogComparatorMuxOps,
+    AnalogComparatorOps, Event, PositiveInput,
+};
+
+/// Check the [`avr_hal_generic::comparator::AnalogComparator`] documentation.
+pub type AnalogComparator =
+    avr_hal_generic::comparator::AnalogComparator<crate::Attiny, crate::pac::AC>;
+
+/// Check the [`avr_hal_generic::comparator::MuxedAdc`] documentation.
+#[cfg(not(feature = "attiny2313"))]
+pub type MuxedAdc<CLOCK> =
+    avr_hal_generic::comparator::MuxedAdc<crate::Attiny, crate::pac::ADC, CLOCK>;
+
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_analog_comparator! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::AC,
+    bandgap: acbg,
+    input_capture: acic,
+    adc_multiplexer: {
+        peripheral: crate::pac::ADC,
+        set_enabled: |ac, adc, enable| {
+            adc.adcsra().modify(|_, w| w.aden().bit(!enable));
+            ac.adcsrb().modify(|_, w| w.acme().bit(enable));
+        },
+    },
+}
+
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_analog_comparator! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::AC,
+    bandgap: acbg,
+    adc_multiplexer: {
+        peripheral: crate::pac::ADC,
+        set_enabled: |ac, adc, enable| {
+            adc.adcsra().modify(|_, w| w.aden().bit(!enable));
+            ac.adcsrb().modify(|_, w| w.acme().bit(enable));
+        },
+    },
+}
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_analog_comparator! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::AC,
+    bandgap: acbg,
+    input_capture: acic,
+    adc_multiplexer: {
+        peripheral: crate::pac::ADC,
+        set_enabled: |_ac, adc, enable| {
+            adc.adcsra().modify(|_, w| w.aden().bit(!enable));
+            adc.adcsrb().modify(|_, w| w.acme().bit(enable));
+        },
+    },
+}
+
+#[cfg(feature = "attiny167")]
+avr_hal_generic::impl_analog_comparator! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::AC,
+    input_capture: acic,
+    adc_multiplexer: {
+        peripheral: crate::pac::ADC,
+        set_enabled: |ac, adc, enable| {
+            adc.adcsra().modify(|_, w| w.aden().bit(!enable));
+            ac.adcsrb().modify(|_, w| w.acme().bit(enable));
+        },
+    },
+}
+
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_analog_comparator! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::AC,
+    bandgap: acbg,
+    input_capture: acic,
+}
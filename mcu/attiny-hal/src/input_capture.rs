@@ -0,0 +1,112 @@
+//! Input capture unit of 16-bit timers
+//!
+//! Check the [`avr_hal_generic::input_capture`] documentation for details.
+//!
+//! The input capture unit is available on `TC1` of the following MCUs:
+//!
+//! | MCU | ICP pin |
+//! | --- | --- |
+//! | ATtiny84 | `PA7` |
+//! | ATtiny88 | `PB0` |
+//! | ATtiny167 | `PA4` |
+//! | ATtiny2313 | `PD6` |
+//!
+//! # Example
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//!
+//! let mut icp = InputCapture::<_, _, CoreClock>::new(
+//!     dp.TC1,
+//!     pins.pa7.into_floating_input(),
+//!     Prescaler::Prescale8,
+//! )
+//! .unwrap();
+//!
+//! // Width of the next high pulse, in timer ticks
+//! let ticks = icp.measure_pulse(Edge::Rising, 100.millis()).unwrap();
+//! ```
+
+pub use avr_hal_generic::input_capture::{
+    CaptureError, CaptureEvent, Edge, InputCaptureOps, PwmMeasurement,
+};
+
+/// Check the [`avr_hal_generic::input_capture::InputCapture`] documentation.
+pub type InputCapture<TC, IMODE, CLOCK> =
+    avr_hal_generic::input_capture::InputCapture<crate::Attiny, TC, IMODE, CLOCK>;
+
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PA7,
+    registers: {
+        control: tccr1b,
+        capture: icr1,
+        mask: timsk1,
+        flags: tifr1,
+    },
+    bits: {
+        edge: ices1,
+        noise_canceler: icnc1,
+        interrupt: icie1,
+        flag: icf1,
+    },
+}
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PB0,
+    registers: {
+        control: tccr1b,
+        capture: icr1,
+        mask: timsk1,
+        flags: tifr1,
+    },
+    bits: {
+        edge: ices1,
+        noise_canceler: icnc1,
+        interrupt: icie1,
+        flag: icf1,
+    },
+}
+
+#[cfg(feature = "attiny167")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PA4,
+    registers: {
+        control: tccr1b,
+        capture: icr1,
+        mask: timsk1,
+        flags: tifr1,
+    },
+    bits: {
+        edge: ices1,
+        noise_canceler: icnc1,
+        interrupt: icie1,
+        flag: icf1,
+    },
+}
+
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PD6,
+    registers: {
+        control: tccr1b,
+        capture: icr1,
+        mask: timsk,
+        flags: tifr,
+    },
+    bits: {
+        edge: ices1,
+        noise_canceler: icnc1,
+        interrupt: icie1,
+        flag: icf1,
+    },
+}
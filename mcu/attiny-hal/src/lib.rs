@@ -1,4 +1,5 @@
 #![no_std]
+#![cfg_attr(feature = "reset-cause", feature(asm_experimental_arch))]
 
 //! `attiny-hal`
 //! =============
@@ -60,7 +61,7 @@ pub use avr_device::entry;
 #[cfg(feature = "device-selected")]
 pub use pac::Peripherals;
 
-pub use avr_hal_generic::clock;
+pub mod clock;
 pub use avr_hal_generic::delay;
 pub use avr_hal_generic::prelude;
 
@@ -70,14 +71,51 @@ pub mod adc;
 #[cfg(all(feature = "device-selected", not(feature = "attiny2313")))]
 pub use adc::Adc;
 
+#[cfg(feature = "device-selected")]
+pub mod comparator;
+#[cfg(feature = "device-selected")]
+pub use comparator::AnalogComparator;
+
+#[cfg(feature = "device-selected")]
+pub mod input_capture;
+#[cfg(feature = "device-selected")]
+pub use input_capture::InputCapture;
+
 #[cfg(feature = "device-selected")]
 pub mod port;
 #[cfg(feature = "device-selected")]
 pub use port::Pins;
 
+#[cfg(feature = "device-selected")]
+pub mod osccal;
+
+#[cfg(feature = "device-selected")]
+pub mod power;
+
+#[cfg(feature = "device-selected")]
+pub mod reset;
+
+#[cfg(feature = "device-selected")]
+pub mod servo;
+
 #[cfg(feature = "device-selected")]
 pub mod simple_pwm;
 
+#[cfg(feature = "device-selected")]
+pub mod time;
+#[cfg(feature = "device-selected")]
+pub use time::SysTime;
+
+#[cfg(feature = "device-selected")]
+pub mod timer;
+#[cfg(feature = "device-selected")]
+pub use timer::Timer;
+
+#[cfg(feature = "attiny85")]
+pub mod tone;
+#[cfg(feature = "attiny85")]
+pub use tone::Tone;
+
 #[cfg(feature = "device-selected")]
 pub mod wdt;
 #[cfg(feature = "device-selected")]
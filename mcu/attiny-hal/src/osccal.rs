@@ -0,0 +1,29 @@
+//! Internal RC oscillator calibration
+//!
+//! See [`avr_hal_generic::osccal`] for details.
+//!
+//! # Example
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//! let mut cpu = dp.CPU;
+//!
+//! // Calibrate against 'U' bytes sent by the host at 9600 baud
+//! let mut timer = attiny_hal::Timer::<_, MHz8>::new(dp.TC0);
+//! let rx = pins.pb0.into_floating_input();
+//! let mut reference = osccal::UartReference::new(&mut timer, &rx, 9600);
+//! osccal::calibrate(&mut cpu, &mut reference).unwrap();
+//! ```
+#[allow(unused_imports)]
+pub use avr_hal_generic::osccal::{
+    calibrate, calibration, load_calibration, restore_calibration, set_calibration,
+    store_calibration, CalibrationError, CalibrationReference, CrystalReference, OscCalOps,
+    UartReference,
+};
+
+// The highest bit selects one of two overlapping frequency ranges, or is unused.
+avr_hal_generic::impl_osccal! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    tuning_mask: 0x7f,
+}
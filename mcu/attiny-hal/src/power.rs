@@ -0,0 +1,276 @@
+//! Sleep modes and power management
+//!
+//! # Example
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let mut cpu = dp.CPU;
+//! let mut exint = dp.EXINT;
+//!
+//! // Wake up when INT0 is pulled low
+//! power::wake_on_interrupt(&mut exint, ExternalInterrupt::Int0, Sense::LowLevel);
+//!
+//! loop {
+//!     // SAFETY: Not inside a critical section.
+//!     unsafe { power::sleep(&mut cpu, SleepMode::PowerDown) };
+//! }
+//!
+//! #[avr_device::interrupt(attiny85)]
+//! fn INT0() {}
+//! ```
+#[allow(unused_imports)]
+pub use avr_hal_generic::power::{
+    disable_wake_on_interrupt, disable_wake_on_pin_change, enable_interrupts_and_sleep,
+    set_async_timer_clock, set_powered, sleep, sleep_with_bod_disabled, wait_for_async_timer,
+    wake_on_interrupt, wake_on_pin_change, wake_on_watchdog, AsyncTimerOps, ExternalInterruptOps,
+    PinChangeOps, PowerReductionOps, Sense, SleepBodOps, SleepOps, WakeError,
+};
+
+/// Sleep mode entered by the `sleep` instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SleepMode {
+    /// Stop the CPU, all peripherals keep running.
+    Idle,
+    /// Stop the CPU and the I/O clock, leaving the ADC, the watchdog and external interrupts
+    /// running.
+    #[cfg(not(feature = "attiny2313"))]
+    AdcNoiseReduction,
+    /// Stop all clocks.  Only external and pin change interrupts, the USI start condition and the
+    /// watchdog can wake the CPU up.
+    PowerDown,
+    /// Like power-down, but the asynchronous timer keeps running.
+    #[cfg(feature = "attiny167")]
+    PowerSave,
+    /// Like power-down, but the main oscillator keeps running, so the CPU wakes up within six
+    /// cycles.  Only available with an external crystal or resonator.
+    #[cfg(any(feature = "attiny84", feature = "attiny2313"))]
+    Standby,
+}
+
+/// External interrupt (INTn)
+#[cfg(not(feature = "attiny2313"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalInterrupt {
+    Int0 = 0,
+    #[cfg(any(feature = "attiny88", feature = "attiny167"))]
+    Int1 = 1,
+}
+
+#[cfg(any(feature = "attiny88", feature = "attiny167"))]
+avr_hal_generic::impl_sleep! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    mode: SleepMode,
+    register: smcr,
+    set_mode: |mode, w| match mode {
+        SleepMode::Idle => w.sm().idle(),
+        SleepMode::AdcNoiseReduction => w.sm().adc(),
+        SleepMode::PowerDown => w.sm().pdown(),
+        #[cfg(feature = "attiny167")]
+        SleepMode::PowerSave => w.sm().psave(),
+    },
+}
+
+#[cfg(any(feature = "attiny84", feature = "attiny85"))]
+avr_hal_generic::impl_sleep! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    mode: SleepMode,
+    register: mcucr,
+    set_mode: |mode, w| match mode {
+        SleepMode::Idle => w.sm().idle(),
+        SleepMode::AdcNoiseReduction => w.sm().adc(),
+        SleepMode::PowerDown => w.sm().pdown(),
+        #[cfg(feature = "attiny84")]
+        SleepMode::Standby => w.sm().stdby(),
+    },
+}
+
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_sleep! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    mode: SleepMode,
+    register: mcucr,
+    set_mode: |mode, w| match mode {
+        SleepMode::Idle => w.sm().idle(),
+        SleepMode::PowerDown => w.sm().pdown(),
+        SleepMode::Standby => w.sm().stdby(),
+    },
+}
+
+#[cfg(any(feature = "attiny84", feature = "attiny85"))]
+avr_hal_generic::impl_sleep_bod_disable! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    register: mcucr,
+    bods: 7,
+    bodse: 2,
+}
+
+#[cfg(any(feature = "attiny88", feature = "attiny167"))]
+avr_hal_generic::impl_sleep_bod_disable! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    register: mcucr,
+    bods: 6,
+    bodse: 5,
+}
+
+#[cfg(not(feature = "attiny2313"))]
+impl avr_hal_generic::adc::AdcSleepOps<crate::Attiny, crate::pac::ADC> for crate::pac::CPU {
+    const NOISE_REDUCTION: SleepMode = SleepMode::AdcNoiseReduction;
+}
+
+/// Set the two sense control bits of INTn in an EICRA-like register.
+#[cfg(not(feature = "attiny2313"))]
+fn with_sense(bits: u8, n: u8, isc: u8) -> u8 {
+    let shift = 2 * n;
+    bits & !(0b11 << shift) | isc << shift
+}
+
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_external_interrupts! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    interrupt: ExternalInterrupt,
+    mask: gimsk,
+    flags: gifr,
+    bit: |int| match int {
+        ExternalInterrupt::Int0 => 6,
+    },
+    set_sense: |exint, int, isc| {
+        // SAFETY: Only the sense bits of this interrupt are changed.
+        exint
+            .mcucr()
+            .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), int as u8, isc)) });
+    },
+    pin_change: {
+        control: gimsk,
+        first_bit: 5,
+        masks: [0: pcmsk],
+    },
+}
+
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_external_interrupts! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    interrupt: ExternalInterrupt,
+    mask: gimsk,
+    flags: gifr,
+    bit: |int| match int {
+        ExternalInterrupt::Int0 => 6,
+    },
+    set_sense: |exint, int, isc| {
+        // SAFETY: Only the sense bits of this interrupt are changed.
+        exint
+            .mcucr()
+            .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), int as u8, isc)) });
+    },
+    pin_change: {
+        control: gimsk,
+        first_bit: 4,
+        masks: [0: pcmsk0, 1: pcmsk1],
+    },
+}
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_external_interrupts! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    interrupt: ExternalInterrupt,
+    mask: eimsk,
+    flags: eifr,
+    bit: |int| int as u8,
+    set_sense: |exint, int, isc| {
+        // SAFETY: Only the sense bits of this interrupt are changed.
+        exint
+            .eicra()
+            .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), int as u8, isc)) });
+    },
+    pin_change: {
+        control: pcicr,
+        first_bit: 0,
+        masks: [0: pcmsk0, 1: pcmsk1, 2: pcmsk2, 3: pcmsk3],
+    },
+}
+
+#[cfg(feature = "attiny167")]
+avr_hal_generic::impl_external_interrupts! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    interrupt: ExternalInterrupt,
+    mask: eimsk,
+    flags: eifr,
+    bit: |int| int as u8,
+    set_sense: |exint, int, isc| {
+        // SAFETY: Only the sense bits of this interrupt are changed.
+        exint
+            .eicra()
+            .modify(|r, w| unsafe { w.bits(with_sense(r.bits(), int as u8, isc)) });
+    },
+    pin_change: {
+        control: pcicr,
+        first_bit: 0,
+        masks: [0: pcmsk0, 1: pcmsk1],
+    },
+}
+
+#[cfg(feature = "attiny167")]
+avr_hal_generic::impl_async_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC0,
+    async_clock: as0,
+    busy_mask: 0x1b,
+}
+
+#[cfg(any(feature = "attiny84", feature = "attiny85"))]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    gates: [
+        crate::pac::ADC: prr.pradc,
+        crate::pac::USI: prr.prusi,
+        crate::pac::TC0: prr.prtim0,
+        crate::pac::TC1: prr.prtim1,
+    ],
+}
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    gates: [
+        crate::pac::ADC: prr.pradc,
+        crate::pac::SPI: prr.prspi,
+        crate::pac::TC0: prr.prtim0,
+        crate::pac::TC1: prr.prtim1,
+        crate::pac::TWI: prr.prtwi,
+    ],
+}
+
+#[cfg(feature = "attiny167")]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    gates: [
+        crate::pac::ADC: prr.pradc,
+        crate::pac::USI: prr.prusi,
+        crate::pac::SPI: prr.prspi,
+        crate::pac::TC0: prr.prtim0,
+        crate::pac::TC1: prr.prtim1,
+        crate::pac::LINUART: prr.prlin,
+    ],
+}
+
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_power_reduction! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    always_on: [
+        crate::pac::TC0,
+        crate::pac::TC1,
+        crate::pac::USART,
+        crate::pac::USI,
+    ],
+}
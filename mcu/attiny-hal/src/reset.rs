@@ -0,0 +1,37 @@
+//! Reset cause reporting
+//!
+//! Enable the `reset-cause` feature to capture the reset flags at startup, before a bootloader or
+//! the application had a chance to clear them.  See [`avr_hal_generic::reset`] for details.
+//!
+//! # Example
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//!
+//! if ResetCause::read(&dp.CPU).contains(ResetSource::Watchdog) {
+//!     // the firmware locked up before
+//! }
+//! ```
+pub use avr_hal_generic::reset::{ResetCause, ResetCauseOps, ResetSource};
+
+#[cfg(all(feature = "reset-cause", feature = "device-selected"))]
+avr_hal_generic::impl_reset_cause_hook! {
+    // MCUSR is at the same address on all MCUs.
+    io_address: 0x34,
+}
+
+#[cfg(feature = "reset-cause")]
+fn snapshot() -> Option<u8> {
+    Some(reset_cause_snapshot())
+}
+
+#[cfg(not(feature = "reset-cause"))]
+fn snapshot() -> Option<u8> {
+    None
+}
+
+avr_hal_generic::impl_reset_cause! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::CPU,
+    register: mcusr,
+    snapshot: snapshot(),
+}
@@ -1,4 +1,10 @@
-pub use avr_hal_generic::simple_pwm::{IntoPwmPin, Prescaler, PwmPinOps};
+pub use avr_hal_generic::simple_pwm::{
+    ComplementaryPwm, ComplementaryPwmOps, DeadTimePrescaler, IntoPwmPin, Prescaler, Pwm16Ops,
+    PwmError, PwmMode, PwmPinOps, PwmTimer,
+};
+
+/// Check the [`avr_hal_generic::simple_pwm::Pwm16`] documentation.
+pub type Pwm16<TC, CLOCK> = avr_hal_generic::simple_pwm::Pwm16<crate::Attiny, TC, CLOCK>;
 
 #[cfg(any(feature = "attiny85", feature = "attiny84", feature = "attiny88"))]
 use crate::port::*;
@@ -7,9 +13,14 @@ use crate::port::*;
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC0` for PWM (pins `PB2`, `PA7`)
     pub struct Timer0Pwm {
+        hal: crate::Attiny,
         timer: crate::pac::TC0,
-        init: |tim, prescaler| {
-            tim.tccr0a().modify(|_r, w| w.wgm0().pwm_fast());
+        modes: [Fast, PhaseCorrect],
+        init: |tim, prescaler, mode| {
+            tim.tccr0a().modify(|_r, w| match mode {
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+                _ => w.wgm0().pwm_fast(),
+            });
             tim.tccr0b().modify(|_r, w| match prescaler {
                 Prescaler::Direct => w.cs0().direct(),
                 Prescaler::Prescale8 => w.cs0().prescale_8(),
@@ -21,8 +32,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB2: {
                 ocr: ocr0a,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0a().match_set()
+                    } else {
+                        w.com0a().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0a().disconnected());
                 },
@@ -30,8 +45,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PA7: {
                 ocr: ocr0b,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0b().match_set()
+                    } else {
+                        w.com0b().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0b().disconnected());
                 },
@@ -44,10 +63,13 @@ avr_hal_generic::impl_simple_pwm! {
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC1` for PWM (pins `PA6`, 'PA5')
     pub struct Timer1Pwm {
+        hal: crate::Attiny,
         timer: crate::pac::TC1,
-        init: |tim, prescaler| {
-            tim.tccr1a().modify(|_, w| w.wgm1().set(0b01));
-            tim.tccr1b().modify(|_, w| w.wgm1().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr1,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr1a().modify(|_, w| w.wgm1().set(wgm_a));
+            tim.tccr1b().modify(|_, w| w.wgm1().set(wgm_b));
 
             tim.tccr1b().modify(|_r, w| match prescaler {
                 Prescaler::Direct => w.cs1().direct(),
@@ -60,8 +82,8 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PA6: {
                 ocr: ocr1a,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_, w| w.com1a().set(0b10));
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_, w| w.com1a().set(if inverted { 0b11 } else { 0b10 }));
                 } else {
                     tim.tccr1a().modify(|_, w| w.com1a().disconnected());
                 },
@@ -69,8 +91,8 @@ avr_hal_generic::impl_simple_pwm! {
 
             PA5: {
                 ocr: ocr1b,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_, w| w.com1b().set(0b10));
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_, w| w.com1b().set(if inverted { 0b11 } else { 0b10 }));
                 } else {
                     tim.tccr1a().modify(|_, w| w.com1b().disconnected());
                 },
@@ -94,9 +116,14 @@ avr_hal_generic::impl_simple_pwm! {
     /// d0.enable();
     /// ```
     pub struct Timer0Pwm {
+        hal: crate::Attiny,
         timer: crate::pac::TC0,
-        init: |tim, prescaler| {
-            tim.tccr0a().modify(|_r, w| w.wgm0().pwm_fast());
+        modes: [Fast, PhaseCorrect],
+        init: |tim, prescaler, mode| {
+            tim.tccr0a().modify(|_r, w| match mode {
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+                _ => w.wgm0().pwm_fast(),
+            });
             tim.tccr0b().modify(|_r, w| match prescaler {
                 Prescaler::Direct => w.cs0().direct(),
                 Prescaler::Prescale8 => w.cs0().prescale_8(),
@@ -108,8 +135,12 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB0: {
                 ocr: ocr0a,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0a().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0a().match_set()
+                    } else {
+                        w.com0a().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0a().disconnected());
                 },
@@ -117,8 +148,12 @@ avr_hal_generic::impl_simple_pwm! {
 
             PB1: {
                 ocr: ocr0b,
-                into_pwm: |tim| if enable {
-                    tim.tccr0a().modify(|_r, w| w.com0b().match_clear());
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr0a().modify(|_r, w| if inverted {
+                        w.com0b().match_set()
+                    } else {
+                        w.com0b().match_clear()
+                    });
                 } else {
                     tim.tccr0a().modify(|_r, w| w.com0b().disconnected());
                 },
@@ -129,7 +164,9 @@ avr_hal_generic::impl_simple_pwm! {
 
 #[cfg(feature = "attiny85")]
 avr_hal_generic::impl_simple_pwm! {
-    /// Use `TC1` for PWM (pins `PB4`)
+    /// Use `TC1` for PWM (pins `PB1`, `PB4`)
+    ///
+    /// The complementary outputs `PB0` and `PB3` are available through [`ComplementaryPwm`].
     ///
     /// # Example
     /// ```
@@ -141,8 +178,11 @@ avr_hal_generic::impl_simple_pwm! {
     /// d4.enable();
     /// ```
     pub struct Timer1Pwm {
+        hal: crate::Attiny,
         timer: crate::pac::TC1,
-        init: |tim, prescaler| {
+        modes: [Fast],
+        init: |tim, prescaler, _mode| {
+            tim.tccr1().modify(|_, w| w.pwm1a().set_bit());
             tim.gtccr().modify(|_, w| w.pwm1b().bit(true));
 
             tim.tccr1().modify(|_r, w| match prescaler {
@@ -154,10 +194,19 @@ avr_hal_generic::impl_simple_pwm! {
             });
         },
         pins: {
+            PB1: {
+                ocr: ocr1a,
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1().modify(|_, w| w.com1a().set(if inverted { 0b11 } else { 0b10 }));
+                } else {
+                    tim.tccr1().modify(|_, w| w.com1a().disconnected());
+                },
+            },
+
             PB4: {
                 ocr: ocr1b,
-                into_pwm: |tim| if enable {
-                    tim.gtccr().modify(|_, w| w.com1b().set(0b10));
+                into_pwm: |tim, inverted| if enable {
+                    tim.gtccr().modify(|_, w| w.com1b().set(if inverted { 0b11 } else { 0b10 }));
                 } else {
                     tim.gtccr().modify(|_, w| w.com1b().disconnected());
                 },
@@ -166,6 +215,308 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_complementary_pwm! {
+    timer: Timer1Pwm,
+    peripheral: crate::pac::TC1,
+    pins: {
+        PB1: {
+            complement: PB0,
+            enable: |tim| {
+                // COM1A = 0b01: OC1A and !OC1A connected
+                tim.tccr1().modify(|_, w| w.com1a().set(0b01));
+            },
+            dead_time: |tim, prescaler, output, complement| {
+                set_dead_time_prescaler_tc1(tim, prescaler);
+                tim.dt1a().write(|w| w.dtvh().set(output).dtvl().set(complement));
+            },
+        },
+
+        PB4: {
+            complement: PB3,
+            enable: |tim| {
+                // COM1B = 0b01: OC1B and !OC1B connected
+                tim.gtccr().modify(|_, w| w.com1b().set(0b01));
+            },
+            dead_time: |tim, prescaler, output, complement| {
+                set_dead_time_prescaler_tc1(tim, prescaler);
+                tim.dt1b().write(|w| w.dtvh().set(output).dtvl().set(complement));
+            },
+        },
+    },
+}
+
+/// The dead-time prescaler of `TC1` is shared by both channels.
+#[cfg(feature = "attiny85")]
+fn set_dead_time_prescaler_tc1(tim: &crate::pac::tc1::RegisterBlock, prescaler: DeadTimePrescaler) {
+    tim.dtps().write(|w| match prescaler {
+        DeadTimePrescaler::Direct => w.dtps().direct(),
+        DeadTimePrescaler::Prescale2 => w.dtps().prescale_2(),
+        DeadTimePrescaler::Prescale4 => w.dtps().prescale_4(),
+        DeadTimePrescaler::Prescale8 => w.dtps().prescale_8(),
+    });
+}
+
+/// Frequency of the PLL clock (`PCK`) which drives [`Timer1PllPwm`].
+#[cfg(feature = "attiny85")]
+pub const PLL_FREQUENCY: u32 = 64_000_000;
+
+/// Use `TC1` clocked from the 64 MHz PLL for high-speed PWM (pins `PB1`, `PB4`)
+///
+/// The counter counts from 0 to the TOP value in `OCR1C`, so the PWM frequency is
+/// `64 MHz / (prescaler * (top + 1))`: up to 250 kHz with the full 8-bit duty range, and more
+/// with a lower TOP.  The maximum duty of the pins is TOP.
+///
+/// The complementary outputs `PB0` and `PB3` are available through [`ComplementaryPwm`].
+///
+/// # Example
+/// ```
+/// let timer1 = Timer1PllPwm::new(dp.TC1, &dp.CPU, 200.kHz()).unwrap();
+///
+/// let mut d4 = pins.d4.into_output().into_pwm(&timer1);
+///
+/// d4.set_duty(d4.get_max_duty() / 2);
+/// d4.enable();
+/// ```
+#[cfg(feature = "attiny85")]
+pub struct Timer1PllPwm {
+    timer: crate::pac::TC1,
+    prescaler: crate::timer::Prescaler,
+    top: u8,
+}
+
+#[cfg(feature = "attiny85")]
+impl Timer1PllPwm {
+    /// Start PWM with the highest resolution available for `frequency`.
+    pub fn new(
+        timer: crate::pac::TC1,
+        cpu: &crate::pac::CPU,
+        frequency: crate::timer::Hertz,
+    ) -> Result<Self, crate::timer::TimerError> {
+        let (prescaler, top) = Self::settings(frequency)?;
+        Ok(Self::with_top(timer, cpu, prescaler, top))
+    }
+
+    /// Start PWM with the given prescaler and TOP value.
+    pub fn with_top(
+        mut timer: crate::pac::TC1,
+        cpu: &crate::pac::CPU,
+        prescaler: crate::timer::Prescaler,
+        top: u8,
+    ) -> Self {
+        crate::power::PowerReductionOps::<crate::Attiny>::raw_set_powered(&mut timer, true);
+        enable_pll(cpu);
+
+        timer.tccr1().modify(|_, w| w.pwm1a().set_bit());
+        timer.gtccr().modify(|_, w| w.pwm1b().set_bit());
+
+        let mut t = Self {
+            timer,
+            prescaler,
+            top,
+        };
+        t.set_top(prescaler, top);
+        t
+    }
+
+    /// Change the PWM frequency, choosing the highest resolution available.
+    ///
+    /// The duty cycles of the pins are not rescaled, so set them again afterwards.
+    pub fn set_frequency(
+        &mut self,
+        frequency: crate::timer::Hertz,
+    ) -> Result<(), crate::timer::TimerError> {
+        let (prescaler, top) = Self::settings(frequency)?;
+        self.set_top(prescaler, top);
+        Ok(())
+    }
+
+    /// Change the prescaler and the TOP value.
+    pub fn set_top(&mut self, prescaler: crate::timer::Prescaler, top: u8) {
+        use crate::timer::Prescaler;
+
+        self.timer.tccr1().modify(|_, w| w.cs1().no_clock());
+        self.timer.ocr1c().write(|w| w.set(top));
+        self.timer.tcnt1().write(|w| w.set(0));
+        self.timer.tccr1().modify(|_, w| match prescaler {
+            Prescaler::Direct => w.cs1().direct(),
+            Prescaler::Prescale8 => w.cs1().prescale_8(),
+            Prescaler::Prescale32 => w.cs1().prescale_32(),
+            Prescaler::Prescale64 => w.cs1().prescale_64(),
+            Prescaler::Prescale128 => w.cs1().prescale_128(),
+            Prescaler::Prescale256 => w.cs1().prescale_256(),
+            Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+        });
+
+        self.prescaler = prescaler;
+        self.top = top;
+    }
+
+    /// The prescaler of the PLL clock.
+    #[inline]
+    pub fn prescaler(&self) -> crate::timer::Prescaler {
+        self.prescaler
+    }
+
+    /// TOP value of the counter, which is also the maximum duty.
+    #[inline]
+    pub fn top(&self) -> u8 {
+        self.top
+    }
+
+    /// The PWM frequency.
+    pub fn frequency(&self) -> crate::timer::Hertz {
+        crate::timer::Hertz::from_raw(
+            PLL_FREQUENCY / (self.prescaler.divider() * (self.top as u32 + 1)),
+        )
+    }
+
+    /// Give up the PWM timer and return the timer peripheral.
+    ///
+    /// The timer keeps running from the PLL, so PWM outputs which were already set up continue
+    /// to work.
+    pub fn into_timer(self) -> crate::pac::TC1 {
+        self.timer
+    }
+
+    fn settings(
+        frequency: crate::timer::Hertz,
+    ) -> Result<(crate::timer::Prescaler, u8), crate::timer::TimerError> {
+        use crate::timer::TimerOps;
+
+        let hz = frequency.to_Hz();
+        if hz == 0 {
+            return Err(crate::timer::TimerError::PeriodTooLong);
+        }
+        let (prescaler, top) = crate::timer::Prescaler::for_cycles(
+            PLL_FREQUENCY / hz,
+            <crate::pac::TC1 as TimerOps<crate::Attiny>>::PRESCALERS,
+            u8::MAX as u16,
+        )?;
+        Ok((prescaler, top as u8))
+    }
+}
+
+/// Start the PLL and switch `TC1` to the PLL clock.
+#[cfg(feature = "attiny85")]
+fn enable_pll(cpu: &crate::pac::CPU) {
+    if cpu.pllcsr().read().pcke().bit_is_set() {
+        return;
+    }
+    cpu.pllcsr().modify(|_, w| w.plle().set_bit());
+    // The PLL must run for about 100 µs before the lock detector is valid.  2000 cycles are
+    // enough for all clock speeds of this chip.
+    avr_hal_generic::avr_device::asm::delay_cycles(2000);
+    while cpu.pllcsr().read().plock().bit_is_clear() {}
+    cpu.pllcsr().modify(|_, w| w.pcke().set_bit());
+}
+
+#[cfg(feature = "attiny85")]
+macro_rules! impl_timer1_pll_pin {
+    (
+        $PXi:ident: {
+            ocr: $ocr:ident,
+            enable: |$tim:ident, $com:ident| $enable_block:block,
+        },
+    ) => {
+        impl PwmPinOps<Timer1PllPwm> for $PXi {
+            type Duty = u8;
+
+            fn enable(&mut self) {
+                // SAFETY: The read-modify-write sequence is wrapped in a critical section.
+                avr_hal_generic::avr_device::interrupt::free(|_| {
+                    let $tim = unsafe { &*crate::pac::TC1::ptr() };
+                    let $com = 0b10;
+                    $enable_block
+                });
+            }
+
+            fn enable_inverted(&mut self) {
+                // SAFETY: See `enable()`.
+                avr_hal_generic::avr_device::interrupt::free(|_| {
+                    let $tim = unsafe { &*crate::pac::TC1::ptr() };
+                    let $com = 0b11;
+                    $enable_block
+                });
+            }
+
+            fn disable(&mut self) {
+                // SAFETY: See `enable()`.
+                avr_hal_generic::avr_device::interrupt::free(|_| {
+                    let $tim = unsafe { &*crate::pac::TC1::ptr() };
+                    let $com = 0b00;
+                    $enable_block
+                });
+            }
+
+            fn get_duty(&self) -> Self::Duty {
+                unsafe { &*crate::pac::TC1::ptr() }.$ocr().read().bits()
+            }
+
+            fn get_max_duty(&self) -> Self::Duty {
+                unsafe { &*crate::pac::TC1::ptr() }.ocr1c().read().bits()
+            }
+
+            fn set_duty(&mut self, duty: Self::Duty) {
+                // SAFETY: This register is exclusively used here so there are no concurrency
+                // issues.
+                unsafe { &*crate::pac::TC1::ptr() }
+                    .$ocr()
+                    .write(|w| w.set(duty));
+            }
+        }
+    };
+}
+
+#[cfg(feature = "attiny85")]
+impl_timer1_pll_pin! {
+    PB1: {
+        ocr: ocr1a,
+        enable: |tim, com| {
+            tim.tccr1().modify(|_, w| w.com1a().set(com));
+        },
+    },
+}
+
+#[cfg(feature = "attiny85")]
+impl_timer1_pll_pin! {
+    PB4: {
+        ocr: ocr1b,
+        enable: |tim, com| {
+            tim.gtccr().modify(|_, w| w.com1b().set(com));
+        },
+    },
+}
+
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_complementary_pwm! {
+    timer: Timer1PllPwm,
+    peripheral: crate::pac::TC1,
+    pins: {
+        PB1: {
+            complement: PB0,
+            enable: |tim| {
+                tim.tccr1().modify(|_, w| w.com1a().set(0b01));
+            },
+            dead_time: |tim, prescaler, output, complement| {
+                set_dead_time_prescaler_tc1(tim, prescaler);
+                tim.dt1a().write(|w| w.dtvh().set(output).dtvl().set(complement));
+            },
+        },
+
+        PB4: {
+            complement: PB3,
+            enable: |tim| {
+                tim.gtccr().modify(|_, w| w.com1b().set(0b01));
+            },
+            dead_time: |tim, prescaler, output, complement| {
+                set_dead_time_prescaler_tc1(tim, prescaler);
+                tim.dt1b().write(|w| w.dtvh().set(output).dtvl().set(complement));
+            },
+        },
+    },
+}
+
 #[cfg(feature = "attiny88")]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC1` for PWM (pins `PB1`, 'PB2')
@@ -181,10 +532,13 @@ avr_hal_generic::impl_simple_pwm! {
     /// d9.enable();
     /// ```
     pub struct Timer1Pwm {
+        hal: crate::Attiny,
         timer: crate::pac::TC1,
-        init: |tim, prescaler| {
-            tim.tccr1a().modify(|_, w| w.wgm1().set(0b01));
-            tim.tccr1b().modify(|_, w| w.wgm1().set(0b01));
+        modes: [Fast, PhaseCorrect, PhaseFrequencyCorrect],
+        top: icr1,
+        init: |tim, prescaler, wgm_a, wgm_b| {
+            tim.tccr1a().modify(|_, w| w.wgm1().set(wgm_a));
+            tim.tccr1b().modify(|_, w| w.wgm1().set(wgm_b));
 
             tim.tccr1b().modify(|_r, w| match prescaler {
                 Prescaler::Direct => w.cs1().direct(),
@@ -197,8 +551,8 @@ avr_hal_generic::impl_simple_pwm! {
         pins: {
             PB1: {
                 ocr: ocr1a,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_, w| w.com1a().set(0b10));
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_, w| w.com1a().set(if inverted { 0b11 } else { 0b10 }));
                 } else {
                     tim.tccr1a().modify(|_, w| w.com1a().disconnected());
                 },
@@ -206,8 +560,8 @@ avr_hal_generic::impl_simple_pwm! {
 
             PB2: {
                 ocr: ocr1b,
-                into_pwm: |tim| if enable {
-                    tim.tccr1a().modify(|_, w| w.com1b().set(0b10));
+                into_pwm: |tim, inverted| if enable {
+                    tim.tccr1a().modify(|_, w| w.com1b().set(if inverted { 0b11 } else { 0b10 }));
                 } else {
                     tim.tccr1a().modify(|_, w| w.com1b().disconnected());
                 },
@@ -215,3 +569,63 @@ avr_hal_generic::impl_simple_pwm! {
         },
     }
 }
+
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Attiny,
+    timer: crate::pac::TC1,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
+        tim.tccr1b().modify(|_r, w| w.wgm1().set(wgm_b));
+    },
+    top: icr1,
+    pins: {
+        PA6: {
+            ocr: ocr1a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_, w| w.com1a().set(if inverted { 0b11 } else { 0b10 }));
+            } else {
+                tim.tccr1a().modify(|_, w| w.com1a().disconnected());
+            },
+        },
+
+        PA5: {
+            ocr: ocr1b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_, w| w.com1b().set(if inverted { 0b11 } else { 0b10 }));
+            } else {
+                tim.tccr1a().modify(|_, w| w.com1b().disconnected());
+            },
+        },
+    },
+}
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_simple_pwm_16! {
+    hal: crate::Attiny,
+    timer: crate::pac::TC1,
+    init: |tim, wgm_a, wgm_b| {
+        tim.tccr1a().modify(|_r, w| w.wgm1().set(wgm_a));
+        tim.tccr1b().modify(|_r, w| w.wgm1().set(wgm_b));
+    },
+    top: icr1,
+    pins: {
+        PB1: {
+            ocr: ocr1a,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_, w| w.com1a().set(if inverted { 0b11 } else { 0b10 }));
+            } else {
+                tim.tccr1a().modify(|_, w| w.com1a().disconnected());
+            },
+        },
+
+        PB2: {
+            ocr: ocr1b,
+            into_pwm: |tim, inverted| if enable {
+                tim.tccr1a().modify(|_, w| w.com1b().set(if inverted { 0b11 } else { 0b10 }));
+            } else {
+                tim.tccr1a().modify(|_, w| w.com1b().disconnected());
+            },
+        },
+    },
+}
@@ -0,0 +1,277 @@
+//! General-purpose Timer/Counter
+//!
+//! Check the [`avr_hal_generic::timer`] documentation for details.
+//!
+//! # Example
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//!
+//! let mut timer = Timer::<_, CoreClock>::new(dp.TC0);
+//! timer.start(10.Hz()).unwrap();
+//!
+//! loop {
+//!     nb::block!(timer.wait()).unwrap_infallible();
+//!     // Executed 10 times per second
+//! }
+//! ```
+
+pub use avr_hal_generic::timer::{
+    Duration, Event, Hertz, OutputCompare, Prescaler, TimerError, TimerMode, TimerOps,
+};
+
+/// Check the [`avr_hal_generic::timer::Timer`] documentation.
+pub type Timer<TC, CLOCK> = avr_hal_generic::timer::Timer<crate::Attiny, TC, CLOCK>;
+
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr0b().modify(|_, w| match prescaler {
+            Some(Prescaler::Direct) => w.cs0().direct(),
+            Some(Prescaler::Prescale8) => w.cs0().prescale_8(),
+            Some(Prescaler::Prescale64) => w.cs0().prescale_64(),
+            Some(Prescaler::Prescale256) => w.cs0().prescale_256(),
+            Some(Prescaler::Prescale1024) => w.cs0().prescale_1024(),
+            _ => w.cs0().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr0a().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm0().normal_top(),
+            TimerMode::Ctc => w.wgm0().ctc(),
+        });
+        tim.tccr0b().modify(|_, w| w.wgm02().clear_bit());
+    },
+    registers: {
+        counter: tcnt0,
+        compare_a: ocr0a,
+        compare_b: ocr0b,
+        mask: timsk0,
+        flags: tifr0,
+    },
+    interrupts: {
+        overflow: (toie0, tov0),
+        compare_a: (ocie0a, ocf0a),
+        compare_b: (ocie0b, ocf0b),
+    },
+}
+
+#[cfg(any(feature = "attiny85", feature = "attiny2313"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr0b().modify(|_, w| match prescaler {
+            Some(Prescaler::Direct) => w.cs0().direct(),
+            Some(Prescaler::Prescale8) => w.cs0().prescale_8(),
+            Some(Prescaler::Prescale64) => w.cs0().prescale_64(),
+            Some(Prescaler::Prescale256) => w.cs0().prescale_256(),
+            Some(Prescaler::Prescale1024) => w.cs0().prescale_1024(),
+            _ => w.cs0().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr0a().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm0().normal_top(),
+            TimerMode::Ctc => w.wgm0().ctc(),
+        });
+        tim.tccr0b().modify(|_, w| w.wgm02().clear_bit());
+    },
+    registers: {
+        counter: tcnt0,
+        compare_a: ocr0a,
+        compare_b: ocr0b,
+        mask: timsk,
+        flags: tifr,
+    },
+    interrupts: {
+        overflow: (toie0, tov0),
+        compare_a: (ocie0a, ocf0a),
+        compare_b: (ocie0b, ocf0b),
+    },
+}
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr0a().modify(|_, w| match prescaler {
+            Some(Prescaler::Direct) => w.cs0().direct(),
+            Some(Prescaler::Prescale8) => w.cs0().prescale_8(),
+            Some(Prescaler::Prescale64) => w.cs0().prescale_64(),
+            Some(Prescaler::Prescale256) => w.cs0().prescale_256(),
+            Some(Prescaler::Prescale1024) => w.cs0().prescale_1024(),
+            _ => w.cs0().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr0a().modify(|_, w| w.ctc0().bit(mode == TimerMode::Ctc));
+    },
+    registers: {
+        counter: tcnt0,
+        compare_a: ocr0a,
+        compare_b: ocr0b,
+        mask: timsk0,
+        flags: tifr0,
+    },
+    interrupts: {
+        overflow: (toie0, tov0),
+        compare_a: (ocie0a, ocf0a),
+        compare_b: (ocie0b, ocf0b),
+    },
+}
+
+#[cfg(feature = "attiny167")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale32, Prescale64, Prescale128, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr0b().modify(|_, w| match prescaler {
+            Some(Prescaler::Direct) => w.cs0().direct(),
+            Some(Prescaler::Prescale8) => w.cs0().prescale_8(),
+            Some(Prescaler::Prescale32) => w.cs0().prescale_32(),
+            Some(Prescaler::Prescale64) => w.cs0().prescale_64(),
+            Some(Prescaler::Prescale128) => w.cs0().prescale_128(),
+            Some(Prescaler::Prescale256) => w.cs0().prescale_256(),
+            Some(Prescaler::Prescale1024) => w.cs0().prescale_1024(),
+            None => w.cs0().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr0a().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm0().set(0b00),
+            TimerMode::Ctc => w.wgm0().set(0b10),
+        });
+    },
+    registers: {
+        counter: tcnt0,
+        compare_a: ocr0a,
+        mask: timsk0,
+        flags: tifr0,
+    },
+    interrupts: {
+        overflow: (toie0, tov0),
+        compare_a: (ocie0a, ocf0a),
+    },
+}
+
+#[cfg(any(feature = "attiny84", feature = "attiny88", feature = "attiny167"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr1b().modify(|_, w| match prescaler {
+            Some(Prescaler::Direct) => w.cs1().direct(),
+            Some(Prescaler::Prescale8) => w.cs1().prescale_8(),
+            Some(Prescaler::Prescale64) => w.cs1().prescale_64(),
+            Some(Prescaler::Prescale256) => w.cs1().prescale_256(),
+            Some(Prescaler::Prescale1024) => w.cs1().prescale_1024(),
+            _ => w.cs1().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr1a().modify(|_, w| w.wgm1().set(0b00));
+        tim.tccr1b().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm1().set(0b00),
+            TimerMode::Ctc => w.wgm1().set(0b01),
+        });
+    },
+    registers: {
+        counter: tcnt1,
+        compare_a: ocr1a,
+        compare_b: ocr1b,
+        mask: timsk1,
+        flags: tifr1,
+    },
+    interrupts: {
+        overflow: (toie1, tov1),
+        compare_a: (ocie1a, ocf1a),
+        compare_b: (ocie1b, ocf1b),
+    },
+}
+
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    prescalers: [Direct, Prescale8, Prescale64, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr1b().modify(|_, w| match prescaler {
+            Some(Prescaler::Direct) => w.cs1().direct(),
+            Some(Prescaler::Prescale8) => w.cs1().prescale_8(),
+            Some(Prescaler::Prescale64) => w.cs1().prescale_64(),
+            Some(Prescaler::Prescale256) => w.cs1().prescale_256(),
+            Some(Prescaler::Prescale1024) => w.cs1().prescale_1024(),
+            _ => w.cs1().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        tim.tccr1a().modify(|_, w| w.wgm1().set(0b00));
+        tim.tccr1b().modify(|_, w| match mode {
+            TimerMode::Normal => w.wgm1().set(0b00),
+            TimerMode::Ctc => w.wgm1().set(0b01),
+        });
+    },
+    registers: {
+        counter: tcnt1,
+        compare_a: ocr1a,
+        compare_b: ocr1b,
+        mask: timsk,
+        flags: tifr,
+    },
+    interrupts: {
+        overflow: (toie1, tov1),
+        compare_a: (ocie1a, ocf1a),
+        compare_b: (ocie1b, ocf1b),
+    },
+}
+
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    counter: u8,
+    prescalers: [Direct, Prescale8, Prescale32, Prescale64, Prescale128, Prescale256, Prescale1024],
+    set_clock: |tim, prescaler| {
+        tim.tccr1().modify(|_, w| match prescaler {
+            Some(Prescaler::Direct) => w.cs1().direct(),
+            Some(Prescaler::Prescale8) => w.cs1().prescale_8(),
+            Some(Prescaler::Prescale32) => w.cs1().prescale_32(),
+            Some(Prescaler::Prescale64) => w.cs1().prescale_64(),
+            Some(Prescaler::Prescale128) => w.cs1().prescale_128(),
+            Some(Prescaler::Prescale256) => w.cs1().prescale_256(),
+            Some(Prescaler::Prescale1024) => w.cs1().prescale_1024(),
+            None => w.cs1().no_clock(),
+        });
+    },
+    set_mode: |tim, mode| {
+        // In CTC mode, the counter is cleared after a match with OCR1C.
+        tim.tccr1().modify(|_, w| w.ctc1().bit(mode == TimerMode::Ctc));
+    },
+    registers: {
+        counter: tcnt1,
+        compare_a: ocr1a,
+        compare_b: ocr1b,
+        top: ocr1c,
+        mask: timsk,
+        flags: tifr,
+    },
+    interrupts: {
+        overflow: (toie1, tov1),
+        compare_a: (ocie1a, ocf1a),
+        compare_b: (ocie1b, ocf1b),
+    },
+}
@@ -0,0 +1,34 @@
+//! Square-wave tone generation
+//!
+//! Check the [`avr_hal_generic::tone`] documentation for details.
+//!
+//! Tones are supported on `TC1` of the ATtiny85, whose hardware-toggled output is `OC1A` (`PB1`).
+//!
+//! # Example
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//!
+//! let mut tone = Tone::<_, CoreClock, _>::new(dp.TC1, pins.pb1.into_output());
+//! tone.play(440.Hz()).unwrap();
+//! ```
+
+pub use avr_hal_generic::tone::{Note, ToneOps};
+
+/// Check the [`avr_hal_generic::tone::Tone`] documentation.
+pub type Tone<TC, CLOCK, PIN> = avr_hal_generic::tone::Tone<crate::Attiny, TC, CLOCK, PIN>;
+
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    pin: crate::port::PB1,
+    set_toggle: |tim, enable| {
+        // The toggle output only works with PWM disabled.
+        tim.tccr1().modify(|_, w| if enable {
+            w.pwm1a().clear_bit().com1a().match_toggle()
+        } else {
+            w.com1a().disconnected()
+        });
+    },
+}